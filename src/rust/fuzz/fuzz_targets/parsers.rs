@@ -0,0 +1,9 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    gcatcirc::fuzzing::code_string(data);
+    gcatcirc::fuzzing::fasta(data);
+    gcatcirc::fuzzing::checkpoint_and_transformation(data);
+});
@@ -0,0 +1,269 @@
+use std::collections::BTreeMap;
+
+use extendr_api::prelude::*;
+
+use crate::genetic_code::AMINO_ACIDS;
+use crate::guard::guard;
+
+/// The names of the alphabet presets, see [Alphabet::preset]
+pub(crate) const PRESETS: [&str; 4] = ["dna", "rna", "binary", "amino_acid"];
+
+/// An alphabet, i.e. the letters words are built of, with an optional complement map
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Alphabet {
+    letters: Vec<char>,
+    /// The complement of each letter, empty if the alphabet has no complement
+    complement: BTreeMap<char, char>,
+}
+
+impl Alphabet {
+    /// Returns an alphabet without complement
+    ///
+    /// # Arguments
+    /// * `letters` the letters in their order
+    pub(crate) fn new(letters: Vec<char>) -> Alphabet {
+        return Alphabet { letters, complement: BTreeMap::new() };
+    }
+
+    /// Returns an alphabet with a complement map
+    ///
+    /// # Arguments
+    /// * `letters` the letters in their order
+    /// * `pairs` the complementary pairs, each pair is complemented in both directions
+    fn with_pairs(letters: &str, pairs: &[(char, char)]) -> Alphabet {
+        let complement = pairs.iter().flat_map(|(a, b)| [(*a, *b), (*b, *a)]).collect();
+        return Alphabet { letters: letters.chars().collect(), complement };
+    }
+
+    /// Returns the DNA nucleotides `ACGT` with the Watson-Crick complement
+    pub(crate) fn dna() -> Alphabet {
+        return Alphabet::with_pairs("ACGT", &[('A', 'T'), ('C', 'G')]);
+    }
+
+    /// Returns the RNA nucleotides `ACGU` with the Watson-Crick complement
+    pub(crate) fn rna() -> Alphabet {
+        return Alphabet::with_pairs("ACGU", &[('A', 'U'), ('C', 'G')]);
+    }
+
+    /// Returns the binary alphabet `01` with the complement `0 <-> 1`
+    pub(crate) fn binary() -> Alphabet {
+        return Alphabet::with_pairs("01", &[('0', '1')]);
+    }
+
+    /// Returns the 20 proteinogenic amino acids in the one letter code (without complement)
+    pub(crate) fn amino_acid() -> Alphabet {
+        return Alphabet::new(AMINO_ACIDS.chars().collect());
+    }
+
+    /// Returns the alphabet preset with the given name (see [PRESETS]), ignoring case
+    pub(crate) fn preset(name: &str) -> Result<Alphabet, String> {
+        match name.to_ascii_lowercase().as_str() {
+            "dna" => return Ok(Alphabet::dna()),
+            "rna" => return Ok(Alphabet::rna()),
+            "binary" => return Ok(Alphabet::binary()),
+            "amino_acid" => return Ok(Alphabet::amino_acid()),
+            _ => return Err(format!("Unknown alphabet '{}', expected one of {}", name, PRESETS.join(", "))),
+        }
+    }
+
+    /// Returns the alphabet passed from R
+    ///
+    /// A single entry of more than one letter names a preset, e.g. `"dna"`. Otherwise each entry must be a single letter.
+    ///
+    /// # Arguments
+    /// * `alphabet` the letters or the name of a preset
+    pub(crate) fn from_r(alphabet: &[String]) -> Result<Alphabet, String> {
+        if alphabet.len() == 1 && alphabet[0].chars().count() > 1 {
+            return Alphabet::preset(&alphabet[0]);
+        }
+
+        let mut letters = Vec::new();
+        for a in alphabet {
+            let mut chars = a.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => letters.push(c),
+                _ => return Err(format!("Alphabet entry '{}' is not a single letter", a)),
+            }
+        }
+
+        return Ok(Alphabet::new(letters));
+    }
+
+    /// Returns the nucleotides of a set of words: RNA if the words are RNA (see [crate::transform::is_rna]), DNA otherwise
+    pub(crate) fn nucleotides_of(words: &[String]) -> Alphabet {
+        return if crate::transform::is_rna(words) { Alphabet::rna() } else { Alphabet::dna() };
+    }
+
+    /// Returns the letters in their order
+    pub(crate) fn letters(&self) -> &[char] {
+        return &self.letters;
+    }
+
+    /// Returns true if the letter is in the alphabet
+    pub(crate) fn contains(&self, c: char) -> bool {
+        return self.letters.contains(&c);
+    }
+
+    /// Returns true if all letters of the word are in the alphabet
+    pub(crate) fn covers(&self, word: &str) -> bool {
+        return word.chars().all(|c| self.contains(c));
+    }
+
+    /// Returns the complement of a letter (lower case letters are complemented to lower case),
+    /// `None` if the alphabet has no complement for it
+    pub(crate) fn complement(&self, c: char) -> Option<char> {
+        if let Some(d) = self.complement.get(&c) {
+            return Some(*d);
+        }
+
+        return self.complement.get(&c.to_ascii_uppercase())
+            .filter(|_| c.is_ascii_lowercase())
+            .map(|d| d.to_ascii_lowercase());
+    }
+
+    /// Checks that all words only use letters of the alphabet
+    ///
+    /// Returns an error naming the first word and letter which are not covered by the alphabet.
+    ///
+    /// # Arguments
+    /// * `words` a set of words
+    pub(crate) fn validate(&self, words: &[String]) -> Result<(), String> {
+        for (word_idx, word) in words.iter().enumerate() {
+            if let Some((pos, c)) = word.chars().enumerate().find(|(_, c)| !self.contains(*c)) {
+                return Err(format!("Word {} ('{}') contains '{}' at position {}, which is not in the alphabet {{{}}}",
+                                   word_idx + 1, word, c, pos + 1,
+                                   self.letters.iter().map(|l| l.to_string()).collect::<Vec<String>>().join(", ")));
+            }
+        }
+
+        return Ok(());
+    }
+
+    /// Returns the letters of the alphabet an IUPAC nucleotide code stands for (ignoring case)
+    ///
+    /// `T` and `U` are both mapped to the thymine/uracil letter of the alphabet. Returns an error if the letter
+    /// is not an IUPAC code or the alphabet has no letter for it (e.g. the binary alphabet).
+    pub(crate) fn iupac_letters(&self, c: char) -> Result<Vec<char>, String> {
+        let t = if self.contains('U') { 'U' } else { 'T' };
+        let bases: &[char] = match c.to_ascii_uppercase() {
+            'A' => &['A'],
+            'C' => &['C'],
+            'G' => &['G'],
+            'T' | 'U' => &['T'],
+            'R' => &['A', 'G'],
+            'Y' => &['C', 'T'],
+            'S' => &['C', 'G'],
+            'W' => &['A', 'T'],
+            'K' => &['G', 'T'],
+            'M' => &['A', 'C'],
+            'B' => &['C', 'G', 'T'],
+            'D' => &['A', 'G', 'T'],
+            'H' => &['A', 'C', 'T'],
+            'V' => &['A', 'C', 'G'],
+            'N' => &['A', 'C', 'G', 'T'],
+            _ => return Err(format!("'{}' is not an IUPAC nucleotide code", c)),
+        };
+
+        let letters = bases.iter().map(|b| if *b == 'T' { t } else { *b }).collect::<Vec<char>>();
+        if let Some(l) = letters.iter().find(|l| !self.contains(**l)) {
+            return Err(format!("The IUPAC code '{}' stands for '{}', which is not in the alphabet", c, l));
+        }
+
+        return Ok(letters);
+    }
+
+    /// Returns all words an IUPAC word stands for, in lexicographic order of the alphabet
+    ///
+    /// # Arguments
+    /// * `word` a word of IUPAC nucleotide codes, e.g. `"ARN"`
+    pub(crate) fn expand_iupac(&self, word: &str) -> Result<Vec<String>, String> {
+        let mut words = vec![String::new()];
+        for c in word.chars() {
+            let letters = self.iupac_letters(c)?;
+            words = words.iter()
+                .flat_map(|w| letters.iter().map(move |l| format!("{}{}", w, l)))
+                .collect();
+        }
+
+        return Ok(words);
+    }
+}
+
+/// Returns an alphabet preset
+///
+/// This function returns the letters and the complement map of an alphabet preset. The presets are
+/// "dna" (A, C, G, T), "rna" (A, C, G, U), "binary" (0, 1) and "amino_acid" (the 20 proteinogenic amino acids
+/// in the one letter code, without complement). All functions with an alphabet argument accept these names
+/// instead of a vector of letters.
+///
+/// @param name A String, the name of the preset
+///
+/// @return A list with the letters (letters) and the complement of each letter (complement, empty if there is none).
+///
+/// @examples
+/// alphabet_preset("rna")
+///
+/// @export
+#[extendr]
+fn alphabet_preset(name: String) -> Robj {
+    return guard(|| {
+        match Alphabet::preset(&name) {
+            Ok(alphabet) => {
+                let letters = alphabet.letters().iter().map(|c| c.to_string()).collect::<Vec<String>>();
+                let complement = alphabet.letters().iter()
+                    .map(|c| alphabet.complement(*c).map_or(String::new(), |d| d.to_string()))
+                    .collect::<Vec<String>>();
+                return list!(letters = letters, complement = complement);
+            }
+            Err(e) => {
+                r_error!("Alphabet is not correct: {}", e);
+            }
+        }
+    });
+}
+
+/// Expands IUPAC nucleotide codes
+///
+/// This function replaces each word containing IUPAC nucleotide codes (e.g. R for A or G, N for any nucleotide)
+/// by all words it stands for. The result is written in RNA if the words are RNA, in DNA otherwise.
+///
+/// @param tuples A String vector, the words with IUPAC nucleotide codes
+///
+/// @return A String vector with all expanded words (without duplicates, in the order of their first occurrence).
+///
+/// @examples
+/// expand_iupac(c("ARG", "CCN"))
+///
+/// @export
+#[extendr]
+fn expand_iupac(tuples: Vec<String>) -> Vec<String> {
+    return guard(|| {
+        let alphabet = Alphabet::nucleotides_of(&tuples);
+        let mut result: Vec<String> = Vec::new();
+        for t in &tuples {
+            match alphabet.expand_iupac(t) {
+                Ok(words) => {
+                    for w in words {
+                        if !result.contains(&w) {
+                            result.push(w);
+                        }
+                    }
+                }
+                Err(e) => {
+                    r_error!("Word is not correct: '{}': {}", t, e);
+                }
+            }
+        }
+
+        return result;
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod alphabet;
+    fn alphabet_preset;
+    fn expand_iupac;
+}
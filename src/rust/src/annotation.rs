@@ -0,0 +1,139 @@
+use extendr_api::prelude::*;
+
+use crate::guard::guard;
+use crate::lib_utils::new_code_from_vec;
+use crate::scan::{frame_modulus, AhoCorasick};
+
+/// A maximal run of consecutive in-frame code words in a sequence
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Annotation {
+    /// The first position (character index starting with 0)
+    pub(crate) start: usize,
+    /// The position after the last character
+    pub(crate) end: usize,
+    /// The frame, i.e. `start` modulo the word length
+    pub(crate) frame: usize,
+    /// The number of code words in the run
+    pub(crate) words: usize,
+}
+
+/// Returns all maximal runs of consecutive code words in the same frame, ordered by their start
+///
+/// Only words of the length `n` of the longest word are considered, a run consists of
+/// occurrences starting at `p, p + n, p + 2n, ...`.
+///
+/// # Arguments
+/// * `ac` the automaton of the code
+/// * `seq` a sequence
+pub(crate) fn annotate(ac: &AhoCorasick, seq: &str) -> Vec<Annotation> {
+    let n = frame_modulus(ac.words());
+    let mut starts = ac.find_all(seq).into_iter()
+        .filter(|(_, idx)| ac.lengths[*idx] == n)
+        .map(|(start, _)| start)
+        .collect::<Vec<usize>>();
+    starts.sort_unstable();
+    starts.dedup();
+
+    let mut open: Vec<Option<Annotation>> = vec![None; n];
+    let mut annotations = Vec::new();
+    for start in starts {
+        let frame = start % n;
+        match &mut open[frame] {
+            Some(a) if a.end == start => {
+                a.end += n;
+                a.words += 1;
+            }
+            current => {
+                if let Some(a) = current.take() {
+                    annotations.push(a);
+                }
+                *current = Some(Annotation { start, end: start + n, frame, words: 1 });
+            }
+        }
+    }
+    annotations.extend(open.into_iter().flatten());
+    annotations.sort_by_key(|a| (a.start, a.frame));
+
+    return annotations;
+}
+
+/// Returns the X motifs of a sequence, ordered by their start
+///
+/// An X motif is a maximal run of consecutive in-frame code words (see [annotate]) with at least
+/// `min_length` characters, e.g. the motifs of at least 12 nucleotides built of words of the code X0.
+///
+/// # Arguments
+/// * `ac` the automaton of the code
+/// * `seq` a sequence
+/// * `min_length` the minimal number of characters of a motif
+pub(crate) fn x_motifs(ac: &AhoCorasick, seq: &str, min_length: usize) -> Vec<Annotation> {
+    return annotate(ac, seq).into_iter().filter(|a| a.end - a.start >= min_length).collect();
+}
+
+/// Returns the intervals of a sequence covered by in-frame code words
+///
+/// An interval is a maximal run of consecutive words of the code in the same frame, i.e.
+/// the sequence is read in tuples of length \emph{n} (the length of the longest word)
+/// and neighbouring tuples which are code words are merged. The frame of an interval is its
+/// start position (starting with 0) modulo \emph{n}. This is the per-position counterpart to
+/// \link{sequence_frame_coverage} and can be converted to genome browser tracks.
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param seq A String, the sequence
+///
+/// @return A list with the start positions (start, starting with 1), the end positions (end, inclusive),
+/// the frames (frame) and the number of words (words) of all intervals.
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGG", "AAC"))
+/// annotate_sequence(code, "ACGCGGAACACG")
+///
+/// @seealso \link{scan_sequence}, \link{sequence_frame_coverage}
+///
+/// @export
+#[extendr]
+fn annotate_sequence(tuples: Vec<String>, seq: String) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let annotations = annotate(&AhoCorasick::new(&code.get_code()), &seq);
+
+        return list!(start = annotations.iter().map(|a| a.start as i32 + 1).collect::<Vec<i32>>(),
+        end = annotations.iter().map(|a| a.end as i32).collect::<Vec<i32>>(),
+        frame = annotations.iter().map(|a| a.frame as i32).collect::<Vec<i32>>(),
+        words = annotations.iter().map(|a| a.words as i32).collect::<Vec<i32>>());
+    });
+}
+
+/// Returns the X motifs of a sequence
+///
+/// @param tuples A gcatbase::gcat.code object, e.g. the code X0
+/// @param seq A String, the sequence
+/// @param min_length An integer, the minimal number of characters of a motif
+///
+/// @return A list with the start positions (start, starting with 1), the end positions (end, inclusive),
+/// the frames (frame) and the subsequences (sequence) of all motifs.
+///
+/// @seealso \link{extract_x_motifs}
+///
+#[extendr]
+fn extract_x_motifs_obj(tuples: Vec<String>, seq: String, min_length: i32) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let motifs = x_motifs(&AhoCorasick::new(&code.get_code()), &seq, min_length.max(1) as usize);
+        let chars = seq.chars().collect::<Vec<char>>();
+
+        return list!(start = motifs.iter().map(|m| m.start as i32 + 1).collect::<Vec<i32>>(),
+        end = motifs.iter().map(|m| m.end as i32).collect::<Vec<i32>>(),
+        frame = motifs.iter().map(|m| m.frame as i32).collect::<Vec<i32>>(),
+        sequence = motifs.iter().map(|m| chars[m.start..m.end].iter().collect::<String>()).collect::<Vec<String>>());
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod annotation;
+    fn annotate_sequence;
+    fn extract_x_motifs_obj;
+}
@@ -0,0 +1,149 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::limits::Budget;
+
+/// The flower automaton of a set of words X
+///
+/// The automaton has a single initial and final state, the center `0`. Each word
+/// `a1...am` forms a petal `0 -a1-> (w,1) -a2-> ... -am-> 0`. The automaton recognizes X*.
+#[derive(Debug, Clone)]
+pub(crate) struct FlowerAutomaton {
+    pub(crate) n_states: usize,
+    /// All transitions `(from, letter, to)`
+    pub(crate) transitions: Vec<(usize, char, usize)>,
+}
+
+impl FlowerAutomaton {
+    /// Returns the flower automaton of a set of words
+    ///
+    /// # Arguments
+    /// * `words` a set of words
+    pub(crate) fn new(words: &[String]) -> FlowerAutomaton {
+        let mut n_states = 1;
+        let mut transitions = Vec::new();
+        for word in words {
+            let chars = word.chars().collect::<Vec<char>>();
+            let mut prev = 0;
+            for (i, c) in chars.iter().enumerate() {
+                let next = if i + 1 == chars.len() {
+                    0
+                } else {
+                    n_states += 1;
+                    n_states - 1
+                };
+                transitions.push((prev, *c, next));
+                prev = next;
+            }
+        }
+
+        return FlowerAutomaton { n_states, transitions };
+    }
+
+    /// Returns the outgoing transitions `(letter, to)` of each state
+    pub(crate) fn outgoing(&self) -> Vec<Vec<(char, usize)>> {
+        let mut out = vec![vec![]; self.n_states];
+        for (from, a, to) in &self.transitions {
+            out[*from].push((*a, *to));
+        }
+
+        return out;
+    }
+}
+
+/// Returns true if the flower automaton is unambiguous, i.e. if the words form a code
+///
+/// The automaton is ambiguous iff two different paths from the center to the center carry the same label.
+/// In the product (square) automaton this is the case iff a state `(p, q)` with `p != q` is accessible from
+/// `(0, 0)` and co-accessible to `(0, 0)`.
+///
+/// # Arguments
+/// * `flower` a flower automaton
+pub(crate) fn is_unambiguous(flower: &FlowerAutomaton) -> bool {
+    let out = flower.outgoing();
+
+    // Accessible pairs and the reversed transitions between them. Only the pairs accessible from
+    // the center are stored, so the memory grows with the reachable part of the product automaton.
+    let mut reverse: HashMap<(usize, usize), Vec<(usize, usize)>> = HashMap::from([((0, 0), vec![])]);
+    let mut queue = VecDeque::from([(0, 0)]);
+    while let Some((p, q)) = queue.pop_front() {
+        for (a, p_next) in &out[p] {
+            for (b, q_next) in &out[q] {
+                if a != b {
+                    continue;
+                }
+                let next = (*p_next, *q_next);
+                if !reverse.contains_key(&next) {
+                    queue.push_back(next);
+                }
+                reverse.entry(next).or_default().push((p, q));
+            }
+        }
+    }
+
+    // Co-accessible pairs (restricted to accessible ones)
+    let mut co_accessible = HashSet::from([(0, 0)]);
+    let mut queue = VecDeque::from([(0, 0)]);
+    while let Some(pq) = queue.pop_front() {
+        for prev in &reverse[&pq] {
+            if co_accessible.insert(*prev) {
+                queue.push_back(*prev);
+            }
+        }
+    }
+
+    return !co_accessible.iter().any(|(p, q)| p != q);
+}
+
+/// Returns sequences with two different factorizations found within the budget
+///
+/// The search walks through the product automaton: two paths leave the center with different
+/// transitions and the sequence is reported when both paths return to the center at the same time.
+/// Each state of the product automaton is expanded at most once, so the search terminates
+/// and results in at most one sequence per reachable state pair. If the words form a code the result is empty.
+/// The search is breadth first, so the sequences are ordered by their length and the first one is a shortest ambiguous sequence.
+///
+/// # Arguments
+/// * `flower` a flower automaton
+/// * `budget` the limits of the search
+pub(crate) fn ambiguous_sequences_limited(flower: &FlowerAutomaton, budget: &mut Budget) -> Vec<String> {
+    trace_span!("ambiguity_search", states = flower.n_states);
+    let out = flower.outgoing();
+
+    let mut result = Vec::new();
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::new();
+    for (i, (a, p)) in out[0].iter().enumerate() {
+        for (b, q) in out[0].iter().skip(i + 1) {
+            if a == b {
+                queue.push_back((*p, *q, a.to_string()));
+            }
+        }
+    }
+
+    while let Some((p, q, label)) = queue.pop_front() {
+        if !budget.visit() {
+            break;
+        }
+        if p == 0 && q == 0 {
+            if !budget.found_sized(label.len() + std::mem::size_of::<String>()) {
+                break;
+            }
+            result.push(label);
+            continue;
+        }
+        if !visited.insert((p, q)) {
+            continue;
+        }
+
+        for (a, p_next) in &out[p] {
+            for (b, q_next) in &out[q] {
+                if a == b {
+                    queue.push_back((*p_next, *q_next, format!("{}{}", label, a)));
+                }
+            }
+        }
+    }
+    trace_event!(sequences = result.len(), nodes = budget.nodes(), truncated = budget.is_truncated());
+
+    return result;
+}
@@ -0,0 +1,143 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+use extendr_api::prelude::*;
+
+use crate::guard::guard;
+
+/// A cached result of an expensive analysis
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum CachedValue {
+    Bool(bool),
+    Int(i64),
+}
+
+/// The cache maps (canonical hash, property) to the canonical code and the result
+type ResultCache = HashMap<(u64, &'static str), (Vec<String>, CachedValue)>;
+
+/// The maximal number of cached results, the cache is cleared before it grows beyond
+pub(crate) const MAX_CACHED_RESULTS: usize = 10000;
+
+static CACHE_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Returns the global result cache
+fn cache() -> &'static Mutex<ResultCache> {
+    static CACHE: OnceLock<Mutex<ResultCache>> = OnceLock::new();
+    return CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+}
+
+/// Returns the canonical form of a code: its distinct words in sorted order
+///
+/// # Arguments
+/// * `words` a set of words
+pub(crate) fn canonical_code(words: &[String]) -> Vec<String> {
+    let mut canonical = words.to_vec();
+    canonical.sort();
+    canonical.dedup();
+    return canonical;
+}
+
+/// Returns the hash of the canonical form of a code
+///
+/// Codes with the same words have the same hash, independent of the order of the words.
+///
+/// # Arguments
+/// * `words` a set of words
+pub(crate) fn canonical_hash(words: &[String]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    canonical_code(words).hash(&mut hasher);
+    return hasher.finish();
+}
+
+/// Returns the cached result of `property` for the code or computes and caches it
+///
+/// If the cache is disabled the result is always computed.
+///
+/// # Arguments
+/// * `words` a set of words
+/// * `property` the name of the analysis
+/// * `compute` computes the result
+pub(crate) fn cached(words: &[String], property: &'static str, compute: impl FnOnce() -> CachedValue) -> CachedValue {
+    if !CACHE_ENABLED.load(Ordering::Relaxed) {
+        return compute();
+    }
+
+    let canonical = canonical_code(words);
+    let key = (canonical_hash(&canonical), property);
+    if let Ok(cache) = cache().lock() {
+        if let Some((code, value)) = cache.get(&key) {
+            if *code == canonical {
+                return value.clone();
+            }
+        }
+    }
+
+    let value = compute();
+    if let Ok(mut cache) = cache().lock() {
+        if cache.len() >= MAX_CACHED_RESULTS && !cache.contains_key(&key) {
+            cache.clear();
+        }
+        cache.insert(key, (canonical, value.clone()));
+    }
+
+    return value;
+}
+
+/// Returns the cached boolean result of `property` for the code or computes and caches it
+pub(crate) fn cached_bool(words: &[String], property: &'static str, compute: impl FnOnce() -> bool) -> bool {
+    match cached(words, property, || CachedValue::Bool(compute())) {
+        CachedValue::Bool(b) => b,
+        CachedValue::Int(i) => i != 0,
+    }
+}
+
+/// Returns the cached integer result of `property` for the code or computes and caches it
+pub(crate) fn cached_int(words: &[String], property: &'static str, compute: impl FnOnce() -> i64) -> i64 {
+    match cached(words, property, || CachedValue::Int(compute())) {
+        CachedValue::Int(i) => i,
+        CachedValue::Bool(b) => b as i64,
+    }
+}
+
+/// Enables or disables the result cache
+///
+/// If the cache is enabled the results of expensive analyses (e.g. \link{is_code_circular},
+/// \link{get_exact_k_circular}) are memoized for the rest of the R session.
+/// Codes with the same words share their results, independent of the order of the words.
+/// At most 10000 results are kept, the cache is cleared when it is full.
+/// Disabling the cache also clears it.
+///
+/// @param enabled A boolean, true enables the cache
+///
+/// @return The number of cached results before the call.
+///
+/// @examples
+/// set_result_cache(TRUE)
+///
+/// @export
+#[extendr]
+fn set_result_cache(enabled: bool) -> i32 {
+    return guard(|| {
+        CACHE_ENABLED.store(enabled, Ordering::Relaxed);
+        let mut size = 0;
+        if let Ok(mut cache) = cache().lock() {
+            size = cache.len();
+            if !enabled {
+                cache.clear();
+            }
+        }
+
+        return size as i32;
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod cache;
+    fn set_result_cache;
+}
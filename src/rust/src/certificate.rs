@@ -0,0 +1,245 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use extendr_api::prelude::*;
+
+use crate::automaton::{ambiguous_sequences_limited, FlowerAutomaton};
+use crate::decoder::factorizations;
+use crate::guard::guard;
+use crate::lib_utils::new_code_from_vec;
+use crate::limits::{Budget, SearchLimits};
+use crate::word_graph::{cycles_per_word, represented_path_word, simple_cycles_limited};
+
+/// A proof that a set of words X is not a code: a sequence with two different factorizations into words of X
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AmbiguityCertificate {
+    /// The ambiguous sequence
+    pub(crate) sequence: String,
+    /// The first factorization of the sequence
+    pub(crate) first: Vec<String>,
+    /// The second factorization of the sequence, it differs from the first one
+    pub(crate) second: Vec<String>,
+}
+
+impl AmbiguityCertificate {
+    /// Returns the certificate of a sequence or None if the sequence has less than two factorizations
+    ///
+    /// The factorizations are the first two in the lexicographic order of the words, see [factorizations].
+    ///
+    /// # Arguments
+    /// * `words` a set of words, duplicates are ignored
+    /// * `sequence` a sequence
+    pub(crate) fn from_sequence(words: &[String], sequence: &str) -> Option<AmbiguityCertificate> {
+        let mut budget = Budget::new(SearchLimits::from_r(0.0, 2.0, 0.0));
+        let mut factorizations = factorizations(words, sequence, &mut budget).ok()?.into_iter();
+        let (first, second) = (factorizations.next()?, factorizations.next()?);
+
+        return Some(AmbiguityCertificate { sequence: sequence.to_string(), first, second });
+    }
+
+    /// Returns the words used by the factorizations (sorted, without duplicates)
+    pub(crate) fn words(&self) -> Vec<String> {
+        return self.first.iter().chain(self.second.iter()).cloned().collect::<BTreeSet<String>>().into_iter().collect();
+    }
+
+    /// Returns the certificate as R list with the entries sequence, first, second, words and text
+    pub(crate) fn to_robj(&self) -> Robj {
+        return list!(sequence = self.sequence.clone(),
+        first = self.first.clone(),
+        second = self.second.clone(),
+        words = self.words(),
+        text = self.to_string());
+    }
+}
+
+impl fmt::Display for AmbiguityCertificate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{} = {} = {}", self.sequence, self.first.join("."), self.second.join("."));
+    }
+}
+
+/// Returns certificates of ambiguous sequences found within the budget
+///
+/// The sequences are found by [ambiguous_sequences_limited]. If the words form a code the result is empty.
+///
+/// # Arguments
+/// * `words` a set of words
+/// * `budget` the limits of the search
+pub(crate) fn ambiguity_certificates_limited(words: &[String], budget: &mut Budget) -> Vec<AmbiguityCertificate> {
+    return ambiguous_sequences_limited(&FlowerAutomaton::new(words), budget).iter()
+        .filter_map(|seq| AmbiguityCertificate::from_sequence(words, seq))
+        .collect();
+}
+
+/// A proof that a set of words X is not circular: a circular sequence with two different decompositions into words of X
+///
+/// The sequence is represented by a cycle of the graph G(X) associated to X.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct NonCircularityCertificate {
+    /// The circular sequence (read from the first vertex of the cycle)
+    pub(crate) word: String,
+    /// The first circular decomposition of the sequence
+    pub(crate) first: Vec<String>,
+    /// The second circular decomposition of the sequence
+    pub(crate) second: Vec<String>,
+    /// The vertices of the cycle in G(X) representing the sequence
+    pub(crate) cycle: Vec<String>,
+}
+
+impl NonCircularityCertificate {
+    /// Returns the certificate of a cycle in G(X)
+    ///
+    /// # Arguments
+    /// * `cycle` the vertices of a cycle
+    pub(crate) fn from_cycle(cycle: &[String]) -> NonCircularityCertificate {
+        let pw = represented_path_word(cycle, true);
+        return NonCircularityCertificate {
+            word: pw.word,
+            first: pw.first_decomposition,
+            second: pw.second_decomposition,
+            cycle: cycle.to_vec(),
+        };
+    }
+
+    /// Returns the certificate as R list with the entries word, first, second, cycle and text
+    pub(crate) fn to_robj(&self) -> Robj {
+        return list!(word = self.word.clone(),
+        first = self.first.clone(),
+        second = self.second.clone(),
+        cycle = self.cycle.clone(),
+        text = self.to_string());
+    }
+}
+
+impl fmt::Display for NonCircularityCertificate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{} = {} = {} (cycle [{}])", self.word, self.first.join("."), self.second.join("."), self.cycle.join(", "));
+    }
+}
+
+/// Checks if a set of words is circular and returns a certificate if it is not
+///
+/// A set of words is circular iff its associated graph G(X) is acyclic, so the certificate is derived from
+/// the first cycle found. None means that no cycle was found within the budget, i.e. the words are circular
+/// if the budget is not truncated.
+///
+/// # Arguments
+/// * `words` a set of words
+/// * `budget` the limits of the search
+pub(crate) fn check_circularity(words: &[String], budget: &mut Budget) -> Option<NonCircularityCertificate> {
+    return simple_cycles_limited(words, budget).first().map(|cycle| NonCircularityCertificate::from_cycle(cycle));
+}
+
+/// The number of counterexamples a word participates in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Hotspot {
+    pub(crate) word: String,
+    /// The number of ambiguous sequences whose factorizations use the word
+    pub(crate) sequences: usize,
+    /// The number of cycles of G(X) using an edge of the word
+    pub(crate) cycles: usize,
+}
+
+/// Returns the words ranked by the number of ambiguous sequences and cycles they participate in and true if a limit stopped a search
+///
+/// Dropping the words at the top first is a greedy way to turn a set of words into a code or a circular code.
+/// The words are sorted by the number of ambiguous sequences (decreasing), then by the number of cycles
+/// (decreasing) and then lexicographically. Both searches use their own budget with the same limits.
+///
+/// # Arguments
+/// * `words` a set of words
+/// * `limits` the limits of each search
+pub(crate) fn ambiguity_hotspots(words: &[String], limits: SearchLimits) -> (Vec<Hotspot>, bool) {
+    let mut sequence_budget = Budget::new(limits);
+    let certificates = ambiguity_certificates_limited(words, &mut sequence_budget);
+    let mut cycle_budget = Budget::new(limits);
+    let cycles = cycles_per_word(words, &mut cycle_budget).into_iter().collect::<BTreeMap<String, usize>>();
+
+    let certificate_words = certificates.iter().map(|c| c.words()).collect::<Vec<Vec<String>>>();
+    let mut hotspots = words.iter().map(|w| Hotspot {
+        word: w.clone(),
+        sequences: certificate_words.iter().filter(|c| c.contains(w)).count(),
+        cycles: cycles.get(w).copied().unwrap_or(0),
+    }).collect::<Vec<Hotspot>>();
+    hotspots.sort_by(|a, b| b.sequences.cmp(&a.sequences).then(b.cycles.cmp(&a.cycles)).then(a.word.cmp(&b.word)));
+
+    return (hotspots, sequence_budget.is_truncated() || cycle_budget.is_truncated());
+}
+
+/// Returns a certificate which proves that a set of words is not a code
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param max_millis A numeric value, the maximal run time in milliseconds (non-positive: unlimited)
+///
+/// @return A list with the ambiguous sequence (sequence), two different factorizations (first, second),
+/// the involved words (words) and a printable text (text). An empty list if no certificate was found.
+///
+/// @seealso \link{get_ambiguity_certificate}
+///
+#[extendr]
+fn get_ambiguity_certificate_obj(tuples: Vec<String>, max_millis: f64) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let mut budget = Budget::new(SearchLimits::from_r(max_millis, 1.0, 0.0));
+        return match ambiguity_certificates_limited(&code.get_code(), &mut budget).first() {
+            Some(certificate) => certificate.to_robj(),
+            None => list!(),
+        };
+    });
+}
+
+/// Checks if a code is circular and returns a certificate if it is not
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param max_millis A numeric value, the maximal run time in milliseconds (non-positive: unlimited)
+///
+/// @return A list with the circular sequence (word), two different circular decompositions (first, second),
+/// the cycle in the associated graph (cycle), a printable text (text) and a boolean (truncated) which is true
+/// if the time limit stopped the search. Only truncated if no certificate was found.
+///
+/// @seealso \link{check_circularity}
+///
+#[extendr]
+fn check_circularity_obj(tuples: Vec<String>, max_millis: f64) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let mut budget = Budget::new(SearchLimits::from_r(max_millis, 1.0, 0.0));
+        return match check_circularity(&code.get_code(), &mut budget) {
+            Some(certificate) => certificate.to_robj(),
+            None => list!(truncated = budget.is_truncated()),
+        };
+    });
+}
+
+/// Ranks the words of a code by the number of ambiguous sequences and cycles they participate in
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param max_millis A numeric value, the maximal run time in milliseconds of each search (non-positive: unlimited)
+///
+/// @return A list with the words (word), the number of ambiguous sequences (sequences), the number of cycles (cycles)
+/// and a boolean (truncated) which is true if a limit stopped a search.
+///
+/// @seealso \link{ambiguity_hotspots}
+///
+#[extendr]
+fn ambiguity_hotspots_obj(tuples: Vec<String>, max_millis: f64) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let (hotspots, truncated) = ambiguity_hotspots(&code.get_code(), SearchLimits::from_r(max_millis, 0.0, 0.0));
+
+        return list!(word = hotspots.iter().map(|h| h.word.clone()).collect::<Vec<String>>(),
+        sequences = hotspots.iter().map(|h| h.sequences as i32).collect::<Vec<i32>>(),
+        cycles = hotspots.iter().map(|h| h.cycles as i32).collect::<Vec<i32>>(),
+        truncated = truncated);
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod certificate;
+    fn get_ambiguity_certificate_obj;
+    fn check_circularity_obj;
+    fn ambiguity_hotspots_obj;
+}
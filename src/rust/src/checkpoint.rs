@@ -0,0 +1,88 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// The first line of every checkpoint, the number is the version of the format
+const HEADER: &str = "# gcatcirc checkpoint 1";
+
+/// The saved state of a long running search, e.g. an enumeration or a heuristic search
+///
+/// A checkpoint is a text of `key=value` lines, so it can be written to disk and read by a later
+/// R session to resume the search. The entry `kind` names the search the state belongs to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Checkpoint {
+    values: BTreeMap<String, String>,
+}
+
+impl Checkpoint {
+    /// Returns an empty checkpoint of a search
+    pub(crate) fn new(kind: &str) -> Checkpoint {
+        return Checkpoint { values: BTreeMap::from([("kind".to_string(), kind.to_string())]) };
+    }
+
+    /// Parses a checkpoint and checks that it belongs to the search `kind`
+    pub(crate) fn parse(text: &str, kind: &str) -> Result<Checkpoint, String> {
+        let mut lines = text.lines();
+        if lines.next().map(|l| l.trim()) != Some(HEADER) {
+            return Err("Not a checkpoint or unsupported version".to_string());
+        }
+
+        let mut values = BTreeMap::new();
+        for line in lines.filter(|l| !l.trim().is_empty()) {
+            match line.split_once('=') {
+                Some((key, value)) => values.insert(key.trim().to_string(), value.trim().to_string()),
+                None => return Err(format!("Line '{}' is not a key=value pair", line)),
+            };
+        }
+        let checkpoint = Checkpoint { values };
+        if checkpoint.get("kind")? != kind {
+            return Err(format!("Checkpoint of '{}' can not resume '{}'", checkpoint.get("kind")?, kind));
+        }
+
+        return Ok(checkpoint);
+    }
+
+    /// Sets a value
+    pub(crate) fn set(&mut self, key: &str, value: impl ToString) {
+        self.values.insert(key.to_string(), value.to_string());
+    }
+
+    /// Sets a list of words
+    pub(crate) fn set_words(&mut self, key: &str, words: &[String]) {
+        self.set(key, words.join(","));
+    }
+
+    /// Sets a floating point value exactly (as bit pattern)
+    pub(crate) fn set_f64(&mut self, key: &str, value: f64) {
+        self.set(key, format!("{:016x}", value.to_bits()));
+    }
+
+    /// Returns a value
+    pub(crate) fn get(&self, key: &str) -> Result<&str, String> {
+        return self.values.get(key).map(|v| v.as_str()).ok_or(format!("Checkpoint has no entry '{}'", key));
+    }
+
+    /// Returns a parsed value
+    pub(crate) fn get_parsed<T: FromStr>(&self, key: &str) -> Result<T, String> {
+        return self.get(key)?.parse::<T>().map_err(|_| format!("Checkpoint entry '{}' is not correct", key));
+    }
+
+    /// Returns a list of words
+    pub(crate) fn get_words(&self, key: &str) -> Result<Vec<String>, String> {
+        return Ok(self.get(key)?.split(',').filter(|w| !w.is_empty()).map(|w| w.to_string()).collect());
+    }
+
+    /// Returns a floating point value written by [Checkpoint::set_f64]
+    pub(crate) fn get_f64(&self, key: &str) -> Result<f64, String> {
+        return u64::from_str_radix(self.get(key)?, 16).map(f64::from_bits).map_err(|_| format!("Checkpoint entry '{}' is not correct", key));
+    }
+
+    /// Returns the text of the checkpoint
+    pub(crate) fn to_text(&self) -> String {
+        let mut text = format!("{}\n", HEADER);
+        for (key, value) in &self.values {
+            text.push_str(&format!("{}={}\n", key, value));
+        }
+
+        return text;
+    }
+}
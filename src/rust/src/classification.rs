@@ -0,0 +1,89 @@
+use std::fmt;
+
+use extendr_api::prelude::*;
+
+use crate::cache::{cached_bool, cached_int};
+use crate::comma_free::suffix_prefix_overlap;
+use crate::gcat::CircCode;
+use crate::guard::guard;
+use crate::lib_utils::new_code_from_vec;
+
+/// The most restrictive class of the circular code hierarchy a set of words belongs to
+///
+/// Each class is contained in the next one: strong comma-free codes are comma-free,
+/// comma-free codes are circular, circular codes are k-circular for every k and k-circular codes are codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CodeClass {
+    StrongCommaFree,
+    CommaFree,
+    Circular,
+    /// A code which is k-circular but not circular, the value is the exact k (at least 2)
+    KCircular(u32),
+    Code,
+    NotACode,
+}
+
+impl CodeClass {
+    /// Returns the class of a code
+    ///
+    /// The properties are checked from the least to the most restrictive one, so
+    /// the expensive checks are only done for codes of the more restrictive classes.
+    ///
+    /// # Arguments
+    /// * `code` a code
+    pub(crate) fn of(code: &CircCode) -> CodeClass {
+        let words = code.get_code();
+        if !cached_bool(&words, "is_code", || code.is_code()) {
+            return CodeClass::NotACode;
+        }
+        if !cached_bool(&words, "is_circular", || code.is_circular()) {
+            let k = cached_int(&words, "exact_k_circular", || code.get_exact_k_circular() as i64) as u32;
+            return if k >= 2 { CodeClass::KCircular(k) } else { CodeClass::Code };
+        }
+        if !code.is_comma_free() {
+            return CodeClass::Circular;
+        }
+        if suffix_prefix_overlap(&words).is_some() {
+            return CodeClass::CommaFree;
+        }
+
+        return CodeClass::StrongCommaFree;
+    }
+}
+
+impl fmt::Display for CodeClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodeClass::StrongCommaFree => write!(f, "strong comma-free"),
+            CodeClass::CommaFree => write!(f, "comma-free"),
+            CodeClass::Circular => write!(f, "circular"),
+            CodeClass::KCircular(k) => write!(f, "{}-circular", k),
+            CodeClass::Code => write!(f, "code"),
+            CodeClass::NotACode => write!(f, "not a code"),
+        }
+    }
+}
+
+/// Returns the most restrictive class of a code
+///
+/// @param tuples A gcatbase::gcat.code object
+///
+/// @return A String, the class of the code.
+///
+/// @seealso \link{code_class}
+///
+#[extendr]
+fn code_class_obj(tuples: Vec<String>) -> String {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        return CodeClass::of(&code).to_string();
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod classification;
+    fn code_class_obj;
+}
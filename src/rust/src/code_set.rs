@@ -0,0 +1,160 @@
+use std::collections::BTreeSet;
+
+use extendr_api::prelude::*;
+
+use crate::guard::guard;
+use crate::lib_utils::new_code_from_vec;
+
+/// Returns the canonical form of a set of words
+///
+/// The canonical form does not depend on the order, the duplicates and the case of the words:
+/// the words are trimmed, converted to upper case, sorted and deduplicated.
+///
+/// # Arguments
+/// * `words` a set of words
+pub(crate) fn canonical_words(words: &[String]) -> Vec<String> {
+    return words.iter().map(|w| w.trim().to_uppercase()).collect::<BTreeSet<String>>().into_iter().collect();
+}
+
+/// Returns the canonical words of a validated code as set
+///
+/// # Arguments
+/// * `tuples` a set of words, stops the execution if it is not a correct code
+fn word_set(tuples: Vec<String>) -> BTreeSet<String> {
+    return canonical_words(&new_code_from_vec(tuples).get_code()).into_iter().collect();
+}
+
+/// Returns the words of a set operation as validated code (sorted)
+fn code_from_set(words: BTreeSet<String>) -> Vec<String> {
+    let words = words.into_iter().collect::<Vec<String>>();
+    if words.is_empty() {
+        return words;
+    }
+    new_code_from_vec(words.clone());
+    return words;
+}
+
+/// Returns the union of two codes
+///
+/// @param a A gcatbase::gcat.code object
+/// @param b A gcatbase::gcat.code object
+///
+/// @return A String vector, the canonical words of a or b.
+///
+/// @seealso \link{code_union}
+///
+#[extendr]
+fn code_union_obj(a: Vec<String>, b: Vec<String>) -> Vec<String> {
+    return guard(|| {
+        let (a, b) = (word_set(a), word_set(b));
+        return code_from_set(a.union(&b).cloned().collect());
+    });
+}
+
+/// Returns the intersection of two codes
+///
+/// @param a A gcatbase::gcat.code object
+/// @param b A gcatbase::gcat.code object
+///
+/// @return A String vector, the canonical words of a and b.
+///
+/// @seealso \link{code_intersect}
+///
+#[extendr]
+fn code_intersect_obj(a: Vec<String>, b: Vec<String>) -> Vec<String> {
+    return guard(|| {
+        let (a, b) = (word_set(a), word_set(b));
+        return code_from_set(a.intersection(&b).cloned().collect());
+    });
+}
+
+/// Returns the difference of two codes
+///
+/// @param a A gcatbase::gcat.code object
+/// @param b A gcatbase::gcat.code object
+///
+/// @return A String vector, the canonical words of a which are not in b.
+///
+/// @seealso \link{code_setdiff}
+///
+#[extendr]
+fn code_setdiff_obj(a: Vec<String>, b: Vec<String>) -> Vec<String> {
+    return guard(|| {
+        let (a, b) = (word_set(a), word_set(b));
+        return code_from_set(a.difference(&b).cloned().collect());
+    });
+}
+
+/// Checks if a code is a subset of another code
+///
+/// Both codes are validated first and compared in their canonical form (see \link{code_canonical}).
+///
+/// @param a A gcatbase::gcat.code object
+/// @param b A gcatbase::gcat.code object
+///
+/// @return A boolean, true if every word of a is a word of b.
+///
+/// @examples
+/// code_is_subset(gcatbase::code(c("ACG", "AC")), gcatbase::code(c("ACG", "CGG", "AC")))
+///
+/// @seealso \link{code_union}, \link{code_intersect}, \link{code_setdiff}
+///
+/// @export
+#[extendr]
+fn code_is_subset(a: Vec<String>, b: Vec<String>) -> bool {
+    return guard(|| {
+        return word_set(a).is_subset(&word_set(b));
+    });
+}
+
+/// Returns the canonical form of a code
+///
+/// @param tuples A gcatbase::gcat.code object
+///
+/// @return A String vector, the canonical words.
+///
+/// @seealso \link{code_canonical}
+///
+#[extendr]
+fn code_canonical_obj(tuples: Vec<String>) -> Vec<String> {
+    return guard(|| {
+        return word_set(tuples).into_iter().collect();
+    });
+}
+
+/// Checks if two codes are equal
+///
+/// The codes are validated and compared in their canonical form, i.e. the order, duplicates
+/// and the case of the words are ignored (see \link{code_canonical}). Unlike \emph{setequal}
+/// this applies the same normalization as all other functions of this package.
+///
+/// @param a A gcatbase::gcat.code object
+/// @param b A gcatbase::gcat.code object
+///
+/// @return A boolean, true if both codes contain the same words.
+///
+/// @examples
+/// codes_equal(gcatbase::code(c("ACG", "cgg")), c("CGG", "ACG", "ACG"))
+///
+/// @seealso \link{code_canonical}, \link{code_is_subset}
+///
+/// @export
+#[extendr]
+fn codes_equal(a: Vec<String>, b: Vec<String>) -> bool {
+    return guard(|| {
+        return word_set(a) == word_set(b);
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod code_set;
+    fn code_union_obj;
+    fn code_intersect_obj;
+    fn code_setdiff_obj;
+    fn code_is_subset;
+    fn code_canonical_obj;
+    fn codes_equal;
+}
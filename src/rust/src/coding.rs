@@ -0,0 +1,114 @@
+use extendr_api::prelude::*;
+
+use crate::guard::guard;
+use crate::lib_utils::new_code_from_vec;
+use crate::parallel::par_map;
+use crate::scan::{frame_coverage, frame_modulus, AhoCorasick};
+
+/// A predicted coding region
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CodingRegion {
+    /// The first position (character index starting with 0)
+    pub(crate) start: usize,
+    /// The position after the last character
+    pub(crate) end: usize,
+    /// The predicted reading frame, i.e. the start positions of the codons modulo the word length
+    pub(crate) frame: usize,
+    /// The mean margin between the coverage of the predicted frame and the second best frame
+    pub(crate) score: f64,
+}
+
+/// Returns the best frame (absolute, i.e. relative to the sequence start) of a window and its margin
+///
+/// The margin is the difference between the coverage of the best and the second best frame.
+fn best_frame(ac: &AhoCorasick, window: &str, offset: usize) -> (usize, f64) {
+    let n = frame_modulus(ac.words());
+    let coverage = frame_coverage(ac, window);
+    let mut order = (0..coverage.len()).collect::<Vec<usize>>();
+    order.sort_by(|a, b| coverage[*b].partial_cmp(&coverage[*a]).unwrap_or(std::cmp::Ordering::Equal).then(a.cmp(b)));
+    let second = order.get(1).map_or(0.0, |f| coverage[*f]);
+
+    return ((offset + order[0]) % n, coverage[order[0]] - second);
+}
+
+/// Predicts coding regions by comparing the coverage of the frames in a sliding window
+///
+/// In each window the frame with the highest coverage by the code is the candidate reading frame
+/// (e.g. for the code X0 in genes). A window is predicted as coding if the margin to the second best
+/// frame is at least `threshold`. Overlapping or adjacent coding windows with the same frame are merged
+/// into one region whose score is the mean margin of its windows. The windows are evaluated in parallel
+/// and merged in the order of their start positions, so the result is deterministic.
+///
+/// # Arguments
+/// * `ac` the automaton of the code
+/// * `seq` a sequence
+/// * `window` the window length (a shorter sequence is analysed as one window)
+/// * `step` the distance between the starts of two windows
+/// * `threshold` the minimal margin of a coding window
+pub(crate) fn predict_coding_regions(ac: &AhoCorasick, seq: &str, window: usize, step: usize, threshold: f64) -> Vec<CodingRegion> {
+    let chars = seq.chars().collect::<Vec<char>>();
+    let window = window.max(1).min(chars.len());
+    let step = step.max(1);
+    if chars.is_empty() {
+        return vec![];
+    }
+
+    let starts = (0..=chars.len() - window).step_by(step).collect::<Vec<usize>>();
+    let windows = par_map(&starts, |start| best_frame(ac, &chars[*start..start + window].iter().collect::<String>(), *start));
+
+    let mut regions: Vec<CodingRegion> = Vec::new();
+    let mut windows_in_last = 0;
+    for (start, (frame, margin)) in starts.into_iter().zip(windows) {
+        if margin < threshold || margin <= 0.0 {
+            continue;
+        }
+        match regions.last_mut() {
+            Some(r) if r.frame == frame && start <= r.end => {
+                r.end = start + window;
+                r.score = (r.score * windows_in_last as f64 + margin) / (windows_in_last + 1) as f64;
+                windows_in_last += 1;
+            }
+            _ => {
+                regions.push(CodingRegion { start, end: start + window, frame, score: margin });
+                windows_in_last = 1;
+            }
+        }
+    }
+
+    return regions;
+}
+
+/// Predicts coding regions of a sequence
+///
+/// @param tuples A gcatbase::gcat.code object, e.g. the code X0
+/// @param seq A String, the sequence
+/// @param window An integer, the window length
+/// @param step An integer, the distance between the starts of two windows
+/// @param threshold A numeric value, the minimal coverage margin of a coding window
+///
+/// @return A list (convertible to a data.frame) with the start positions (start, starting with 1),
+/// the end positions (end, inclusive), the frames (frame) and the scores (score) of all predicted regions.
+///
+/// @seealso \link{predict_coding_regions}
+///
+#[extendr]
+fn predict_coding_regions_obj(tuples: Vec<String>, seq: String, window: i32, step: i32, threshold: f64) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let regions = predict_coding_regions(&AhoCorasick::new(&code.get_code()), &seq,
+                                             window.max(1) as usize, step.max(1) as usize, threshold);
+
+        return list!(start = regions.iter().map(|r| r.start as i32 + 1).collect::<Vec<i32>>(),
+        end = regions.iter().map(|r| r.end as i32).collect::<Vec<i32>>(),
+        frame = regions.iter().map(|r| r.frame as i32).collect::<Vec<i32>>(),
+        score = regions.iter().map(|r| r.score).collect::<Vec<f64>>());
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod coding;
+    fn predict_coding_regions_obj;
+}
@@ -0,0 +1,162 @@
+use extendr_api::prelude::*;
+
+use crate::guard::guard;
+
+/// Returns all divisors of `n` (sorted)
+fn divisors(n: u32) -> Vec<u32> {
+    return (1..=n).filter(|d| n.is_multiple_of(*d)).collect();
+}
+
+/// Euler's totient function
+fn euler_phi(n: u32) -> u32 {
+    return (1..=n).filter(|i| gcd(*i, n) == 1).count() as u32;
+}
+
+/// Möbius function
+fn moebius(n: u32) -> i32 {
+    let mut n = n;
+    let mut result = 1;
+    let mut p = 2;
+    while p * p <= n {
+        if n.is_multiple_of(p) {
+            n /= p;
+            if n.is_multiple_of(p) {
+                return 0;
+            }
+            result = -result;
+        }
+        p += 1;
+    }
+    if n > 1 {
+        result = -result;
+    }
+
+    return result;
+}
+
+/// Greatest common divisor
+pub(crate) fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 {
+        return a;
+    }
+
+    return gcd(b, a % b);
+}
+
+/// Returns the number of necklaces (conjugacy classes) of words of length `n` over `k` letters
+///
+/// `N(n, k) = 1/n * sum_(d | n) phi(d) * k^(n/d)`. Returns `None` on overflow.
+pub(crate) fn necklaces(n: u32, k: u32) -> Option<u128> {
+    if n == 0 {
+        return Some(1);
+    }
+
+    let mut sum: u128 = 0;
+    for d in divisors(n) {
+        sum = sum.checked_add((euler_phi(d) as u128).checked_mul((k as u128).checked_pow(n / d)?)?)?;
+    }
+
+    return Some(sum / n as u128);
+}
+
+/// Returns the number of Lyndon words (primitive necklaces) of length `n` over `k` letters
+///
+/// `L(n, k) = 1/n * sum_(d | n) mu(d) * k^(n/d)`. Returns `None` on overflow.
+pub(crate) fn lyndon_words(n: u32, k: u32) -> Option<u128> {
+    if n == 0 {
+        return Some(0);
+    }
+
+    let mut sum: i128 = 0;
+    for d in divisors(n) {
+        let term = (k as i128).checked_pow(n / d)?.checked_mul(moebius(d) as i128)?;
+        sum = sum.checked_add(term)?;
+    }
+
+    return Some((sum / n as i128) as u128);
+}
+
+/// Converts a count passed to R, overflows result in `Inf`
+fn count_to_r(count: Option<u128>) -> f64 {
+    return count.map_or(f64::INFINITY, |c| c as f64);
+}
+
+/// Returns the number of necklaces of length n over k letters
+///
+/// A necklace is a class of words which are equal up to circular permutation (conjugacy class).
+/// Their number is \emph{N(n, k) = 1/n sum_(d | n) phi(d) k^(n/d)}.
+///
+/// @param n An integer, the word length
+/// @param k An integer, the alphabet size
+///
+/// @return A numeric value, the number of necklaces.
+///
+/// @examples
+/// necklace_count(3, 4)
+///
+/// @seealso \link{lyndon_word_count}
+///
+/// @export
+#[extendr]
+fn necklace_count(n: i32, k: i32) -> f64 {
+    return guard(|| {
+        return count_to_r(necklaces(n.max(0) as u32, k.max(0) as u32));
+    });
+}
+
+/// Returns the number of Lyndon words of length n over k letters
+///
+/// A Lyndon word is the lexicographically minimal representative of a necklace
+/// of primitive (non periodic) words. Their number is \emph{L(n, k) = 1/n sum_(d | n) mu(d) k^(n/d)}.
+///
+/// @param n An integer, the word length
+/// @param k An integer, the alphabet size
+///
+/// @return A numeric value, the number of Lyndon words.
+///
+/// @examples
+/// lyndon_word_count(3, 4)
+///
+/// @seealso \link{necklace_count}, \link{max_circular_code_size}
+///
+/// @export
+#[extendr]
+fn lyndon_word_count(n: i32, k: i32) -> f64 {
+    return guard(|| {
+        return count_to_r(lyndon_words(n.max(0) as u32, k.max(0) as u32));
+    });
+}
+
+/// Returns the maximal size of a circular code of words of length n over k letters
+///
+/// A circular code contains at most one word of each conjugacy class and no periodic words.
+/// Hence its size is bounded by the number of Lyndon words \emph{L(n, k)}, and this bound is attained.
+/// For trinucleotides (n = 3, k = 4) the maximal size is 20.
+///
+/// @param n An integer, the word length
+/// @param k An integer, the alphabet size
+///
+/// @return A numeric value, the maximal size of a circular code.
+///
+/// @examples
+/// max_circular_code_size(3, 4)
+///
+/// @seealso \link{lyndon_word_count}
+///
+/// @export
+#[extendr]
+fn max_circular_code_size(n: i32, k: i32) -> f64 {
+    return guard(|| {
+        return count_to_r(lyndon_words(n.max(0) as u32, k.max(0) as u32));
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod combinatorics;
+    fn necklace_count;
+    fn lyndon_word_count;
+    fn max_circular_code_size;
+}
@@ -0,0 +1,397 @@
+use extendr_api::prelude::*;
+
+use std::collections::HashSet;
+
+use crate::alphabet::Alphabet;
+use crate::checkpoint::Checkpoint;
+use crate::guard::guard;
+use crate::lib_utils::{all_words_of_length, new_code_from_vec};
+use crate::limits::{Budget, SearchLimits};
+use crate::overlap::OverlapMatrix;
+use crate::subsets::{largest_subset_exact, largest_subset_greedy};
+
+/// Returns the first word of X occurring at a position which is not a word boundary in a concatenation of `k` words
+///
+/// Returns the concatenated words and the occurring word, or `None` if there is no such occurrence.
+///
+/// # Arguments
+/// * `words` a set of words
+/// * `k` the number of concatenated words
+pub(crate) fn k_comma_free_violation(words: &[String], k: usize) -> Option<(Vec<String>, String)> {
+    if words.is_empty() || k < 2 {
+        return None;
+    }
+
+    let mut indices = vec![0; k];
+    loop {
+        let concatenation = indices.iter().map(|i| words[*i].clone()).collect::<Vec<String>>();
+        let seq = concatenation.concat().chars().collect::<Vec<char>>();
+        let mut boundaries = vec![false; seq.len() + 1];
+        let mut pos = 0;
+        for w in &concatenation {
+            boundaries[pos] = true;
+            pos += w.chars().count();
+        }
+
+        for w in words {
+            let w_chars = w.chars().collect::<Vec<char>>();
+            for p in 1..seq.len() {
+                if !boundaries[p] && p + w_chars.len() <= seq.len() && seq[p..p + w_chars.len()] == w_chars[..] {
+                    return Some((concatenation, w.clone()));
+                }
+            }
+        }
+
+        // Next combination of k words
+        let mut i = 0;
+        while i < k {
+            indices[i] += 1;
+            if indices[i] < words.len() {
+                break;
+            }
+            indices[i] = 0;
+            i += 1;
+        }
+        if i == k {
+            return None;
+        }
+    }
+}
+
+/// Returns true if no word of X occurs at a position which is not a word boundary in a concatenation of `k` words
+///
+/// For `k = 2` this is the definition of comma-freeness.
+///
+/// # Arguments
+/// * `words` a set of words
+/// * `k` the number of concatenated words
+pub(crate) fn is_k_comma_free(words: &[String], k: usize) -> bool {
+    return k_comma_free_violation(words, k).is_none();
+}
+
+/// Returns a proper suffix of a word which is a proper prefix of a word (strong comma-freeness violation)
+///
+/// Returns the overlap, the word it is a suffix of and the word it is a prefix of,
+/// or `None` if the words are strong comma-free.
+///
+/// # Arguments
+/// * `words` a set of words
+pub(crate) fn suffix_prefix_overlap(words: &[String]) -> Option<(String, String, String)> {
+    let matrix = OverlapMatrix::new(words);
+    for (i, u) in words.iter().enumerate() {
+        let longest = (0..words.len()).map(|j| matrix.longest(i, j)).max().unwrap_or(0);
+        for l in (1..=longest).rev() {
+            if let Some(j) = (0..words.len()).find(|j| matrix.get(i, *j).contains(&l)) {
+                let chars = u.chars().collect::<Vec<char>>();
+                return Some((chars[chars.len() - l..].iter().collect(), u.clone(), words[j].clone()));
+            }
+        }
+    }
+
+    return None;
+}
+
+/// Returns true if a word of `set` occurs inside `xy` at a position which is not a word boundary
+///
+/// All words have the length `n`.
+fn has_inner_factor(x: &[char], y: &[char], n: usize, set: &HashSet<Vec<char>>) -> bool {
+    let xy = x.iter().chain(y.iter()).copied().collect::<Vec<char>>();
+    return (1..n).any(|p| set.contains(&xy[p..p + n]));
+}
+
+/// Returns true if the comma-free code `current` (words of length `n`) stays comma-free if `w` is added
+///
+/// Only the concatenations involving `w` and the occurrences of `w` are checked.
+fn can_extend(current: &[Vec<char>], set: &HashSet<Vec<char>>, w: &[char], n: usize) -> bool {
+    let mut extended = set.clone();
+    extended.insert(w.to_vec());
+    if has_inner_factor(w, w, n, &extended) {
+        return false;
+    }
+
+    for x in current {
+        if has_inner_factor(x, w, n, &extended) || has_inner_factor(w, x, n, &extended) {
+            return false;
+        }
+    }
+
+    let single = HashSet::from([w.to_vec()]);
+    for x in current {
+        for y in current {
+            if has_inner_factor(x, y, n, &single) {
+                return false;
+            }
+        }
+    }
+
+    return true;
+}
+
+/// The resumable enumeration of all maximal comma-free codes of words of length `n` over an alphabet
+///
+/// A comma-free code is maximal if no word of length `n` can be added without violating comma-freeness.
+/// The search adds the candidate words in lexicographic order and prunes every candidate which produces
+/// an occurrence at a position which is not a word boundary. Words which overlap with themselves
+/// (e.g. `AAA` or `ACAC`) are never comma-free and are removed in advance.
+///
+/// The search is a depth-first search over the candidate words which first adds and then skips each candidate.
+/// Its state is the path of decisions (added or skipped) to the next unvisited search node,
+/// so a search stopped by its budget can be saved as [Checkpoint] and resumed later.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CommaFreeEnumeration {
+    alphabet: Vec<char>,
+    n: usize,
+    /// The decisions for the candidates `0..path.len()`, true if the candidate is added
+    path: Vec<bool>,
+    done: bool,
+}
+
+impl CommaFreeEnumeration {
+    /// The kind of the checkpoints of this search
+    pub(crate) const KIND: &'static str = "maximal_comma_free_codes";
+
+    /// Returns a new enumeration
+    ///
+    /// # Arguments
+    /// * `alphabet` the letters
+    /// * `n` the word length
+    pub(crate) fn new(alphabet: &[char], n: usize) -> CommaFreeEnumeration {
+        let mut letters = alphabet.to_vec();
+        letters.sort_unstable();
+        letters.dedup();
+        return CommaFreeEnumeration { alphabet: letters, n, path: vec![], done: n == 0 };
+    }
+
+    /// True if all codes have been enumerated
+    pub(crate) fn is_done(&self) -> bool {
+        return self.done;
+    }
+
+    /// Returns the state of the enumeration
+    pub(crate) fn to_checkpoint(&self) -> Checkpoint {
+        let mut checkpoint = Checkpoint::new(Self::KIND);
+        checkpoint.set("alphabet", self.alphabet.iter().collect::<String>());
+        checkpoint.set("n", self.n);
+        checkpoint.set("path", self.path.iter().map(|add| if *add { '1' } else { '0' }).collect::<String>());
+        checkpoint.set("done", self.done);
+        return checkpoint;
+    }
+
+    /// Restores an enumeration from its state
+    pub(crate) fn from_checkpoint(checkpoint: &Checkpoint) -> Result<CommaFreeEnumeration, String> {
+        return Ok(CommaFreeEnumeration {
+            alphabet: checkpoint.get("alphabet")?.chars().collect(),
+            n: checkpoint.get_parsed("n")?,
+            path: checkpoint.get("path")?.chars().map(|c| c == '1').collect(),
+            done: checkpoint.get_parsed("done")?,
+        });
+    }
+
+    /// Continues the enumeration until all codes are found or the budget is exhausted
+    ///
+    /// Returns the codes found by this call.
+    pub(crate) fn run(&mut self, budget: &mut Budget) -> Vec<Vec<String>> {
+        let n = self.n;
+        let candidates = all_words_of_length(&self.alphabet, n).into_iter()
+            .map(|w| w.chars().collect::<Vec<char>>())
+            .filter(|w| can_extend(&[], &HashSet::new(), w, n))
+            .collect::<Vec<Vec<char>>>();
+        let mut results = Vec::new();
+        if self.done || self.path.len() > candidates.len() {
+            self.done = true;
+            return results;
+        }
+
+        let mut current = self.path.iter().zip(candidates.iter()).filter(|(add, _)| **add).map(|(_, w)| w.clone()).collect::<Vec<Vec<char>>>();
+        let mut set = current.iter().cloned().collect::<HashSet<Vec<char>>>();
+
+        trace_span!("maximal_comma_free_enumeration", candidates = candidates.len());
+        while budget.visit() {
+            let idx = self.path.len();
+            if idx < candidates.len() {
+                let w = &candidates[idx];
+                let add = can_extend(&current, &set, w, n);
+                if add {
+                    current.push(w.clone());
+                    set.insert(w.clone());
+                }
+                self.path.push(add);
+                continue;
+            }
+
+            let maximal = candidates.iter().all(|c| set.contains(c) || !can_extend(&current, &set, c, n));
+            if maximal && !current.is_empty() {
+                if !budget.found() {
+                    break;
+                }
+                results.push(current.iter().map(|w| w.iter().collect()).collect());
+            }
+
+            // Backtrack to the last added candidate and skip it
+            loop {
+                match self.path.pop() {
+                    Some(false) => continue,
+                    Some(true) => {
+                        if let Some(w) = current.pop() {
+                            set.remove(&w);
+                        }
+                        self.path.push(false);
+                    }
+                    None => self.done = true,
+                }
+                break;
+            }
+            if self.done {
+                break;
+            }
+        }
+        trace_event!(codes = results.len(), nodes = budget.nodes(), truncated = budget.is_truncated());
+
+        return results;
+    }
+}
+
+/// This function checks if a code is k-comma-free.
+///
+/// A code \emph{X} is k-comma-free if no word of \emph{X} occurs in a concatenation
+/// of \emph{k} words of \emph{X} at a position which is not a word boundary. For \emph{k = 2}
+/// this is the definition of comma-freeness, see \link{is_code_comma_free}.
+/// For codes of words of different lengths larger \emph{k} are more restrictive.
+/// The check enumerates all concatenations of \emph{k} words, so only small k are feasible.
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param k An integer, the number of concatenated words (k >= 2)
+///
+/// @return Boolean value. True if the code is k-comma-free.
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGG", "AC"))
+/// is_code_k_comma_free(code, 3)
+///
+/// @seealso \link{is_code_comma_free}
+///
+/// @export
+#[extendr]
+fn is_code_k_comma_free(tuples: Vec<String>, k: i32) -> bool {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        return is_k_comma_free(&code.get_code(), k.max(2) as usize);
+    });
+}
+
+/// Returns a largest comma-free subset of a code
+///
+/// This function returns a subset of maximum size which is a comma-free code.
+/// The exact search is a branch and bound over all subsets which is only feasible for small codes.
+/// For larger codes the greedy search (\emph{exact = FALSE}) adds the words in the
+/// given order as long as the code stays comma-free, which results in a maximal but not
+/// necessarily maximum comma-free subset.
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param exact A boolean, if true the exact search is used
+///
+/// @return A String vector, the comma-free subset.
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGG", "GAC", "AC"))
+/// largest_comma_free_subset(code, TRUE)
+///
+/// @seealso \link{largest_circular_subset}
+///
+/// @export
+#[extendr]
+fn largest_comma_free_subset(tuples: Vec<String>, exact: bool) -> Vec<String> {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let words = code.get_code();
+        let accept = |w: &[String]| is_k_comma_free(w, 2);
+        if exact {
+            return largest_subset_exact(&words, &accept);
+        }
+
+        return largest_subset_greedy(&words, &accept);
+    });
+}
+
+/// Returns why a code is not strong comma-free
+///
+/// A code is strong comma-free if no nonempty proper suffix of a word is a nonempty proper prefix of a word,
+/// see \link{is_code_strong_comma_free}. This function returns such a suffix (overlap)
+/// together with the word it is a suffix of (suffix_word) and the word it is a prefix of (prefix_word).
+///
+/// @param tuples A gcatbase::gcat.code object
+///
+/// @return A list with the entries overlap, suffix_word and prefix_word. The list is empty if the code is strong comma-free.
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGG", "AC"))
+/// strong_comma_free_violation(code)
+///
+/// @seealso \link{is_code_strong_comma_free}
+///
+/// @export
+#[extendr]
+fn strong_comma_free_violation(tuples: Vec<String>) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        match suffix_prefix_overlap(&code.get_code()) {
+            Some((overlap, suffix_word, prefix_word)) => return list!(overlap = overlap, suffix_word = suffix_word, prefix_word = prefix_word),
+            None => return list!(),
+        }
+    });
+}
+
+/// Returns the maximal comma-free codes of a word length within limits
+///
+/// @param alphabet A String vector of single letters, e.g. c("A", "C", "G", "T"), or the name of a preset
+/// @param n An integer, the word length
+/// @param max_millis A numeric value, the maximal run time in milliseconds (non-positive: unlimited)
+/// @param max_codes A numeric value, the maximal number of codes (non-positive: unlimited)
+/// @param max_nodes A numeric value, the maximal number of visited search nodes (non-positive: unlimited)
+/// @param checkpoint A String, the state of a stopped search to resume. If empty a new search is started.
+///
+/// @return A list with the codes (codes), a boolean (truncated) which is true if a limit stopped the search,
+/// the number of visited search nodes (nodes) and the state to resume the search (checkpoint), empty if the search is complete.
+///
+/// @seealso \link{maximal_comma_free_codes}
+///
+#[extendr]
+fn maximal_comma_free_codes_obj(alphabet: Vec<String>, n: i32, max_millis: f64, max_codes: f64, max_nodes: f64, checkpoint: String) -> Robj {
+    return guard(|| {
+        let mut enumeration = if checkpoint.is_empty() {
+            let letters = match Alphabet::from_r(&alphabet) {
+                Ok(alphabet) => alphabet.letters().to_vec(),
+                Err(e) => {
+                    r_error!("Alphabet is not correct: {}", e);
+                }
+            };
+            CommaFreeEnumeration::new(&letters, n.max(0) as usize)
+        } else {
+            match Checkpoint::parse(&checkpoint, CommaFreeEnumeration::KIND).and_then(|c| CommaFreeEnumeration::from_checkpoint(&c)) {
+                Ok(enumeration) => enumeration,
+                Err(e) => {
+                    r_error!("Checkpoint is not correct: {}", e);
+                }
+            }
+        };
+
+        let mut budget = Budget::new(SearchLimits::from_r(max_millis, max_codes, max_nodes));
+        let codes = enumeration.run(&mut budget);
+
+        return list!(codes = codes.iter().map(|x| x.iter().collect_robj()).collect::<Vec<Robj>>(),
+        truncated = budget.is_truncated(),
+        nodes = budget.nodes() as f64,
+        checkpoint = if enumeration.is_done() { String::new() } else { enumeration.to_checkpoint().to_text() });
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod comma_free;
+    fn is_code_k_comma_free;
+    fn largest_comma_free_subset;
+    fn strong_comma_free_violation;
+    fn maximal_comma_free_codes_obj;
+}
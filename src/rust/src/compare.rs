@@ -0,0 +1,199 @@
+use std::collections::BTreeSet;
+
+use extendr_api::prelude::*;
+
+use crate::comma_free::{is_k_comma_free, suffix_prefix_overlap};
+use crate::gcat::CircCode;
+use crate::guard::guard;
+use crate::lib_utils::new_code_from_vec;
+use crate::transform::{is_invariant_under_reverse_complement, words_to_dna};
+use crate::word_graph::{edge_difference, word_edges};
+
+/// The properties of one code in a comparison
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CodeProperties {
+    pub(crate) name: String,
+    pub(crate) size: usize,
+    pub(crate) is_code: bool,
+    pub(crate) circular: bool,
+    pub(crate) cn_circular: bool,
+    pub(crate) comma_free: bool,
+    pub(crate) strong_comma_free: bool,
+    pub(crate) self_complementary: bool,
+}
+
+impl CodeProperties {
+    /// Computes the properties of a set of words
+    ///
+    /// # Arguments
+    /// * `name` the name of the code
+    /// * `words` a set of distinct words
+    pub(crate) fn new(name: &str, words: &[String]) -> CodeProperties {
+        let code = CircCode::new_from_vec(words.to_vec()).ok();
+        return CodeProperties {
+            name: name.to_string(),
+            size: words.len(),
+            is_code: code.as_ref().is_some_and(|c| c.is_code()),
+            circular: code.as_ref().is_some_and(|c| c.is_circular()),
+            cn_circular: CircCode::new_from_vec(words_to_dna(words)).is_ok_and(|c| c.is_cn_circular()),
+            comma_free: is_k_comma_free(words, 2),
+            strong_comma_free: suffix_prefix_overlap(words).is_none(),
+            self_complementary: is_invariant_under_reverse_complement(&words_to_dna(words)),
+        };
+    }
+}
+
+/// The comparison of several codes
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CodeComparison {
+    /// The properties of each code
+    pub(crate) properties: Vec<CodeProperties>,
+    /// The pairwise Jaccard similarities |A ∩ B| / |A ∪ B| (row major)
+    pub(crate) jaccard: Vec<Vec<f64>>,
+    /// The words shared by all codes (sorted)
+    pub(crate) shared: Vec<String>,
+}
+
+/// Returns the Jaccard similarity of two sets of distinct words (1 for two empty sets)
+pub(crate) fn jaccard(a: &[String], b: &[String]) -> f64 {
+    let intersection = a.iter().filter(|w| b.contains(w)).count();
+    let union = a.len() + b.len() - intersection;
+    if union == 0 {
+        return 1.0;
+    }
+
+    return intersection as f64 / union as f64;
+}
+
+/// Compares several codes
+///
+/// # Arguments
+/// * `codes` the codes as pairs of name and distinct words
+pub(crate) fn compare_codes(codes: &[(String, Vec<String>)]) -> CodeComparison {
+    let mut shared = codes.first().map(|c| c.1.clone()).unwrap_or_default();
+    shared.retain(|w| codes.iter().all(|c| c.1.contains(w)));
+    shared.sort();
+
+    return CodeComparison {
+        properties: codes.iter().map(|(name, words)| CodeProperties::new(name, words)).collect(),
+        jaccard: codes.iter().map(|a| codes.iter().map(|b| jaccard(&a.1, &b.1)).collect()).collect(),
+        shared,
+    };
+}
+
+/// Returns a string as JSON string literal
+pub(crate) fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    return out;
+}
+
+impl CodeComparison {
+    /// Serializes the comparison to JSON
+    pub(crate) fn to_json(&self) -> String {
+        let properties = self.properties.iter().map(|p| format!(
+            "{{\"name\":{},\"size\":{},\"is_code\":{},\"circular\":{},\"cn_circular\":{},\"comma_free\":{},\"strong_comma_free\":{},\"self_complementary\":{}}}",
+            json_string(&p.name), p.size, p.is_code, p.circular, p.cn_circular, p.comma_free, p.strong_comma_free, p.self_complementary))
+            .collect::<Vec<String>>();
+        let jaccard = self.jaccard.iter()
+            .map(|row| format!("[{}]", row.iter().map(|v| v.to_string()).collect::<Vec<String>>().join(",")))
+            .collect::<Vec<String>>();
+        let shared = self.shared.iter().map(|w| json_string(w)).collect::<Vec<String>>();
+
+        return format!("{{\"properties\":[{}],\"jaccard\":[{}],\"shared\":[{}]}}",
+                       properties.join(","), jaccard.join(","), shared.join(","));
+    }
+}
+
+/// Returns the property table of a comparison as R list (convertible to a data.frame)
+fn properties_robj(p: &[CodeProperties]) -> Robj {
+    return list!(name = p.iter().map(|x| x.name.clone()).collect::<Vec<String>>(),
+    size = p.iter().map(|x| x.size as i32).collect::<Vec<i32>>(),
+    is_code = p.iter().map(|x| x.is_code).collect::<Vec<bool>>(),
+    circular = p.iter().map(|x| x.circular).collect::<Vec<bool>>(),
+    cn_circular = p.iter().map(|x| x.cn_circular).collect::<Vec<bool>>(),
+    comma_free = p.iter().map(|x| x.comma_free).collect::<Vec<bool>>(),
+    strong_comma_free = p.iter().map(|x| x.strong_comma_free).collect::<Vec<bool>>(),
+    self_complementary = p.iter().map(|x| x.self_complementary).collect::<Vec<bool>>());
+}
+
+/// Compares several codes
+///
+/// @param words A String vector, the words of all codes concatenated
+/// @param sizes An integer vector, the number of words of each code
+/// @param names A String vector, the names of the codes
+///
+/// @return A list with the property table (properties), the Jaccard similarities (jaccard, row major),
+/// the words shared by all codes (shared) and the comparison as JSON (json).
+///
+/// @seealso \link{compare_codes}
+///
+#[extendr]
+fn compare_codes_obj(words: Vec<String>, sizes: Vec<i32>, names: Vec<String>) -> Robj {
+    return guard(|| {
+        if sizes.len() != names.len() || sizes.iter().map(|s| (*s).max(0) as usize).sum::<usize>() != words.len() {
+            r_error!("Codes are not correct: {} words, {} sizes and {} names", words.len(), sizes.len(), names.len());
+        }
+
+        let mut codes = Vec::new();
+        let mut start = 0;
+        for (name, size) in names.iter().zip(sizes.iter()) {
+            let end = start + (*size).max(0) as usize;
+            codes.push((name.clone(), new_code_from_vec(words[start..end].to_vec()).get_code()));
+            start = end;
+        }
+
+        let comparison = compare_codes(&codes);
+        return list!(properties = properties_robj(&comparison.properties),
+        jaccard = comparison.jaccard.concat(),
+        shared = comparison.shared.clone(),
+        json = comparison.to_json());
+    });
+}
+
+/// Compares two codes
+///
+/// @param a A gcatbase::gcat.code object
+/// @param b A gcatbase::gcat.code object
+///
+/// @return A list with the property table (properties), the words of both codes (shared), the Jaccard
+/// similarity (jaccard) and the number of edges only in the graph of a (edges_only_a), only in the graph of b
+/// (edges_only_b) and in both graphs (edges_shared).
+///
+/// @seealso \link{compare_codes_r}
+///
+#[extendr]
+fn compare_code_pair_obj(a: Vec<String>, b: Vec<String>) -> Robj {
+    return guard(|| {
+        let a = new_code_from_vec(a).get_code();
+        let b = new_code_from_vec(b).get_code();
+        let comparison = compare_codes(&[("a".to_string(), a.clone()), ("b".to_string(), b.clone())]);
+        let diff = edge_difference(&a, &b);
+        let edges_b = word_edges(&b).into_iter().map(|e| (e.from, e.to)).collect::<BTreeSet<(String, String)>>();
+
+        return list!(properties = properties_robj(&comparison.properties),
+        shared = comparison.shared.clone(),
+        jaccard = comparison.jaccard[0][1],
+        edges_only_a = diff.lost.len() as i32,
+        edges_only_b = diff.gained.len() as i32,
+        edges_shared = (edges_b.len() - diff.gained.len()) as i32);
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod compare;
+    fn compare_codes_obj;
+    fn compare_code_pair_obj;
+}
@@ -0,0 +1,171 @@
+use std::collections::BTreeSet;
+
+use extendr_api::prelude::*;
+
+use crate::alphabet::Alphabet;
+use crate::guard::guard;
+use crate::lib_utils::{all_words_of_length, alphabet_of, new_code_from_vec};
+use crate::longest_path::LongestPathMemo;
+
+/// Returns the letters the words of a completion are built of
+///
+/// If no alphabet is given, the nucleotides `ACGT` (or `ACGU` for RNA) are used for codes over
+/// nucleotides and the letters of the code otherwise.
+///
+/// # Arguments
+/// * `words` a set of words
+/// * `alphabet` the letters or the name of a preset (see [Alphabet::from_r]). If empty the alphabet is inferred.
+pub(crate) fn completion_alphabet(words: &[String], alphabet: &[String]) -> Result<Vec<char>, String> {
+    if !alphabet.is_empty() {
+        let alphabet = Alphabet::from_r(alphabet)?;
+        alphabet.validate(words)?;
+        return Ok(alphabet.letters().to_vec());
+    }
+    let letters = alphabet_of(words);
+    let nucleotides = Alphabet::nucleotides_of(words);
+    if letters.iter().all(|c| nucleotides.contains(*c)) {
+        return Ok(nucleotides.letters().to_vec());
+    }
+
+    return Ok(letters);
+}
+
+/// Returns all words which may complete a code, in lexicographic order
+///
+/// The candidates are all words over the alphabet with one of the word lengths of the code
+/// which are not in the code.
+///
+/// # Arguments
+/// * `words` a set of words
+/// * `letters` the alphabet
+pub(crate) fn completion_candidates(words: &[String], letters: &[char]) -> Vec<String> {
+    let lengths = words.iter().map(|w| w.chars().count()).collect::<BTreeSet<usize>>();
+    let mut candidates = lengths.iter().flat_map(|l| all_words_of_length(letters, *l))
+        .filter(|w| !words.contains(w))
+        .collect::<Vec<String>>();
+    candidates.sort();
+
+    return candidates;
+}
+
+/// Returns true if the code is circular and no candidate (see [completion_candidates]) can be added
+/// without losing circularity
+///
+/// Each candidate is inserted into the incremental graph G(X) and removed again, so only the
+/// longest-path values of the vertices reaching its edges are recomputed.
+///
+/// # Arguments
+/// * `words` a set of words
+/// * `letters` the alphabet
+pub(crate) fn is_maximal_circular(words: &[String], letters: &[char]) -> bool {
+    let mut memo = LongestPathMemo::new(words);
+    if !memo.is_acyclic() {
+        return false;
+    }
+
+    return completion_candidates(words, letters).iter().all(|c| {
+        memo.insert_word(c);
+        let circular = memo.is_acyclic();
+        memo.remove_word(c);
+        !circular
+    });
+}
+
+/// Completes a circular code greedily
+///
+/// The candidates (see [completion_candidates]) are added in lexicographic order as long as the code
+/// stays circular. Returns the added words and true if the completed code is maximal, i.e. the completion
+/// was not stopped by the limit. Returns `None` if the code is not circular.
+///
+/// # Arguments
+/// * `words` a circular code
+/// * `letters` the alphabet
+/// * `limit` the maximal number of added words, 0 for unlimited
+pub(crate) fn complete_circular(words: &[String], letters: &[char], limit: usize) -> Option<(Vec<String>, bool)> {
+    let mut memo = LongestPathMemo::new(words);
+    if !memo.is_acyclic() {
+        return None;
+    }
+
+    let mut added = Vec::new();
+    for c in completion_candidates(words, letters) {
+        memo.insert_word(&c);
+        if !memo.is_acyclic() {
+            memo.remove_word(&c);
+            continue;
+        }
+        if limit > 0 && added.len() == limit {
+            memo.remove_word(&c);
+            return Some((added, false));
+        }
+        added.push(c);
+    }
+
+    return Some((added, true));
+}
+
+/// Returns the alphabet of a completion or stops with an R error
+fn completion_alphabet_from_r(words: &[String], alphabet: &[String]) -> Vec<char> {
+    match completion_alphabet(words, alphabet) {
+        Ok(letters) => return letters,
+        Err(e) => {
+            r_error!("Alphabet is not correct: {}", e);
+        }
+    }
+}
+
+/// Checks if a code is a maximal circular code
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param alphabet A String vector of single letters or the name of a preset, empty to infer the alphabet
+///
+/// @return A boolean, true if the code is maximal circular.
+///
+/// @seealso \link{is_code_maximal_circular}
+///
+#[extendr]
+fn is_code_maximal_circular_obj(tuples: Vec<String>, alphabet: Vec<String>) -> bool {
+    return guard(|| {
+        let words = new_code_from_vec(tuples).get_code();
+        let letters = completion_alphabet_from_r(&words, &alphabet);
+        return is_maximal_circular(&words, &letters);
+    });
+}
+
+/// Completes a circular code
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param limit An integer, the maximal number of added words (non-positive: unlimited)
+/// @param alphabet A String vector of single letters or the name of a preset, empty to infer the alphabet
+///
+/// @return A list with the completed code (code), the added words (added) and a boolean (maximal)
+/// which is false if the limit stopped the completion.
+///
+/// @seealso \link{complete_code}
+///
+#[extendr]
+fn complete_code_obj(tuples: Vec<String>, limit: i32, alphabet: Vec<String>) -> Robj {
+    return guard(|| {
+        let words = new_code_from_vec(tuples).get_code();
+        let letters = completion_alphabet_from_r(&words, &alphabet);
+        match complete_circular(&words, &letters, limit.max(0) as usize) {
+            Some((added, maximal)) => {
+                let mut code = words.iter().chain(added.iter()).cloned().collect::<Vec<String>>();
+                code.sort();
+                return list!(code = code, added = added, maximal = maximal);
+            }
+            None => {
+                r_error!("Code is not circular");
+            }
+        }
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod completion;
+    fn is_code_maximal_circular_obj;
+    fn complete_code_obj;
+}
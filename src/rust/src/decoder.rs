@@ -0,0 +1,305 @@
+use std::collections::HashMap;
+
+use extendr_api::prelude::*;
+
+use crate::guard::guard;
+use crate::lib_utils::new_code_from_vec;
+use crate::limits::{Budget, SearchLimits};
+use crate::trie::CodeTrie;
+
+/// An event of the streaming decoder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum DecodeEvent {
+    /// A decoded code word starting at the position `start`
+    Word { word: String, start: usize },
+    /// The characters from `start` up to (excluding) `end` can not be decoded and were skipped
+    Invalid { start: usize, end: usize },
+}
+
+/// One possible factorization of the characters fed so far which are not yet emitted
+#[derive(Debug, Clone)]
+struct Parse {
+    /// Completed words which are not yet emitted
+    pending: Vec<(String, usize)>,
+    /// The start of the current incomplete word
+    start: usize,
+}
+
+/// A streaming decoder which factorizes a sequence into code words as the data arrives
+///
+/// The decoder follows all factorizations of the data fed so far in parallel and emits a word as
+/// soon as all factorizations agree on it. For a code two factorizations can not reach the same position
+/// with the same incomplete word, so the number of followed factorizations is bounded by the number
+/// of prefixes of the words. If no factorization can be continued, the undecodable part is reported
+/// and decoding restarts after the offending character.
+#[derive(Debug, Clone)]
+pub(crate) struct Decoder {
+    /// The dictionary of the words
+    trie: CodeTrie,
+    /// The followed factorizations keyed by the trie node of their incomplete word
+    parses: HashMap<usize, Parse>,
+    /// The number of characters fed so far
+    position: usize,
+    /// The start of the data which is not yet emitted
+    emitted: usize,
+}
+
+impl Decoder {
+    /// Returns a decoder for a set of words
+    ///
+    /// # Arguments
+    /// * `words` a code
+    pub(crate) fn new(words: &[String]) -> Decoder {
+        let mut decoder = Decoder { trie: CodeTrie::from_words(words), parses: HashMap::new(), position: 0, emitted: 0 };
+        decoder.reset(0);
+        return decoder;
+    }
+
+    fn reset(&mut self, start: usize) {
+        self.parses.clear();
+        self.parses.insert(CodeTrie::ROOT, Parse { pending: vec![], start });
+        self.emitted = start;
+    }
+
+    /// Emits the words all factorizations agree on
+    fn emit_common(&mut self, events: &mut Vec<DecodeEvent>) {
+        let common = self.parses.values()
+            .map(|p| p.pending.len())
+            .min()
+            .unwrap_or(0);
+        let first = match self.parses.values().next() {
+            Some(p) => p.pending.clone(),
+            None => return,
+        };
+        let agreed = (0..common).take_while(|i| self.parses.values().all(|p| p.pending[*i] == first[*i])).count();
+        if agreed == 0 {
+            return;
+        }
+
+        for (word, start) in first.into_iter().take(agreed) {
+            self.emitted = start + word.chars().count();
+            events.push(DecodeEvent::Word { word, start });
+        }
+        for p in self.parses.values_mut() {
+            p.pending.drain(..agreed);
+        }
+    }
+
+    /// Feeds the next characters of the sequence and returns the resulting events
+    ///
+    /// # Arguments
+    /// * `data` the next characters (ASCII)
+    pub(crate) fn feed(&mut self, data: &[u8]) -> Vec<DecodeEvent> {
+        let mut events = Vec::new();
+        for &b in data {
+            let c = b as char;
+            let mut next: HashMap<usize, Parse> = HashMap::new();
+            for (node, parse) in self.parses.drain() {
+                let extended = match self.trie.step(node, c) {
+                    Some(extended) => extended,
+                    None => continue,
+                };
+                match self.trie.word_at(extended) {
+                    Some(idx) => {
+                        let mut completed = parse.clone();
+                        completed.pending.push((self.trie.words()[idx].clone(), parse.start));
+                        completed.start = self.position + 1;
+                        next.entry(CodeTrie::ROOT).or_insert(completed);
+                        if self.trie.has_children(extended) {
+                            next.entry(extended).or_insert(parse);
+                        }
+                    }
+                    None => {
+                        next.entry(extended).or_insert(parse);
+                    }
+                }
+            }
+            self.position += 1;
+            self.parses = next;
+
+            if self.parses.is_empty() {
+                events.push(DecodeEvent::Invalid { start: self.emitted, end: self.position });
+                self.reset(self.position);
+            } else {
+                self.emit_common(&mut events);
+            }
+        }
+
+        return events;
+    }
+
+    /// Ends the sequence and returns the remaining events
+    ///
+    /// The pending words of a complete factorization are emitted, an incomplete rest is reported as invalid.
+    pub(crate) fn finish(&mut self) -> Vec<DecodeEvent> {
+        let mut events = Vec::new();
+        match self.parses.remove(&CodeTrie::ROOT) {
+            Some(parse) => {
+                events.extend(parse.pending.into_iter().map(|(word, start)| DecodeEvent::Word { word, start }));
+            }
+            None if self.emitted < self.position => {
+                events.push(DecodeEvent::Invalid { start: self.emitted, end: self.position });
+            }
+            None => {}
+        }
+        self.reset(self.position);
+
+        return events;
+    }
+}
+
+/// Returns the factorizations of a sequence into words
+///
+/// The factorizations are found by a depth-first search which only follows positions from which
+/// the rest of the sequence can be factorized, ordered by the lexicographic order of the words.
+/// The words starting at a position are read from a [CodeTrie].
+/// For a code there is at most one factorization. If the sequence can not be factorized, the error
+/// is the position (character index) of the first character at which every factorization fails,
+/// the length of the sequence if it ends with an incomplete word.
+///
+/// # Arguments
+/// * `words` a set of words
+/// * `seq` a sequence (ASCII)
+/// * `budget` the limits of the search, each factorization is a result
+pub(crate) fn factorizations(words: &[String], seq: &str, budget: &mut Budget) -> Result<Vec<Vec<String>>, usize> {
+    let mut words = words.iter().filter(|w| !w.is_empty()).cloned().collect::<Vec<String>>();
+    words.sort();
+    words.dedup();
+    let trie = CodeTrie::from_words(&words);
+    let n = seq.len();
+
+    // reachable[i] is true if the prefix of length i has a factorization
+    let mut reachable = vec![false; n + 1];
+    reachable[0] = true;
+    let mut failure = 0;
+    for i in 0..n {
+        if !reachable[i] {
+            continue;
+        }
+        let mut node = CodeTrie::ROOT;
+        let mut matched = i;
+        for c in seq[i..].chars() {
+            node = match trie.step(node, c) {
+                Some(next) => next,
+                None => break,
+            };
+            matched += c.len_utf8();
+            if trie.word_at(node).is_some() {
+                reachable[matched] = true;
+            }
+        }
+        failure = failure.max(matched);
+    }
+    if !reachable[n] {
+        return Err(failure);
+    }
+
+    // factorizable[i] is true if the suffix starting at i has a factorization
+    let mut factorizable = vec![false; n + 1];
+    factorizable[n] = true;
+    for i in (0..n).rev() {
+        factorizable[i] = seq.is_char_boundary(i) && trie.prefixes_of(&seq[i..]).any(|w| factorizable[i + words[w].len()]);
+    }
+
+    fn collect(seq: &str, pos: usize, trie: &CodeTrie, factorizable: &[bool], current: &mut Vec<String>, result: &mut Vec<Vec<String>>, budget: &mut Budget) -> bool {
+        if !budget.visit() {
+            return false;
+        }
+        if pos == seq.len() {
+            if !budget.found() {
+                return false;
+            }
+            result.push(current.clone());
+            return true;
+        }
+        for w in trie.prefixes_of(&seq[pos..]).map(|w| &trie.words()[w]).filter(|w| factorizable[pos + w.len()]) {
+            current.push(w.clone());
+            let go_on = collect(seq, pos + w.len(), trie, factorizable, current, result, budget);
+            current.pop();
+            if !go_on {
+                return false;
+            }
+        }
+
+        return true;
+    }
+
+    let mut result = Vec::new();
+    collect(seq, 0, &trie, &factorizable, &mut vec![], &mut result, budget);
+
+    return Ok(result);
+}
+
+/// Decodes a sequence into code words
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param seq A String, the sequence
+/// @param max_results A numeric value, the maximal number of factorizations (non-positive: unlimited)
+///
+/// @return A list with the factorizations (factorizations), the failure position (failure, starting with 1,
+/// 0 if the sequence was decoded) and a boolean (truncated) which is true if the limit stopped the search.
+///
+/// @seealso \link{decode_sequence}
+///
+#[extendr]
+fn decode_sequence_obj(tuples: Vec<String>, seq: String, max_results: f64) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let mut budget = Budget::new(SearchLimits::from_r(0.0, max_results, 0.0));
+        return match factorizations(&code.get_code(), &seq, &mut budget) {
+            Ok(result) => list!(factorizations = result.iter().map(|f| f.iter().collect_robj()).collect::<Vec<Robj>>(),
+                failure = 0, truncated = budget.is_truncated()),
+            Err(failure) => list!(factorizations = Vec::<Robj>::new(), failure = failure as i32 + 1, truncated = false),
+        };
+    });
+}
+
+/// Decodes a sequence which arrives in chunks
+///
+/// The chunks are fed one after another into a streaming decoder which factorizes the sequence
+/// into code words incrementally, e.g. to process reads on the fly. A word is reported as soon as
+/// it is certain, i.e. all factorizations of the data fed so far agree on it. Parts of the sequence
+/// which can not be factorized are reported as invalid and skipped. The result does not depend on the chunking.
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param chunks A String vector, the consecutive parts of the sequence
+///
+/// @return A list with the kind of each event (kind, "word" or "invalid"), its start (start, starting with 1),
+/// its end (end, inclusive) and the decoded word (word, empty for invalid parts).
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGG", "AC"))
+/// decode_stream(code, c("ACGC", "GGAC"))
+///
+/// @export
+#[extendr]
+fn decode_stream(tuples: Vec<String>, chunks: Vec<String>) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let mut decoder = Decoder::new(&code.get_code());
+        let mut events = Vec::new();
+        for chunk in &chunks {
+            events.extend(decoder.feed(chunk.as_bytes()));
+        }
+        events.extend(decoder.finish());
+
+        let rows = events.iter().map(|e| match e {
+            DecodeEvent::Word { word, start } => ("word", *start, start + word.chars().count(), word.clone()),
+            DecodeEvent::Invalid { start, end } => ("invalid", *start, *end, String::new()),
+        }).collect::<Vec<(&str, usize, usize, String)>>();
+
+        return list!(kind = rows.iter().map(|r| r.0).collect::<Vec<&str>>(),
+        start = rows.iter().map(|r| r.1 as i32 + 1).collect::<Vec<i32>>(),
+        end = rows.iter().map(|r| r.2 as i32).collect::<Vec<i32>>(),
+        word = rows.iter().map(|r| r.3.clone()).collect::<Vec<String>>());
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod decoder;
+    fn decode_stream;
+    fn decode_sequence_obj;
+}
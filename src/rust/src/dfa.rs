@@ -0,0 +1,223 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use extendr_api::prelude::*;
+
+use crate::automaton::FlowerAutomaton;
+use crate::guard::guard;
+use crate::lib_utils::new_code_from_vec;
+
+impl FlowerAutomaton {
+    /// Returns all letters used by the transitions (sorted)
+    pub(crate) fn alphabet(&self) -> Vec<char> {
+        return self.transitions.iter().map(|t| t.1).collect::<BTreeSet<char>>().into_iter().collect();
+    }
+
+    /// Returns all successors of `state` reading `letter`
+    pub(crate) fn successors(&self, state: usize, letter: char) -> impl Iterator<Item = usize> + '_ {
+        return self.transitions.iter().filter(move |t| t.0 == state && t.1 == letter).map(|t| t.2);
+    }
+}
+
+/// A deterministic (partial) finite automaton
+#[derive(Debug, Clone)]
+pub(crate) struct Dfa {
+    pub(crate) alphabet: Vec<char>,
+    /// `delta[state][letter index]` is the successor of `state`
+    pub(crate) delta: Vec<Vec<Option<usize>>>,
+    pub(crate) initial: usize,
+    pub(crate) finals: Vec<bool>,
+}
+
+impl Dfa {
+    /// Returns the DFA of the flower automaton built by the subset construction
+    ///
+    /// # Arguments
+    /// * `flower` a flower automaton
+    pub(crate) fn determinize(flower: &FlowerAutomaton) -> Dfa {
+        trace_span!("determinization", states = flower.n_states);
+        let alphabet = flower.alphabet();
+        let start = BTreeSet::from([0]);
+        let mut ids = BTreeMap::from([(start.clone(), 0)]);
+        let mut subsets = vec![start.clone()];
+        let mut queue = VecDeque::from([start]);
+        let mut delta: Vec<Vec<Option<usize>>> = vec![];
+
+        while let Some(subset) = queue.pop_front() {
+            let mut row = vec![];
+            for a in &alphabet {
+                let next = subset.iter().flat_map(|s| flower.successors(*s, *a)).collect::<BTreeSet<usize>>();
+                if next.is_empty() {
+                    row.push(None);
+                    continue;
+                }
+                let id = match ids.get(&next) {
+                    Some(id) => *id,
+                    None => {
+                        let id = subsets.len();
+                        ids.insert(next.clone(), id);
+                        subsets.push(next.clone());
+                        queue.push_back(next);
+                        id
+                    }
+                };
+                row.push(Some(id));
+            }
+            delta.push(row);
+        }
+
+        let finals = subsets.iter().map(|s| s.contains(&0)).collect();
+        return Dfa { alphabet, delta, initial: 0, finals };
+    }
+
+    /// Returns the minimal DFA recognizing the same language (Moore's partition refinement)
+    ///
+    /// The states of the result are numbered in breadth first order starting with the initial state `0`.
+    pub(crate) fn minimize(&self) -> Dfa {
+        let n = self.delta.len();
+        let mut classes = self.finals.iter().map(|f| *f as usize).collect::<Vec<usize>>();
+        let mut n_classes = 0;
+        loop {
+            let mut signatures = BTreeMap::new();
+            let mut next_classes = vec![0; n];
+            for s in 0..n {
+                let sig = (classes[s], self.delta[s].iter().map(|t| t.map(|t| classes[t])).collect::<Vec<Option<usize>>>());
+                let len = signatures.len();
+                next_classes[s] = *signatures.entry(sig).or_insert(len);
+            }
+            let stable = signatures.len() == n_classes;
+            n_classes = signatures.len();
+            classes = next_classes;
+            if stable {
+                break;
+            }
+        }
+
+        // Renumber classes in breadth first order
+        let mut order = vec![None; n_classes];
+        let mut representatives = vec![];
+        let mut queue = VecDeque::from([self.initial]);
+        order[classes[self.initial]] = Some(0);
+        representatives.push(self.initial);
+        while let Some(s) = queue.pop_front() {
+            for t in self.delta[s].iter().flatten() {
+                if order[classes[*t]].is_none() {
+                    order[classes[*t]] = Some(representatives.len());
+                    representatives.push(*t);
+                    queue.push_back(*t);
+                }
+            }
+        }
+
+        let delta = representatives.iter()
+            .map(|s| self.delta[*s].iter().map(|t| t.and_then(|t| order[classes[t]])).collect())
+            .collect();
+        let finals = representatives.iter().map(|s| self.finals[*s]).collect();
+        return Dfa { alphabet: self.alphabet.clone(), delta, initial: 0, finals };
+    }
+
+    /// Returns all transitions `(from, letter, to)`
+    pub(crate) fn transitions(&self) -> Vec<(usize, char, usize)> {
+        let mut result = vec![];
+        for (s, row) in self.delta.iter().enumerate() {
+            for (a, t) in row.iter().enumerate() {
+                if let Some(t) = t {
+                    result.push((s, self.alphabet[a], *t));
+                }
+            }
+        }
+
+        return result;
+    }
+}
+
+/// Returns the DOT (graphviz) representation of an automaton
+///
+/// Transitions between the same pair of states are merged into one edge with a comma separated label.
+///
+/// # Arguments
+/// * `transitions` all transitions `(from, letter, to)`
+/// * `n_states` the number of states
+/// * `initial` the initial state
+/// * `finals` the final states
+pub(crate) fn automaton_to_dot(transitions: &[(usize, char, usize)], n_states: usize, initial: usize, finals: &[usize]) -> String {
+    let mut labels: BTreeMap<(usize, usize), Vec<char>> = BTreeMap::new();
+    for (from, a, to) in transitions {
+        labels.entry((*from, *to)).or_default().push(*a);
+    }
+
+    let mut dot = String::from("digraph automaton {\n    rankdir = LR;\n    start [shape = point];\n");
+    for s in 0..n_states {
+        let shape = if finals.contains(&s) { "doublecircle" } else { "circle" };
+        dot.push_str(&format!("    {} [shape = {}];\n", s, shape));
+    }
+    dot.push_str(&format!("    start -> {};\n", initial));
+    for ((from, to), letters) in labels {
+        let label = letters.iter().map(|c| c.to_string()).collect::<Vec<String>>().join(",");
+        dot.push_str(&format!("    {} -> {} [label = \"{}\"];\n", from, to, label));
+    }
+    dot.push_str("}\n");
+
+    return dot;
+}
+
+/// Returns the minimal DFA of X* in DOT format
+///
+/// The flower automaton of a code \emph{X} recognizes \emph{X*}. This function
+/// determinizes and minimizes the flower automaton and returns the minimal
+/// deterministic automaton recognizing \emph{X*} in the DOT format of graphviz
+/// (\link{https://graphviz.org}). The initial state is 0, final states are drawn as double circles.
+///
+/// @param tuples A gcatbase::gcat.code object
+///
+/// @return A String, the automaton in DOT format.
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGG", "AC"))
+/// cat(minimal_dfa_dot(code))
+///
+/// @seealso \link{flower_automaton_dot}
+///
+/// @export
+#[extendr]
+fn minimal_dfa_dot(tuples: Vec<String>) -> String {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let dfa = Dfa::determinize(&FlowerAutomaton::new(&code.get_code())).minimize();
+        let finals = dfa.finals.iter().enumerate().filter(|(_, f)| **f).map(|(s, _)| s).collect::<Vec<usize>>();
+        return automaton_to_dot(&dfa.transitions(), dfa.delta.len(), dfa.initial, &finals);
+    });
+}
+
+/// Returns the flower automaton of a code in DOT format
+///
+/// The flower automaton of a code \emph{X} has one center state 0 which is initial and final.
+/// Each word of \emph{X} forms a cycle (petal) through the center.
+///
+/// @param tuples A gcatbase::gcat.code object
+///
+/// @return A String, the automaton in DOT format.
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGG", "AC"))
+/// cat(flower_automaton_dot(code))
+///
+/// @seealso \link{minimal_dfa_dot}
+///
+/// @export
+#[extendr]
+fn flower_automaton_dot(tuples: Vec<String>) -> String {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let flower = FlowerAutomaton::new(&code.get_code());
+        return automaton_to_dot(&flower.transitions, flower.n_states, 0, &[0]);
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod dfa;
+    fn minimal_dfa_dot;
+    fn flower_automaton_dot;
+}
@@ -0,0 +1,119 @@
+use extendr_api::prelude::*;
+
+use crate::certificate::{ambiguity_certificates_limited, check_circularity};
+use crate::comma_free::{k_comma_free_violation, suffix_prefix_overlap};
+use crate::guard::guard;
+use crate::lib_utils::{new_code_from_vec, remove_duplicates};
+use crate::limits::{Budget, SearchLimits};
+
+/// The maximal run time in milliseconds of each counterexample search
+const SEARCH_MILLIS: f64 = 1000.0;
+
+/// A violated property of a set of words together with a minimal counterexample
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Diagnostic {
+    /// The violated property, e.g. `"circular"`
+    pub(crate) property: &'static str,
+    /// The counterexample, e.g. a word or a sequence
+    pub(crate) counterexample: String,
+    /// A human readable explanation of the counterexample
+    pub(crate) detail: String,
+}
+
+/// Returns a diagnostic for each property the set of words violates
+///
+/// The properties are: no duplicated words, code (unique decodability), circular,
+/// comma-free and strong comma-free. The searches for an ambiguous sequence and a cycle stop
+/// after the first counterexample or after [SEARCH_MILLIS].
+///
+/// # Arguments
+/// * `words` a set of words, possibly with duplicates
+pub(crate) fn explain(words: Vec<String>) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let (words, duplicates) = remove_duplicates(words);
+    if let Some(w) = duplicates.first() {
+        diagnostics.push(Diagnostic {
+            property: "distinct",
+            counterexample: w.clone(),
+            detail: format!("The word '{}' occurs more than once", w),
+        });
+    }
+
+    let mut budget = Budget::new(SearchLimits::from_r(SEARCH_MILLIS, 1.0, 0.0));
+    if let Some(certificate) = ambiguity_certificates_limited(&words, &mut budget).first() {
+        diagnostics.push(Diagnostic {
+            property: "code",
+            counterexample: certificate.sequence.clone(),
+            detail: format!("The sequence '{}' can be factorized as {} and as {}", certificate.sequence,
+                            certificate.first.join("."), certificate.second.join(".")),
+        });
+    }
+
+    let mut budget = Budget::new(SearchLimits::from_r(SEARCH_MILLIS, 1.0, 0.0));
+    if let Some(certificate) = check_circularity(&words, &mut budget) {
+        diagnostics.push(Diagnostic {
+            property: "circular",
+            counterexample: certificate.word.clone(),
+            detail: format!("The cyclic path [{}] represents the circular sequence '{}' which can be read as {} and as {}",
+                            certificate.cycle.join(", "), certificate.word, certificate.first.join("."), certificate.second.join(".")),
+        });
+    }
+
+    if let Some((concatenation, w)) = k_comma_free_violation(&words, 2) {
+        diagnostics.push(Diagnostic {
+            property: "comma_free",
+            counterexample: concatenation.concat(),
+            detail: format!("The word '{}' occurs in '{}' at a position which is not a word boundary", w, concatenation.join(".")),
+        });
+    }
+
+    if let Some((overlap, suffix_word, prefix_word)) = suffix_prefix_overlap(&words) {
+        diagnostics.push(Diagnostic {
+            property: "strong_comma_free",
+            counterexample: overlap.clone(),
+            detail: format!("'{}' is a suffix of '{}' and a prefix of '{}'", overlap, suffix_word, prefix_word),
+        });
+    }
+
+    return diagnostics;
+}
+
+/// Explains which properties a code violates
+///
+/// Boolean checks like \link{is_code_circular} do not tell why a code fails.
+/// This function returns a minimal counterexample for each violated property:
+/// a duplicated word (distinct), a sequence with two factorizations (code),
+/// a circular sequence represented by a cyclic path (circular), a concatenation of two words
+/// containing a word at a position which is not a word boundary (comma_free) and
+/// a suffix of a word which is a prefix of a word (strong_comma_free).
+/// The counterexample searches stop after one second.
+///
+/// @param tuples A String vector, a set of words (duplicates are reported)
+///
+/// @return A list with the violated properties (property), the counterexamples (counterexample)
+/// and explanations (detail). All entries are empty if the code satisfies all properties.
+///
+/// @examples
+/// explain_code(c("ACG", "CGA", "CA"))
+///
+/// @seealso \link{is_code}, \link{is_code_circular}, \link{is_code_comma_free}, \link{strong_comma_free_violation}
+///
+/// @export
+#[extendr]
+fn explain_code(tuples: Vec<String>) -> Robj {
+    return guard(|| {
+        new_code_from_vec(tuples.clone());
+        let diagnostics = explain(tuples);
+        return list!(property = diagnostics.iter().map(|d| d.property).collect::<Vec<&str>>(),
+        counterexample = diagnostics.iter().map(|d| d.counterexample.clone()).collect::<Vec<String>>(),
+        detail = diagnostics.iter().map(|d| d.detail.clone()).collect::<Vec<String>>());
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod explain;
+    fn explain_code;
+}
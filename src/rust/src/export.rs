@@ -0,0 +1,191 @@
+use extendr_api::prelude::*;
+
+use crate::annotation::{annotate, Annotation};
+use crate::coding::{predict_coding_regions, CodingRegion};
+use crate::guard::guard;
+use crate::lib_utils::{new_code_from_vec, NamedList};
+use crate::scan::{frame_modulus, AhoCorasick};
+
+/// Options of the coding region prediction
+#[derive(Debug, Clone)]
+pub(crate) struct CodingOptions {
+    /// The window length
+    pub(crate) window: usize,
+    /// The distance between the starts of two windows
+    pub(crate) step: usize,
+    /// The minimal margin of a coding window
+    pub(crate) threshold: f64,
+}
+
+impl CodingOptions {
+    /// Returns the options passed from R as list with the entries window, step and threshold
+    ///
+    /// # Arguments
+    /// * `options` the list passed from R
+    pub(crate) fn from_r(options: &Robj) -> Result<CodingOptions, String> {
+        let options = NamedList::new(options, "coding options")?;
+        return Ok(CodingOptions {
+            window: options.number("window")?.max(1.0) as usize,
+            step: options.number("step")?.max(1.0) as usize,
+            threshold: options.number("threshold")?,
+        });
+    }
+}
+
+/// An interval of a sequence which can be exported to genome browser formats
+pub(crate) trait Feature {
+    /// The first position (starting with 0) and the position after the last character
+    fn interval(&self) -> (usize, usize);
+    /// The frame of the feature
+    fn frame(&self) -> usize;
+    /// The score of the feature
+    fn score(&self) -> f64;
+    /// The BED score of the feature (0 to 1000)
+    fn bed_score(&self) -> u32;
+    /// The name of the kind of the feature
+    fn name(&self) -> &'static str;
+    /// The GFF3 type (Sequence Ontology term) of the feature
+    fn gff_type(&self) -> &'static str;
+}
+
+impl Feature for Annotation {
+    fn interval(&self) -> (usize, usize) {
+        return (self.start, self.end);
+    }
+
+    fn frame(&self) -> usize {
+        return self.frame;
+    }
+
+    fn score(&self) -> f64 {
+        return self.words as f64;
+    }
+
+    fn bed_score(&self) -> u32 {
+        return (self.words as u32).min(1000);
+    }
+
+    fn name(&self) -> &'static str {
+        return "code_word_run";
+    }
+
+    fn gff_type(&self) -> &'static str {
+        return "sequence_feature";
+    }
+}
+
+impl Feature for CodingRegion {
+    fn interval(&self) -> (usize, usize) {
+        return (self.start, self.end);
+    }
+
+    fn frame(&self) -> usize {
+        return self.frame;
+    }
+
+    fn score(&self) -> f64 {
+        return self.score;
+    }
+
+    fn bed_score(&self) -> u32 {
+        return (self.score.clamp(0.0, 1.0) * 1000.0).round() as u32;
+    }
+
+    fn name(&self) -> &'static str {
+        return "coding_region";
+    }
+
+    fn gff_type(&self) -> &'static str {
+        return "CDS";
+    }
+}
+
+/// Returns the features in the BED6 format (0-based, half-open intervals)
+///
+/// # Arguments
+/// * `features` the features
+/// * `chrom` the name of the sequence
+pub(crate) fn to_bed<F: Feature>(features: &[F], chrom: &str) -> String {
+    let mut out = String::new();
+    for f in features {
+        let (start, end) = f.interval();
+        out.push_str(&format!("{}\t{}\t{}\t{}_frame{}\t{}\t+\n", chrom, start, end, f.name(), f.frame(), f.bed_score()));
+    }
+
+    return out;
+}
+
+/// Returns the features in the GFF3 format (1-based, closed intervals)
+///
+/// The phase of a `CDS` feature is the number of characters before the first complete tuple of its frame.
+///
+/// # Arguments
+/// * `features` the features
+/// * `seqid` the name of the sequence
+/// * `n` the tuple length of the frames
+pub(crate) fn to_gff3<F: Feature>(features: &[F], seqid: &str, n: usize) -> String {
+    let mut out = String::from("##gff-version 3\n");
+    for (i, f) in features.iter().enumerate() {
+        let (start, end) = f.interval();
+        let phase = if f.gff_type() == "CDS" { ((f.frame() + n - start % n) % n).to_string() } else { ".".to_string() };
+        out.push_str(&format!("{}\tgcatcirc\t{}\t{}\t{}\t{}\t+\t{}\tID={}_{};Name={};frame={}\n",
+                              seqid, f.gff_type(), start + 1, end, f.score(), phase, f.name(), i + 1, f.name(), f.frame()));
+    }
+
+    return out;
+}
+
+/// Returns the features in the requested format
+fn export_features<F: Feature>(features: &[F], format: &str, seqid: &str, n: usize) -> Result<String, String> {
+    match format.to_lowercase().as_str() {
+        "bed" => Ok(to_bed(features, seqid)),
+        "gff3" | "gff" => Ok(to_gff3(features, seqid, n)),
+        _ => Err(format!("Unknown format '{}', expected 'bed' or 'gff3'", format)),
+    }
+}
+
+/// Exports the results of a sequence analysis to BED or GFF3
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param seq A String, the sequence
+/// @param analysis A String, "annotation" (see \link{annotate_sequence}) or "coding" (predicted coding regions)
+/// @param format A String, "bed" or "gff3"
+/// @param seqid A String, the name of the sequence (chromosome)
+/// @param options A list with the window length (window), the step (step) and the minimal coverage margin
+/// (threshold) of the coding region prediction
+///
+/// @return A String, the content of the BED or GFF3 file.
+///
+/// @seealso \link{export_sequence_features}
+///
+#[extendr]
+fn export_sequence_features_obj(tuples: Vec<String>, seq: String, analysis: String, format: String, seqid: String,
+                                options: Robj) -> String {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let ac = AhoCorasick::new(&code.get_code());
+        let n = frame_modulus(ac.words());
+        let result = match analysis.to_lowercase().as_str() {
+            "annotation" => export_features(&annotate(&ac, &seq), &format, &seqid, n),
+            "coding" => CodingOptions::from_r(&options).and_then(|o| {
+                export_features(&predict_coding_regions(&ac, &seq, o.window, o.step, o.threshold), &format, &seqid, n)
+            }),
+            _ => Err(format!("Unknown analysis '{}', expected 'annotation' or 'coding'", analysis)),
+        };
+
+        match result {
+            Ok(s) => return s,
+            Err(e) => {
+                r_error!("Export is not possible: {}", e);
+            }
+        }
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod export;
+    fn export_sequence_features_obj;
+}
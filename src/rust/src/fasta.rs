@@ -0,0 +1,131 @@
+use extendr_api::prelude::*;
+
+use crate::guard::guard;
+use crate::lib_utils::new_code_from_vec;
+use crate::parallel::par_map;
+use crate::scan::{find_on_strands, frame_modulus, strand_frame_coverage, AhoCorasick, Strand};
+
+/// A record of a FASTA file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FastaRecord {
+    /// The first word of the header line (without `>`)
+    pub(crate) id: String,
+    pub(crate) seq: String,
+}
+
+/// Parses the records of a FASTA text
+///
+/// The sequence lines of a record are concatenated without whitespace, empty lines and comment lines
+/// (starting with `;`) are ignored.
+///
+/// # Arguments
+/// * `text` the content of a FASTA file
+pub(crate) fn parse_fasta(text: &str) -> Result<Vec<FastaRecord>, String> {
+    let mut records: Vec<FastaRecord> = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(';') {
+            continue;
+        }
+        if let Some(header) = line.strip_prefix('>') {
+            let id = header.split_whitespace().next().unwrap_or("").to_string();
+            records.push(FastaRecord { id, seq: String::new() });
+            continue;
+        }
+        match records.last_mut() {
+            Some(record) => record.seq.extend(line.chars().filter(|c| !c.is_whitespace())),
+            None => return Err(format!("Line {}: sequence before the first header", i + 1)),
+        }
+    }
+
+    return Ok(records);
+}
+
+/// Reads the records of a FASTA file
+///
+/// # Arguments
+/// * `path` the path of the file
+pub(crate) fn read_fasta(path: &str) -> Result<Vec<FastaRecord>, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    return parse_fasta(&text);
+}
+
+/// The analysis of a record by a code
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RecordReport {
+    pub(crate) id: String,
+    pub(crate) length: usize,
+    /// The number of occurrences of code words (on both strands if analysed)
+    pub(crate) hits: usize,
+    /// The frame with the highest coverage
+    pub(crate) best_frame: usize,
+    /// The strand of the best frame
+    pub(crate) best_strand: Strand,
+    /// The coverage of the best frame
+    pub(crate) coverage: f64,
+}
+
+/// Analyses the records in parallel
+///
+/// Each record is scanned for code words (see [find_on_strands]) and the frame with the highest
+/// coverage (see [strand_frame_coverage]) is reported. Ties are resolved by the smaller frame on the forward strand.
+///
+/// # Arguments
+/// * `ac` the automaton of the code
+/// * `records` the records
+/// * `both_strands` true if the reverse complement of each record is analysed too
+pub(crate) fn analyze_records(ac: &AhoCorasick, records: &[FastaRecord], both_strands: bool) -> Vec<RecordReport> {
+    let n = frame_modulus(ac.words());
+    return par_map(records, |record| {
+        let coverage = strand_frame_coverage(ac, &record.seq, both_strands);
+        let best = (0..coverage.len()).fold(0, |best, i| if coverage[i] > coverage[best] { i } else { best });
+        RecordReport {
+            id: record.id.clone(),
+            length: record.seq.chars().count(),
+            hits: find_on_strands(ac, &record.seq, both_strands).len(),
+            best_frame: best % n,
+            best_strand: if best < n { Strand::Forward } else { Strand::Reverse },
+            coverage: coverage[best],
+        }
+    });
+}
+
+/// Analyses all records of a FASTA file
+///
+/// @param path A String, the path of the FASTA file
+/// @param tuples A gcatbase::gcat.code object
+/// @param both_strands A boolean, true if the reverse complement of each record is analysed too
+///
+/// @return A list with the ids (id), the lengths (length), the number of code word occurrences (hits),
+/// the best frames (frame), their strands (strand) and their coverages (coverage) of all records.
+///
+/// @seealso \link{analyze_fasta}
+///
+#[extendr]
+fn analyze_fasta_obj(path: String, tuples: Vec<String>, both_strands: bool) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let records = match read_fasta(&path) {
+            Ok(records) => records,
+            Err(e) => {
+                r_error!("FASTA file is not correct: {}", e);
+            }
+        };
+        let reports = analyze_records(&AhoCorasick::new(&code.get_code()), &records, both_strands);
+
+        return list!(id = reports.iter().map(|r| r.id.clone()).collect::<Vec<String>>(),
+        length = reports.iter().map(|r| r.length as f64).collect::<Vec<f64>>(),
+        hits = reports.iter().map(|r| r.hits as f64).collect::<Vec<f64>>(),
+        frame = reports.iter().map(|r| r.best_frame as i32).collect::<Vec<i32>>(),
+        strand = reports.iter().map(|r| r.best_strand.symbol()).collect::<Vec<&str>>(),
+        coverage = reports.iter().map(|r| r.coverage).collect::<Vec<f64>>());
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod fasta;
+    fn analyze_fasta_obj;
+}
@@ -0,0 +1,101 @@
+use extendr_api::prelude::*;
+
+use crate::guard::guard;
+use crate::lib_utils::new_code_from_vec;
+use crate::longest_path::LongestPathMemo;
+use crate::word_graph::{heaviest_path, represented_path_word, PathWord};
+
+/// Returns the sequence represented by a heaviest path of G(X)
+///
+/// Returns `None` if the code is not circular, i.e. there are arbitrarily long sequences
+/// which can be read in two frames. A code without edges (only words of length 1) results in an empty path word.
+///
+/// # Arguments
+/// * `words` a set of words
+/// * `weight` the weight of a vertex, see [heaviest_path]
+fn heaviest_path_word(words: &[String], weight: impl Fn(&str) -> usize) -> Option<PathWord> {
+    if !LongestPathMemo::new(words).is_acyclic() {
+        return None;
+    }
+
+    return Some(represented_path_word(&heaviest_path(words, weight).unwrap_or_default(), false));
+}
+
+/// Returns the reading frame window of a circular code and its witness
+///
+/// A path of G(X) represents a sequence which can be read in two frames (see [represented_path_word]).
+/// The window is one more than the number of letters of the longest such sequence: every window of
+/// this length of a sequence of code words determines the reading frame. The witness is the longest
+/// sequence which does not. Returns `None` if the code is not circular.
+///
+/// # Arguments
+/// * `words` a set of words
+pub(crate) fn reading_frame_window(words: &[String]) -> Option<(usize, PathWord)> {
+    let pw = heaviest_path_word(words, |v| v.chars().count())?;
+    return Some((pw.word.chars().count() + 1, pw));
+}
+
+/// Returns the synchronization delay of a circular code and its witness
+///
+/// The delay is one more than the maximal number of consecutive code words which can also be read
+/// in another frame, i.e. after reading this number of consecutive code words the frame is known.
+/// The witness words are read from the longest path of G(X), the other frame is given by the other
+/// decomposition of the path. Returns `None` if the code is not circular.
+///
+/// # Arguments
+/// * `words` a set of words
+pub(crate) fn synchronization_delay(words: &[String]) -> Option<(usize, Vec<String>, PathWord)> {
+    let pw = heaviest_path_word(words, |_| 1)?;
+    let witness = if pw.first_decomposition.len() >= pw.second_decomposition.len() { pw.first_decomposition.clone() } else { pw.second_decomposition.clone() };
+
+    return Some((witness.len() + 1, witness, pw));
+}
+
+/// Returns the reading frame window of a code
+///
+/// @param tuples A gcatbase::gcat.code object
+///
+/// @return A list with the window (window, Inf if the code is not circular), the longest sequence
+/// which can be read in two frames (sequence) and the words of both readings (witness).
+///
+/// @seealso \link{code_reading_frame_window}
+///
+#[extendr]
+fn code_reading_frame_window_obj(tuples: Vec<String>) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        return match reading_frame_window(&code.get_code()) {
+            Some((window, pw)) => list!(window = window as f64, sequence = pw.word, witness = pw.edge_words),
+            None => list!(window = f64::INFINITY, sequence = String::new(), witness = Vec::<String>::new()),
+        };
+    });
+}
+
+/// Returns the synchronization delay of a code
+///
+/// @param tuples A gcatbase::gcat.code object
+///
+/// @return A list with the delay (delay, Inf if the code is not circular), the sequence of the
+/// witness (sequence) and the consecutive code words which can be read in another frame (witness).
+///
+/// @seealso \link{code_synchronization_delay}
+///
+#[extendr]
+fn code_synchronization_delay_obj(tuples: Vec<String>) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        return match synchronization_delay(&code.get_code()) {
+            Some((delay, witness, pw)) => list!(delay = delay as f64, sequence = pw.word, witness = witness),
+            None => list!(delay = f64::INFINITY, sequence = String::new(), witness = Vec::<String>::new()),
+        };
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod frame_retrieval;
+    fn code_reading_frame_window_obj;
+    fn code_synchronization_delay_obj;
+}
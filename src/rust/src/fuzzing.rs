@@ -0,0 +1,67 @@
+// Entry points of the fuzz targets in `fuzz/` (only with the `fuzzing` feature).
+//
+// Each function takes arbitrary input as it may reach the package from R and runs the code path
+// behind an R entry point without calling into R. A panic aborts the whole R session, so every
+// input has to end in a result or an error.
+
+use crate::checkpoint::Checkpoint;
+use crate::fasta::parse_fasta;
+use crate::gcat::{longest_paths, CircCode};
+use crate::graph_structure::IndexedGraph;
+use crate::lib_utils::{parse_code_str, remove_duplicates};
+use crate::longest_path::LongestPathMemo;
+use crate::overlap_graph::OverlapGraph;
+use crate::transform::Transformation;
+use crate::word_graph::word_edges;
+
+/// Splits fuzz input into words at line breaks and NUL bytes, like a character vector passed from R
+pub fn words_from_bytes(data: &[u8]) -> Vec<String> {
+    return String::from_utf8_lossy(data)
+        .split(['\n', '\0'])
+        .map(|w| w.to_string())
+        .collect();
+}
+
+/// Creates a code from arbitrary words and checks its properties
+pub fn code_from_words(data: &[u8]) {
+    let (words, _) = remove_duplicates(words_from_bytes(data));
+    if let Ok(code) = CircCode::new_from_vec(words) {
+        let _ = (code.is_code(), code.is_circular(), code.is_comma_free(), code.is_strong_comma_free());
+    }
+}
+
+/// Builds the graphs of arbitrary words: G(X) of the library, its indexed form, the incremental
+/// longest-path graph and the overlap graph
+pub fn graphs_from_words(data: &[u8]) {
+    let (words, _) = remove_duplicates(words_from_bytes(data));
+    if let Ok(g) = CircCode::new_from_vec(words.clone()).and_then(|c| c.get_associated_graph()) {
+        let indexed = IndexedGraph::new(&g);
+        let _ = (indexed.strongly_connected_components(), indexed.girth(), indexed.longest_path_length());
+        let _ = (longest_paths(&g), g.all_cycles_as_vertex_vec());
+    }
+    let _ = word_edges(&words);
+    let _ = LongestPathMemo::new(&words).is_acyclic();
+    let overlap = OverlapGraph::new(&words);
+    let _ = overlap.highlighted_edges();
+}
+
+/// Parses an arbitrary string as delimiter separated list of words
+pub fn code_string(data: &[u8]) {
+    let _ = parse_code_str(&String::from_utf8_lossy(data));
+}
+
+/// Parses an arbitrary string as FASTA text
+pub fn fasta(data: &[u8]) {
+    let _ = parse_fasta(&String::from_utf8_lossy(data));
+}
+
+/// Parses an arbitrary string as checkpoint of a search and as transformation
+pub fn checkpoint_and_transformation(data: &[u8]) {
+    let text = String::from_utf8_lossy(data);
+    if let Ok(checkpoint) = Checkpoint::parse(&text, "fuzz") {
+        let _ = checkpoint.get("kind");
+    }
+    if let Ok(t) = Transformation::parse(&text) {
+        let _ = t.apply(&words_from_bytes(data));
+    }
+}
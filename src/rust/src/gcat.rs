@@ -0,0 +1,125 @@
+//! The public API of `rust_gcatcirc_lib` used by the R wrapper
+//!
+//! All modules import the inner library through this facade instead of its module paths,
+//! so the wrapper depends on a single, documented surface of the library. A change of the
+//! library's module layout only affects this file.
+//!
+//! # Stability
+//!
+//! This module is the only public API of the crate, all other modules are internal. The
+//! hidden modules `testing` and `fuzzing` only exist behind their features for the property
+//! tests and the fuzz targets and are not covered by this guarantee.
+//! It follows semantic versioning: the names, signatures and behaviour of its items only
+//! change with a new major version (or a new minor version before 1.0). The error enum
+//! [GcatError] and [Transformation] are `#[non_exhaustive]`, so new variants can be added in
+//! a minor version and matches outside the crate need a wildcard arm. A change of the
+//! inner library which is not visible through this module is not a breaking change.
+
+use std::fmt;
+
+/// A code, i.e. a set of words, see [rust_gcatcirc_lib::code::CircCode]
+pub use rust_gcatcirc_lib::code::CircCode;
+/// The representing graph G(X) of a code, see [rust_gcatcirc_lib::graph_circ::CircGraph]
+pub use rust_gcatcirc_lib::graph_circ::CircGraph;
+/// A transformation of a set of words, e.g. the reverse complement
+pub use crate::transform::Transformation;
+
+/// An error of a call into the library
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GcatError {
+    /// The library rejected the input, e.g. words of a code over different alphabets
+    Invalid(String),
+    /// The library panicked, the message of the panic
+    Panic(String),
+}
+
+impl fmt::Display for GcatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GcatError::Invalid(e) => write!(f, "{}", e),
+            GcatError::Panic(e) => write!(f, "The library panicked: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for GcatError {}
+
+/// Runs a call into the library and returns a panic of the library as error
+///
+/// The library reports some degenerate inputs by panicking instead of returning an error. The
+/// generated R entry points already catch panics, but only report that the function panicked.
+/// Guarded calls keep the message, so the wrapper can stop with a precise R error instead.
+///
+/// # Arguments
+/// * `f` the call into the library
+pub fn guarded<T>(f: impl FnOnce() -> T) -> Result<T, GcatError> {
+    return std::panic::catch_unwind(std::panic::AssertUnwindSafe(f))
+        .map_err(|payload| GcatError::Panic(crate::guard::panic_message(payload.as_ref())));
+}
+
+/// Returns the code of a set of words, see [guarded]
+///
+/// Empty words are rejected before the library is called.
+///
+/// # Arguments
+/// * `words` a set of words
+pub fn new_code(words: Vec<String>) -> Result<CircCode, GcatError> {
+    if let Some(pos) = words.iter().position(|w| w.is_empty()) {
+        return Err(GcatError::Invalid(format!("word {} is empty", pos + 1)));
+    }
+
+    return guarded(|| CircCode::new_from_vec(words)).and_then(|c| c.map_err(GcatError::Invalid));
+}
+
+/// Returns the graph associated to a code, see [guarded]
+pub fn associated_graph(code: &CircCode) -> Result<CircGraph, GcatError> {
+    return guarded(|| code.get_associated_graph()).and_then(|g| g.map_err(GcatError::Invalid));
+}
+
+/// Returns all longest paths of a graph as vertex lists, `None` if the graph is cyclic
+///
+/// The library unwraps the last found path and panics on graphs without start edges, so the paths are
+/// enumerated by the longest path search of the crate instead. A graph without edges
+/// (without vertices or with a single vertex) has no longest paths, the list is empty.
+pub fn longest_paths(g: &CircGraph) -> Option<Vec<Vec<String>>> {
+    return crate::word_graph::longest_paths_of_edges(&g.get_vertices(), &crate::graph::edge_pairs(g));
+}
+
+/// Returns all cycles of a graph as vertex lists, see [guarded]
+pub fn cycles(g: &CircGraph) -> Result<Option<Vec<Vec<String>>>, GcatError> {
+    return guarded(|| g.all_cycles_as_vertex_vec());
+}
+
+/// Returns the sub graph of all longest paths, see [guarded]
+pub fn longest_paths_sub_graph(g: &CircGraph) -> Result<CircGraph, GcatError> {
+    return guarded(|| g.all_longest_paths_as_sub_graph()).and_then(|s_g| s_g.map_err(GcatError::Invalid));
+}
+
+/// Returns the sub graph of all cycles, see [guarded]
+pub fn cycles_sub_graph(g: &CircGraph) -> Result<CircGraph, GcatError> {
+    return guarded(|| g.all_cycles_as_sub_graph()).and_then(|s_g| s_g.map_err(GcatError::Invalid));
+}
+
+/// Returns the i-component of a graph, see [guarded]
+pub fn component(g: &CircGraph, i: u32) -> Result<CircGraph, GcatError> {
+    return guarded(|| g.component(i)).and_then(|c| c.map_err(GcatError::Invalid));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guarded_returns_the_panic_message() {
+        assert_eq!(guarded(|| 1), Ok(1));
+        assert_eq!(guarded(|| -> i32 { panic!("degenerate input") }), Err(GcatError::Panic("degenerate input".to_string())));
+        assert_eq!(guarded(|| -> i32 { panic!("{} words", 2) }), Err(GcatError::Panic("2 words".to_string())));
+    }
+
+    #[test]
+    fn new_code_rejects_empty_words() {
+        assert_eq!(new_code(vec!["ACG".to_string(), "".to_string()]).err(),
+                   Some(GcatError::Invalid("word 2 is empty".to_string())));
+    }
+}
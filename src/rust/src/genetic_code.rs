@@ -0,0 +1,189 @@
+use extendr_api::prelude::*;
+
+use crate::guard::guard;
+use crate::lib_utils::new_code_from_vec;
+use crate::transform::words_to_dna;
+
+/// The nucleotides in the order used by the NCBI codon tables
+const NCBI_BASES: [char; 4] = ['T', 'C', 'A', 'G'];
+
+/// All 20 proteinogenic amino acids (one letter code)
+pub(crate) const AMINO_ACIDS: &str = "ACDEFGHIKLMNPQRSTVWY";
+
+/// Returns the amino acids of a NCBI translation table
+///
+/// The table is encoded as in the NCBI genetic code definitions: the i-th letter is the amino
+/// acid of the i-th codon if codons are ordered by T, C, A, G (first, second and third position).
+/// A `*` denotes a stop codon.
+///
+/// # Arguments
+/// * `id` the NCBI translation table id
+fn ncbi_table(id: u32) -> Option<&'static str> {
+    match id {
+        1 | 11 => Some("FFLLSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG"),
+        2 => Some("FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSS**VVVVAAAADDEEGGGG"),
+        3 => Some("FFLLSSSSYY**CCWWTTTTPPPPHHQQRRRRIIMMTTTTNNKKSSRRVVVVAAAADDEEGGGG"),
+        4 => Some("FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG"),
+        5 => Some("FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSSSSVVVVAAAADDEEGGGG"),
+        6 => Some("FFLLSSSSYYQQCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG"),
+        9 => Some("FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNNKSSSSVVVVAAAADDEEGGGG"),
+        10 => Some("FFLLSSSSYY**CCCWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG"),
+        12 => Some("FFLLSSSSYY**CC*WLLLSPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG"),
+        _ => None,
+    }
+}
+
+/// A codon table mapping each of the 64 codons to an amino acid (or `*` for stop)
+#[derive(Debug, Clone)]
+pub(crate) struct GeneticCode {
+    amino_acids: Vec<char>,
+}
+
+impl GeneticCode {
+    /// Returns the standard genetic code (NCBI table 1)
+    pub(crate) fn standard() -> GeneticCode {
+        return GeneticCode { amino_acids: ncbi_table(1).unwrap_or_default().chars().collect() };
+    }
+
+    /// Returns the genetic code with the given NCBI translation table id
+    ///
+    /// Supported are the tables 1, 2, 3, 4, 5, 6, 9, 10, 11 and 12.
+    pub(crate) fn ncbi(id: u32) -> Result<GeneticCode, String> {
+        match ncbi_table(id) {
+            Some(table) => Ok(GeneticCode { amino_acids: table.chars().collect() }),
+            None => Err(format!("Unknown NCBI translation table {}", id)),
+        }
+    }
+
+    /// Returns the amino acid of a codon (`*` for stop codons)
+    ///
+    /// The codon can be written in DNA or RNA, lower or upper case.
+    /// Returns `None` if the word is not a codon.
+    pub(crate) fn translate(&self, codon: &str) -> Option<char> {
+        let chars = codon.chars().collect::<Vec<char>>();
+        if chars.len() != 3 {
+            return None;
+        }
+
+        let mut idx = 0;
+        for c in chars {
+            let c = match c.to_ascii_uppercase() {
+                'U' => 'T',
+                c => c,
+            };
+            idx = idx * 4 + NCBI_BASES.iter().position(|b| *b == c)?;
+        }
+
+        return Some(self.amino_acids[idx]);
+    }
+}
+
+/// The amino acids encoded by a code
+#[derive(Debug, Clone)]
+pub(crate) struct AminoAcidCoverage {
+    /// All encoded amino acids (sorted, without stop)
+    pub(crate) covered: Vec<char>,
+    /// All amino acids which are not encoded (sorted)
+    pub(crate) missing: Vec<char>,
+    /// All words which are stop codons
+    pub(crate) stop_codons: Vec<String>,
+}
+
+impl AminoAcidCoverage {
+    /// True if all 20 amino acids are encoded
+    pub(crate) fn is_complete(&self) -> bool {
+        return self.missing.is_empty();
+    }
+}
+
+/// Returns the amino acids encoded by the codons of a code
+///
+/// Words which are not codons (length != 3) are ignored.
+///
+/// # Arguments
+/// * `words` a set of words
+/// * `genetic_code` the codon table
+pub(crate) fn coverage_of_words(words: &[String], genetic_code: &GeneticCode) -> AminoAcidCoverage {
+    let mut covered = Vec::new();
+    let mut stop_codons = Vec::new();
+    for word in words {
+        match genetic_code.translate(word) {
+            Some('*') => stop_codons.push(word.clone()),
+            Some(aa) => covered.push(aa),
+            None => {}
+        }
+    }
+
+    covered.sort_unstable();
+    covered.dedup();
+    let missing = AMINO_ACIDS.chars().filter(|aa| !covered.contains(aa)).collect();
+    return AminoAcidCoverage { covered, missing, stop_codons };
+}
+
+/// Returns the amino acids encoded by a code
+///
+/// This function translates all codons (words of length 3) of the code
+/// with a genetic code and reports which amino acids are encoded.
+/// A code is complete if it encodes all 20 amino acids. The genetic code
+/// is selected by its NCBI translation table id (1 is the standard code).
+/// Supported are the tables 1, 2, 3, 4, 5, 6, 9, 10, 11 and 12.
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param table_id An integer, the NCBI translation table id
+///
+/// @return A list with the encoded amino acids (covered), the not encoded amino acids (missing),
+/// the stop codons in the code (stop_codons) and a boolean (complete), true if all 20 amino acids are encoded.
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGG", "AAT"))
+/// amino_acid_coverage(code, 1)
+///
+/// @export
+#[extendr]
+fn amino_acid_coverage(tuples: Vec<String>, table_id: i32) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(words_to_dna(&tuples));
+        let genetic_code = match GeneticCode::ncbi(table_id.max(0) as u32) {
+            Ok(g) => g,
+            Err(e) => {
+                r_error!("Genetic code is not correct: {}", e);
+            }
+        };
+
+        let coverage = coverage_of_words(&code.get_code(), &genetic_code);
+        return list!(covered = coverage.covered.iter().map(|c| c.to_string()).collect::<Vec<String>>(),
+        missing = coverage.missing.iter().map(|c| c.to_string()).collect::<Vec<String>>(),
+        stop_codons = coverage.stop_codons.clone(),
+        complete = coverage.is_complete());
+    });
+}
+
+/// Translates codons into amino acids
+///
+/// Each word of length 3 is translated with the standard genetic code (NCBI table 1).
+/// Stop codons are translated into "*" and words which are not codons into "-".
+///
+/// @param tuples A String vector of codons
+///
+/// @return A String vector with the one letter code of the amino acids.
+///
+/// @examples
+/// translate_codons(c("ATG", "TAA", "GGC"))
+///
+/// @export
+#[extendr]
+fn translate_codons(tuples: Vec<String>) -> Vec<String> {
+    return guard(|| {
+        let genetic_code = GeneticCode::standard();
+        return tuples.iter().map(|t| genetic_code.translate(t).unwrap_or('-').to_string()).collect();
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod genetic_code;
+    fn amino_acid_coverage;
+    fn translate_codons;
+}
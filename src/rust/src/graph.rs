@@ -1,7 +1,11 @@
 use extendr_api::prelude::*;
-use rust_gcatcirc_lib::graph_circ::CircGraph;
 
-use crate::lib_utils::new_code_from_vec;
+use crate::gcat::{associated_graph, component, cycles, cycles_sub_graph, longest_paths, longest_paths_sub_graph, CircGraph};
+use crate::guard::guard;
+use crate::lib_utils::{component_index_from_r, new_code_from_vec};
+use crate::limits::{Budget, PathOptions, SearchLimits};
+use crate::transform::shift_words;
+use crate::word_graph::{cycles_per_word, cycles_summary_limited, cycles_with_prefix_limited, dedup_paths, edge_difference, edges_by_split, Fingerprint, GraphFingerprint, longest_paths_limited, longest_paths_summary, represented_path_word, simple_cycles_bounded, simple_cycles_limited};
 
 
 /// Returns the graph associated to a code
@@ -10,7 +14,9 @@ use crate::lib_utils::new_code_from_vec;
 /// @param show_cycles a boolean, if true all edges in all cyclic paths a red
 /// @param show_longest_path a boolean, if true all edges in all longest paths a red
 ///
-/// @return a rust graph-object associated to a code
+/// @return a rust graph-object associated to a code. Besides the edges the object contains for each vertex
+/// the comma separated indices of its i-components (vertex_components). If the cycles or the longest paths are shown,
+/// it also contains whether the vertex lies on a cycle (vertex_on_cycle) or on a longest path (vertex_on_longest_path).
 ///
 /// @examples
 /// code <- gcatbase::code(c("ACG", "CGG", "AC"))
@@ -18,17 +24,18 @@ use crate::lib_utils::new_code_from_vec;
 ///
 #[extendr]
 pub fn get_representing_graph_obj(tuples: Vec<String>, show_cycles: bool, show_longest_path: bool) -> Robj {
-    let code = new_code_from_vec(tuples);
-    let g = match code.get_associated_graph() {
-        Ok(graph) => graph,
-        Err(e) => {
-            rprintln!("Graph is corrupted: {}", e);
-            R!(stop("Graph is corrupted")).unwrap();
-            return list!()
-        }
-    };
+    return guard(|| {
+        trace_span!("get_representing_graph_obj", words = tuples.len());
+        let code = new_code_from_vec(tuples);
+        let g = match associated_graph(&code) {
+            Ok(graph) => graph,
+            Err(e) => {
+                r_error!("Graph is corrupted: {}", e);
+            }
+        };
 
-    return representing_graph_obj_factory(g,show_cycles,show_longest_path);
+        return representing_graph_obj_factory(g,show_cycles,show_longest_path);
+    });
 }
 
 
@@ -47,34 +54,34 @@ pub fn get_representing_graph_obj(tuples: Vec<String>, show_cycles: bool, show_l
 ///
 #[extendr]
 pub fn get_representing_component_obj(tuples: Vec<String>, i: i32, show_cycles: bool, show_longest_path: bool) -> Robj {
-    let code = new_code_from_vec(tuples);
-    let g = match code.get_associated_graph() {
-        Ok(graph) =>  graph,
-        Err(e) => {
-            rprintln!("Graph is corrupted: {}", e);
-            R!(stop("Graph is corrupted")).unwrap();
-            return list!()
-        }
-    };
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let g = match associated_graph(&code) {
+            Ok(graph) =>  graph,
+            Err(e) => {
+                r_error!("Graph is corrupted: {}", e);
+            }
+        };
 
-    match g.component(i as u32) {
-        Ok(graph) =>  return representing_graph_obj_factory(graph,show_cycles,show_longest_path),
-        Err(e) => {
-            rprintln!("Graph is corrupted: {}", e);
-            R!(stop("Graph is corrupted")).unwrap();
-            return list!()
+        match component(&g, component_index_from_r(i)) {
+            Ok(graph) =>  return representing_graph_obj_factory(graph,show_cycles,show_longest_path),
+            Err(e) => {
+                r_error!("Graph is corrupted: {}", e);
+            }
         }
-    }
+    });
 }
 
 /// Returns a list of all longest paths
 ///
 /// This function returns all longest paths
 /// in the graph associated to a set of words \emph{X}.
+/// Each path is reported once, see \link{get_longest_path_length} for the length only.
 ///
 /// @param tuples A gcatbase::gcat.code object
 ///
-/// @return A list of String vectors with all longest paths.
+/// @return A list of String vectors with all longest paths. The list is empty if the graph
+/// contains a cycle or has no edges (e.g. a single vertex).
 ///
 /// @seealso \link{get_representing_graph}
 ///
@@ -85,28 +92,79 @@ pub fn get_representing_component_obj(tuples: Vec<String>, i: i32, show_cycles:
 /// @export
 #[extendr]
 pub fn get_longest_paths(tuples: Vec<String>) -> Vec<Robj> {
-    let code = new_code_from_vec(tuples);
-    let g = match code.get_associated_graph() {
-        Ok(graph) =>  graph,
-        Err(e) => {
-            rprintln!("Graph is corrupted: {}", e);
-            R!(stop("Graph is corrupted")).unwrap();
-            return vec![]
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let g = match associated_graph(&code) {
+            Ok(graph) =>  graph,
+            Err(e) => {
+                r_error!("Graph is corrupted: {}", e);
+            }
+        };
+
+
+        if let Some(l_paths) = longest_paths(&g) {
+            return dedup_paths(&l_paths).iter().map(|x|  x.iter().collect_robj()).collect::<Vec<Robj>>()
         }
-    };
 
+        return vec![]
+    });
+}
 
-    if let Some(l_paths) = g.all_longest_paths_as_vertex_vec() {
-        return l_paths.iter().map(|x|  x.iter().collect_robj()).collect::<Vec<Robj>>()
-    }
+/// Returns all longest paths of the i-component of a graph
+///
+/// Returns `None` if the component can not be built or is cyclic, no paths if it has no edges.
+///
+/// # Arguments
+/// * `g` the graph associated to a code
+/// * `i` the component index
+pub(crate) fn longest_paths_in_component(g: &CircGraph, i: u32) -> Option<Vec<Vec<String>>> {
+    let c = component(g, i).ok()?;
+    return longest_paths(&c);
+}
 
-    return vec![]
+/// Returns a list of all longest paths of an i-component
+///
+/// This function returns all longest paths of the i-component
+/// of the graph associated to a set of words \emph{X}, see \link{get_component_of_representing_graph}.
+/// This restricts analyses to a single component without building the component graph.
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param i An integer, the component index
+///
+/// @return A list of String vectors with all longest paths of the component.
+///
+/// @seealso \link{get_longest_paths}
+///
+/// @examples
+/// code <- gcatbase::code(c("ACGC", "CGGG", "AC"))
+/// lp <- get_longest_paths_in_component(code, 1)
+///
+/// @export
+#[extendr]
+pub fn get_longest_paths_in_component(tuples: Vec<String>, i: i32) -> Vec<Robj> {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let g = match associated_graph(&code) {
+            Ok(graph) =>  graph,
+            Err(e) => {
+                r_error!("Graph is corrupted: {}", e);
+            }
+        };
+
+        if let Some(l_paths) = longest_paths_in_component(&g, component_index_from_r(i)) {
+            return dedup_paths(&l_paths).iter().map(|x|  x.iter().collect_robj()).collect::<Vec<Robj>>()
+        }
+
+        return vec![]
+    });
 }
 
 /// Returns a list of all cyclic paths
 ///
 /// This function returns all cyclic paths
 /// in the graph associated to a set of words \emph{X}.
+/// Each cycle is reported exactly once in its canonical representation,
+/// the lexicographically minimal rotation of its vertices.
 ///
 /// @param tuples A gcatbase::gcat.code object
 ///
@@ -121,58 +179,520 @@ pub fn get_longest_paths(tuples: Vec<String>) -> Vec<Robj> {
 /// @export
 #[extendr]
 pub fn get_cyclic_paths(tuples: Vec<String>) -> Vec<Robj> {
-    let code = new_code_from_vec(tuples);
-    let g = match code.get_associated_graph() {
-        Ok(graph) =>  graph,
-        Err(e) => {
-            rprintln!("Graph is corrupted: {}", e);
-            R!(stop("Graph is corrupted")).unwrap();
-            return vec![]
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let mut cycles = simple_cycles_limited(&code.get_code(), &mut Budget::new(SearchLimits::default()));
+        cycles.sort();
+        return cycles.iter().map(|x| x.iter().collect_robj()).collect::<Vec<Robj>>();
+    });
+}
+
+/// Returns the edges of the graph associated to a code produced by the split position i
+///
+/// Each word \emph{N1...Nn} of a code induces the edges \emph{[N1...Ni, Ni+1...Nn]} for \emph{0 < i < n}.
+/// This function returns the edges of a single split position \emph{i}, i.e. the component C_i in the sense of
+/// Fimmel, Michel and Strüngmann. Unlike \link{get_representing_component_obj}, which filters by the length of
+/// the vertices, the edges of words of different lengths are grouped by the position which produced them.
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param i An integer, the split position (i > 0)
+///
+/// @return A list with the vertices (vertices), the edges as consecutive pairs of vertices (edges)
+/// and the word of each edge (words).
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGG", "AC"))
+/// get_edges_by_split(code, 1)
+///
+/// @seealso \link{get_representing_graph}
+///
+/// @export
+#[extendr]
+pub fn get_edges_by_split(tuples: Vec<String>, i: i32) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let edges = edges_by_split(&code.get_code(), i.max(0) as usize);
+        let mut vertices = edges.iter().flat_map(|e| [e.from.clone(), e.to.clone()]).collect::<Vec<String>>();
+        vertices.sort();
+        vertices.dedup();
+
+        return list!(vertices = vertices,
+        edges = edges.iter().flat_map(|e| [e.from.clone(), e.to.clone()]).collect::<Vec<String>>(),
+        words = edges.iter().map(|e| e.word.clone()).collect::<Vec<String>>());
+    });
+}
+
+/// Returns the edges gained and lost by a circular shift of a code
+///
+/// Compares the graph associated to a code \emph{X} with the graph associated to
+/// the shifted code \emph{α_sh(X)} (see \link{circular_shift}), i.e. how the
+/// circular permutation of the words alters the structure of the graph.
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param sh A integer, the shift index, i.e. the number of shifts, applied like in \link{circular_shift}.
+///
+/// @return A list with the shifted code (shifted), the edges of the shifted graph which are not edges of the
+/// original graph (gained) and the edges of the original graph which are not edges of the shifted graph (lost).
+/// The edges are given as consecutive pairs of vertices.
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGG", "AC"))
+/// diff_graphs(code, 1)
+///
+/// @seealso \link{circular_shift}, \link{get_representing_graph}
+///
+/// @export
+#[extendr]
+pub fn diff_graphs(tuples: Vec<String>, sh: i32) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples).get_code();
+        let shifted = shift_words(&code, sh);
+        let diff = edge_difference(&code, &shifted);
+
+        return list!(shifted = shifted,
+        gained = diff.gained.iter().flat_map(|e| [e.0.clone(), e.1.clone()]).collect::<Vec<String>>(),
+        lost = diff.lost.iter().flat_map(|e| [e.0.clone(), e.1.clone()]).collect::<Vec<String>>());
+    });
+}
+
+/// Returns the number of cycles each word participates in
+///
+/// A word participates in a cycle of the graph associated to a code if one of
+/// its edges is part of the cycle. A code is circular iff its graph has no cycles,
+/// so the words with the most cycles violate circularity the most.
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param max_millis A numeric value, the maximal run time in milliseconds (non-positive: unlimited)
+/// @param max_cycles A numeric value, the maximal number of enumerated cycles (non-positive: unlimited)
+///
+/// @return A list with the words (word), their number of cycles (cycles), sorted by the number of cycles (decreasing),
+/// and a boolean (truncated) which is true if a limit stopped the cycle search.
+///
+/// @seealso \link{words_on_cycles}
+///
+#[extendr]
+pub fn words_on_cycles_obj(tuples: Vec<String>, max_millis: f64, max_cycles: f64) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let mut budget = Budget::new(SearchLimits::from_r(max_millis, max_cycles, 0.0));
+        let counts = cycles_per_word(&code.get_code(), &mut budget);
+
+        return list!(word = counts.iter().map(|c| c.0.clone()).collect::<Vec<String>>(),
+        cycles = counts.iter().map(|c| c.1 as i32).collect::<Vec<i32>>(),
+        truncated = budget.is_truncated());
+    });
+}
+
+/// Returns the sequence represented by a path
+///
+/// A path \emph{[v1, ..., vk]} in the graph associated to a code represents the sequence
+/// \emph{v1...vk}. Each edge \emph{[vi, vi+1]} represents the word \emph{vivi+1}, so the sequence
+/// can be read with the words of the odd edges and with the words of the even edges.
+/// A cycle (closing edge \emph{[vk, v1]}) represents a circular sequence with two different
+/// decompositions, which certifies that the code is not circular.
+///
+/// @param path A String vector, the vertices of the path, e.g. an entry of \link{get_cyclic_paths} or \link{get_longest_paths}
+/// @param cycle A boolean, true if the path is a cycle
+///
+/// @return A list with the represented sequence (word), the words of all edges (edge_words)
+/// and the two decompositions (first_decomposition, second_decomposition).
+///
+/// @examples
+/// path_word(c("A", "CG"), TRUE)
+///
+/// @export
+#[extendr]
+pub fn path_word(path: Vec<String>, cycle: bool) -> Robj {
+    return guard(|| {
+        let pw = represented_path_word(&path, cycle);
+        return list!(word = pw.word,
+        edge_words = pw.edge_words,
+        first_decomposition = pw.first_decomposition,
+        second_decomposition = pw.second_decomposition);
+    });
+}
+
+/// Returns the cyclic paths found within limits
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param max_millis A numeric value, the maximal run time in milliseconds (non-positive: unlimited)
+/// @param max_paths A numeric value, the maximal number of paths (non-positive: unlimited)
+/// @param max_nodes A numeric value, the maximal number of visited search nodes (non-positive: unlimited)
+/// @param max_megabytes A numeric value, the maximal memory of the paths in megabytes (non-positive: unlimited)
+///
+/// @return A list with the paths (paths), a boolean (truncated) which is true if a limit stopped the search and
+/// the number of visited search nodes (nodes).
+///
+/// @seealso \link{get_cyclic_paths_limited}
+///
+#[extendr]
+pub fn get_cyclic_paths_limited_obj(tuples: Vec<String>, max_millis: f64, max_paths: f64, max_nodes: f64, max_megabytes: f64) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let mut budget = Budget::new(SearchLimits::from_r(max_millis, max_paths, max_nodes).with_max_megabytes(max_megabytes));
+        let cycles = simple_cycles_limited(&code.get_code(), &mut budget);
+        if let Err(e) = budget.check() {
+            r_error!("Result is too large: cyclic paths: {}", e);
         }
-    };
 
-    if let Some(l_paths) = g.all_cycles_as_vertex_vec() {
-        return l_paths.iter().map(|x|  x.iter().collect_robj()).collect::<Vec<Robj>>()
+        return list!(paths = cycles.iter().map(|x| x.iter().collect_robj()).collect::<Vec<Robj>>(),
+        truncated = budget.is_truncated(),
+        nodes = budget.nodes() as f64);
+    });
+}
+
+/// Returns the longest paths found within limits
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param max_millis A numeric value, the maximal run time in milliseconds (non-positive: unlimited)
+/// @param max_paths A numeric value, the maximal number of paths (non-positive: unlimited)
+/// @param max_nodes A numeric value, the maximal number of visited search nodes (non-positive: unlimited)
+///
+/// @return A list with the paths (paths), a boolean (truncated) which is true if a limit stopped the search and
+/// the number of visited search nodes (nodes).
+///
+/// @seealso \link{get_longest_paths_limited}
+///
+#[extendr]
+pub fn get_longest_paths_limited_obj(tuples: Vec<String>, max_millis: f64, max_paths: f64, max_nodes: f64) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let mut budget = Budget::new(SearchLimits::from_r(max_millis, max_paths, max_nodes));
+        let paths = longest_paths_limited(&code.get_code(), &mut budget).unwrap_or_default();
+
+        return list!(paths = paths.iter().map(|x| x.iter().collect_robj()).collect::<Vec<Robj>>(),
+        truncated = budget.is_truncated(),
+        nodes = budget.nodes() as f64);
+    });
+}
+
+/// Returns the cyclic paths within bounds found within limits
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param max_depth An integer, the maximal number of vertices of a path (non-positive: unbounded)
+/// @param max_branches An integer, the maximal number of followed successors of a vertex (non-positive: unbounded)
+/// @param stop_above_length An integer, the search stops at the first cycle with more vertices (non-positive: never)
+/// @param max_millis A numeric value, the maximal run time in milliseconds (non-positive: unlimited)
+/// @param max_paths A numeric value, the maximal number of paths (non-positive: unlimited)
+/// @param max_nodes A numeric value, the maximal number of visited search nodes (non-positive: unlimited)
+///
+/// @return A list with the paths (paths), a boolean (exceeded) which is true if the search stopped at a cycle longer
+/// than stop_above_length, a boolean (truncated) which is true if a limit stopped the search or successors were skipped
+/// and the number of visited search nodes (nodes).
+///
+/// @seealso \link{get_cyclic_paths_bounded}
+///
+#[extendr]
+pub fn get_cyclic_paths_bounded_obj(tuples: Vec<String>, max_depth: i32, max_branches: i32, stop_above_length: i32,
+                                    max_millis: f64, max_paths: f64, max_nodes: f64) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let options = PathOptions::from_r(max_depth, max_branches, stop_above_length);
+        let mut budget = Budget::new(SearchLimits::from_r(max_millis, max_paths, max_nodes));
+        let (cycles, exceeded) = simple_cycles_bounded(&code.get_code(), &options, &mut budget);
+
+        return list!(paths = cycles.iter().map(|x| x.iter().collect_robj()).collect::<Vec<Robj>>(),
+        exceeded = exceeded,
+        truncated = budget.is_truncated(),
+        nodes = budget.nodes() as f64);
+    });
+}
+
+/// Returns the number of cyclic paths and their lengths found within limits
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param max_millis A numeric value, the maximal run time in milliseconds (non-positive: unlimited)
+/// @param max_paths A numeric value, the maximal number of paths (non-positive: unlimited)
+/// @param max_nodes A numeric value, the maximal number of visited search nodes (non-positive: unlimited)
+///
+/// @return A list with the number of paths (count), the lengths (length) and the number of paths of each length (paths),
+/// a boolean (truncated) which is true if a limit stopped the search and the number of visited search nodes (nodes).
+///
+/// @seealso \link{get_cyclic_paths_summary}
+///
+#[extendr]
+pub fn get_cyclic_paths_summary_obj(tuples: Vec<String>, max_millis: f64, max_paths: f64, max_nodes: f64) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let mut budget = Budget::new(SearchLimits::from_r(max_millis, max_paths, max_nodes));
+        let summary = cycles_summary_limited(&code.get_code(), &mut budget);
+
+        return list!(count = summary.count as f64,
+        length = summary.lengths.keys().map(|l| *l as i32).collect::<Vec<i32>>(),
+        paths = summary.lengths.values().map(|c| *c as f64).collect::<Vec<f64>>(),
+        truncated = budget.is_truncated(),
+        nodes = budget.nodes() as f64);
+    });
+}
+
+/// Returns the number of longest paths and their length
+///
+/// This function counts the longest paths of the graph associated to a code
+/// without enumerating them (see \link{get_longest_paths}), so the memory does not
+/// grow with the number of paths. Suited for batch analyses of many codes.
+///
+/// @param tuples A gcatbase::gcat.code object
+///
+/// @return A list with the number of longest paths (count) and their number of edges (length).
+/// The count is 0 and the length -1 if the graph contains a cycle or has no edges.
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGG", "AC"))
+/// get_longest_paths_summary(code)
+///
+/// @seealso \link{get_longest_paths}, \link{get_cyclic_paths_summary}
+///
+/// @export
+#[extendr]
+pub fn get_longest_paths_summary(tuples: Vec<String>) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        return match longest_paths_summary(&code.get_code()) {
+            Some(summary) => list!(count = summary.count as f64,
+                                   length = summary.lengths.keys().next().map_or(-1, |l| *l as i32)),
+            None => list!(count = 0.0, length = -1),
+        };
+    });
+}
+
+/// Returns the cyclic paths through a vertex found within limits
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param vertex A String, the vertex
+/// @param max_millis A numeric value, the maximal run time in milliseconds (non-positive: unlimited)
+/// @param max_paths A numeric value, the maximal number of paths (non-positive: unlimited)
+/// @param max_nodes A numeric value, the maximal number of visited search nodes (non-positive: unlimited)
+///
+/// @return A list with the paths (paths), a boolean (truncated) which is true if a limit stopped the search and
+/// the number of visited search nodes (nodes).
+///
+/// @seealso \link{get_cycles_through_vertex}
+///
+#[extendr]
+pub fn get_cycles_through_vertex_obj(tuples: Vec<String>, vertex: String, max_millis: f64, max_paths: f64, max_nodes: f64) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let mut budget = Budget::new(SearchLimits::from_r(max_millis, max_paths, max_nodes));
+        let cycles = cycles_with_prefix_limited(&code.get_code(), &[vertex], &mut budget);
+
+        return list!(paths = cycles.iter().map(|x| x.iter().collect_robj()).collect::<Vec<Robj>>(),
+        truncated = budget.is_truncated(),
+        nodes = budget.nodes() as f64);
+    });
+}
+
+/// Returns the cyclic paths through an edge found within limits
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param from A String, the start vertex of the edge
+/// @param to A String, the end vertex of the edge
+/// @param max_millis A numeric value, the maximal run time in milliseconds (non-positive: unlimited)
+/// @param max_paths A numeric value, the maximal number of paths (non-positive: unlimited)
+/// @param max_nodes A numeric value, the maximal number of visited search nodes (non-positive: unlimited)
+///
+/// @return A list with the paths (paths), a boolean (truncated) which is true if a limit stopped the search and
+/// the number of visited search nodes (nodes).
+///
+/// @seealso \link{get_cycles_through_edge}
+///
+#[extendr]
+pub fn get_cycles_through_edge_obj(tuples: Vec<String>, from: String, to: String, max_millis: f64, max_paths: f64, max_nodes: f64) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let mut budget = Budget::new(SearchLimits::from_r(max_millis, max_paths, max_nodes));
+        let cycles = cycles_with_prefix_limited(&code.get_code(), &[from, to], &mut budget);
+
+        return list!(paths = cycles.iter().map(|x| x.iter().collect_robj()).collect::<Vec<Robj>>(),
+        truncated = budget.is_truncated(),
+        nodes = budget.nodes() as f64);
+    });
+}
+
+/// The analysis of one i-component of a graph
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ComponentReport {
+    pub(crate) index: usize,
+    pub(crate) vertices: usize,
+    pub(crate) edges: usize,
+    pub(crate) cyclic: bool,
+    /// The number of edges of a longest path, `None` if the component is cyclic or has no edges
+    pub(crate) longest_path: Option<usize>,
+}
+
+/// Returns the analysis of the i-components `1..n` of a graph
+///
+/// Components which can not be built are skipped.
+///
+/// # Arguments
+/// * `g` the graph associated to a code
+/// * `n` the length of the longest word of the code
+pub(crate) fn component_reports(g: &CircGraph, n: usize) -> Vec<ComponentReport> {
+    return (1..n).filter_map(|i| {
+        let c = component(g, i as u32).ok()?;
+        let cyclic = cycles(&c).ok()?.is_some_and(|cycles| !cycles.is_empty());
+        let longest_path = match cyclic {
+            true => None,
+            false => longest_paths(&c)
+                .and_then(|paths| paths.iter().map(|p| p.len()).max())
+                .filter(|l| *l > 1)
+                .map(|l| l - 1),
+        };
+
+        Some(ComponentReport {
+            index: i,
+            vertices: c.get_vertices().len(),
+            edges: c.get_edges().len(),
+            cyclic,
+            longest_path,
+        })
+    }).collect();
+}
+
+/// Returns an analysis of all i-components of the graph associated to a code
+///
+/// For each i-component (see \link{get_component_of_representing_graph}) this function reports
+/// the number of vertices and edges, whether the component contains a cycle and the length
+/// (number of edges) of its longest path. Per-component circularity is a standard table in the literature.
+///
+/// @param tuples A gcatbase::gcat.code object
+///
+/// @return A list with the component indices (component), the numbers of vertices (vertices) and edges (edges),
+/// a boolean vector (cyclic) and the lengths of the longest paths (longest_path, -1 for cyclic or empty components).
+///
+/// @examples
+/// code <- gcatbase::code(c("ACGC", "CGGG", "AC"))
+/// get_component_reports(code)
+///
+/// @seealso \link{get_component_of_representing_graph}
+///
+/// @export
+#[extendr]
+pub fn get_component_reports(tuples: Vec<String>) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let n = code.get_code().iter().map(|w| w.chars().count()).max().unwrap_or(0);
+        let reports = match associated_graph(&code) {
+            Ok(graph) => component_reports(&graph, n),
+            Err(e) => {
+                r_error!("Graph is corrupted: {}", e);
+            }
+        };
+
+        return list!(component = reports.iter().map(|r| r.index as i32).collect::<Vec<i32>>(),
+        vertices = reports.iter().map(|r| r.vertices as i32).collect::<Vec<i32>>(),
+        edges = reports.iter().map(|r| r.edges as i32).collect::<Vec<i32>>(),
+        cyclic = reports.iter().map(|r| r.cyclic).collect::<Vec<bool>>(),
+        longest_path = reports.iter().map(|r| r.longest_path.map_or(-1, |l| l as i32)).collect::<Vec<i32>>());
+    });
+}
+
+/// Returns the edges of a graph as pairs `(from, to)`
+///
+/// # Arguments
+/// * `g` a graph
+pub(crate) fn edge_pairs(g: &CircGraph) -> Vec<(String, String)> {
+    return g.get_edges().into_iter().flatten().collect::<Vec<String>>()
+        .chunks(2)
+        .map(|e| (e[0].clone(), e[1].clone()))
+        .collect();
+}
+
+impl Fingerprint for CircGraph {
+    fn fingerprint(&self) -> GraphFingerprint {
+        return GraphFingerprint::new(&self.get_vertices(), &edge_pairs(self));
     }
+}
+
+/// Returns the fingerprint of the graph associated to a code
+///
+/// The fingerprint is a cheap invariant of the graph consisting of the number of vertices
+/// and edges, the sorted sequence of in- and out-degrees and the cyclomatic number.
+/// Graphs with different fingerprints are different (also up to renaming of the vertices),
+/// so fingerprints allow a fast inequality check, e.g. to remove duplicates when enumerating codes.
+/// Equal fingerprints do not imply equal graphs.
+///
+/// @param tuples A gcatbase::gcat.code object
+///
+/// @return A list with the number of vertices (vertices), the number of edges (edges),
+/// the in-degrees (in_degrees) and out-degrees (out_degrees) sorted by (in, out),
+/// the cyclomatic number (cycle_bound) and a hash of all values as hex string (hash).
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGA", "CA"))
+/// graph_fingerprint(code)
+///
+/// @export
+#[extendr]
+pub fn graph_fingerprint(tuples: Vec<String>) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let fp = match associated_graph(&code) {
+            Ok(graph) => graph.fingerprint(),
+            Err(e) => {
+                r_error!("Graph is corrupted: {}", e);
+            }
+        };
 
-    return vec![]
+        return list!(vertices = fp.vertices as i32,
+        edges = fp.edges as i32,
+        in_degrees = fp.degrees.iter().map(|d| d.0 as i32).collect::<Vec<i32>>(),
+        out_degrees = fp.degrees.iter().map(|d| d.1 as i32).collect::<Vec<i32>>(),
+        cycle_bound = fp.cycle_bound as i32,
+        hash = format!("{:016x}", fp.hash_value()));
+    });
+}
+
+/// Returns the indices of the i-components which contain each vertex of a graph (in the order of `get_vertices`)
+///
+/// # Arguments
+/// * `g` the graph associated to a code
+pub(crate) fn vertex_components(g: &CircGraph) -> Vec<Vec<usize>> {
+    let vertices = g.get_vertices();
+    let n = vertices.iter().map(|v| v.chars().count()).max().unwrap_or(0);
+    let component_vertices = (1..=n)
+        .map(|i| component(g, i as u32).map(|c| c.get_vertices()).unwrap_or_default())
+        .collect::<Vec<Vec<String>>>();
+
+    return vertices.iter().map(|v| (1..=n).filter(|i| component_vertices[i - 1].contains(v)).collect()).collect();
+}
+
+/// Returns for each vertex of a graph (in the order of `get_vertices`) if it is a vertex of a sub graph
+///
+/// # Arguments
+/// * `g` the graph associated to a code
+/// * `sub_graph` a sub graph of `g`, e.g. the cycles
+fn vertex_in_sub_graph(g: &CircGraph, sub_graph: &CircGraph) -> Vec<bool> {
+    let sub_vertices = sub_graph.get_vertices();
+    return g.get_vertices().iter().map(|v| sub_vertices.contains(v)).collect();
 }
 
 fn representing_graph_obj_factory(g: CircGraph, show_cycles: bool, show_longest_path: bool) -> Robj {
     let edges = g.get_edges();
-    let cyclic_paths = match show_cycles {
-        true => {
-            if let Ok(s_g) = g.all_cycles_as_sub_graph() {
-                s_g.get_edges()
-            } else {
-                vec![]
-            }
-        }
-        false => vec![],
+    let cycles = match show_cycles {
+        true => cycles_sub_graph(&g).ok(),
+        false => None,
     };
 
     let longest_paths = match show_longest_path {
-        true => {
-            if let Ok(s_g) = g.all_longest_paths_as_sub_graph() {
-                s_g.get_edges()
-            } else {
-                vec![]
-            }
-        }
-        false => vec![],
+        true => longest_paths_sub_graph(&g).ok(),
+        false => None,
     };
 
-    let edges = edges.into_iter().filter(|x| !longest_paths.contains(x) && !cyclic_paths.contains(x)).flatten().collect::<Vec<String>>();
-
+    let cyclic_path_edges = cycles.as_ref().map(|s_g| s_g.get_edges()).unwrap_or_default();
+    let longest_path_edges = longest_paths.as_ref().map(|s_g| s_g.get_edges()).unwrap_or_default();
+    let edges = edges.into_iter().filter(|x| !longest_path_edges.contains(x) && !cyclic_path_edges.contains(x)).flatten().collect::<Vec<String>>();
 
     return list!(vertices = g.get_vertices(),
     edges = edges,
-    circular_path_edges = cyclic_paths.into_iter().flatten().collect::<Vec<String>>(),
-    longest_path_edges = longest_paths.into_iter().flatten().collect::<Vec<String>>());
-
+    circular_path_edges = cyclic_path_edges.into_iter().flatten().collect::<Vec<String>>(),
+    longest_path_edges = longest_path_edges.into_iter().flatten().collect::<Vec<String>>(),
+    vertex_components = vertex_components(&g).iter()
+        .map(|c| c.iter().map(|i| i.to_string()).collect::<Vec<String>>().join(","))
+        .collect::<Vec<String>>(),
+    vertex_on_cycle = cycles.map_or(Robj::from(()), |s_g| Robj::from(vertex_in_sub_graph(&g, &s_g))),
+    vertex_on_longest_path = longest_paths.map_or(Robj::from(()), |s_g| Robj::from(vertex_in_sub_graph(&g, &s_g))));
 }
 
-
 // Macro to generate exports.
 // This ensures exported functions are registered with R.
 // See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
@@ -181,5 +701,19 @@ extendr_module! {
     fn get_representing_graph_obj;
     fn get_representing_component_obj;
     fn get_longest_paths;
+    fn get_longest_paths_in_component;
     fn get_cyclic_paths;
+    fn words_on_cycles_obj;
+    fn path_word;
+    fn get_cyclic_paths_limited_obj;
+    fn get_longest_paths_limited_obj;
+    fn get_cyclic_paths_bounded_obj;
+    fn get_cyclic_paths_summary_obj;
+    fn get_longest_paths_summary;
+    fn get_cycles_through_vertex_obj;
+    fn get_cycles_through_edge_obj;
+    fn graph_fingerprint;
+    fn get_edges_by_split;
+    fn diff_graphs;
+    fn get_component_reports;
 }
\ No newline at end of file
@@ -0,0 +1,633 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use extendr_api::prelude::*;
+
+use crate::gcat::{associated_graph, cycles_sub_graph, longest_paths_sub_graph, CircGraph};
+use crate::graph::edge_pairs;
+use crate::guard::guard;
+use crate::lib_utils::{new_code_from_vec, NamedList};
+use crate::overlap_graph::OverlapGraph;
+
+/// The placement of the vertices in a drawing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Layout {
+    /// The vertices are placed on a circle
+    Circular,
+    /// The vertices are placed in layers by their distance from the sources, edges point downwards
+    Layered,
+}
+
+impl Layout {
+    /// Parses a layout name (`"circular"` or `"layered"`)
+    pub(crate) fn from_name(name: &str) -> Result<Layout, String> {
+        match name.to_lowercase().as_str() {
+            "circular" => Ok(Layout::Circular),
+            "layered" => Ok(Layout::Layered),
+            _ => Err(format!("Unknown layout '{}', expected 'circular' or 'layered'", name)),
+        }
+    }
+}
+
+/// The shape of a vertex in a drawing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum VertexShape {
+    Circle,
+    Box,
+    Diamond,
+}
+
+impl VertexShape {
+    /// Parses a shape name (`"circle"`, `"box"` or `"diamond"`)
+    pub(crate) fn from_name(name: &str) -> Result<VertexShape, String> {
+        match name.to_lowercase().as_str() {
+            "circle" => Ok(VertexShape::Circle),
+            "box" => Ok(VertexShape::Box),
+            "diamond" => Ok(VertexShape::Diamond),
+            _ => Err(format!("Unknown vertex shape '{}', expected 'circle', 'box' or 'diamond'", name)),
+        }
+    }
+
+    /// Returns the name of the shape in the DOT format
+    fn dot_name(&self) -> &'static str {
+        match self {
+            VertexShape::Circle => "circle",
+            VertexShape::Box => "box",
+            VertexShape::Diamond => "diamond",
+        }
+    }
+
+    /// Returns the name of the shape in TikZ
+    fn tikz_name(&self) -> &'static str {
+        match self {
+            VertexShape::Circle => "circle",
+            VertexShape::Box => "rectangle",
+            VertexShape::Diamond => "diamond",
+        }
+    }
+
+    /// Returns the name of the shape in the GML graphics of Cytoscape and yEd
+    fn gml_name(&self) -> &'static str {
+        match self {
+            VertexShape::Circle => "ellipse",
+            VertexShape::Box => "rectangle",
+            VertexShape::Diamond => "diamond",
+        }
+    }
+}
+
+/// The appearance of a graph in an export
+///
+/// Colors are color names understood by SVG, graphviz and TikZ (e.g. `"red"`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct GraphStyle {
+    /// The color of edges which are neither highlighted nor colored by their component
+    pub(crate) edge_color: String,
+    /// The color of the edges of cycles
+    pub(crate) cycle_color: String,
+    /// The color of the edges of longest paths
+    pub(crate) longest_path_color: String,
+    /// The colors of the i-components, the edges of C_i get the color `i - 1` (cyclically).
+    /// If empty the edges are not colored by their component.
+    pub(crate) component_colors: Vec<String>,
+    /// The shapes of the vertices by label length, the i-th shape is used for labels of length `i + 1`
+    /// and the last shape for all longer labels
+    pub(crate) vertex_shapes: Vec<VertexShape>,
+}
+
+impl Default for GraphStyle {
+    fn default() -> GraphStyle {
+        return GraphStyle {
+            edge_color: "black".to_string(),
+            cycle_color: "red".to_string(),
+            longest_path_color: "green".to_string(),
+            component_colors: vec![],
+            vertex_shapes: vec![VertexShape::Circle],
+        };
+    }
+}
+
+impl GraphStyle {
+    /// Returns the style passed from R as list with the entries edge_color, cycle_color, longest_path_color,
+    /// component_colors and vertex_shapes, see \link{graph_style}
+    ///
+    /// # Arguments
+    /// * `style` the list passed from R
+    pub(crate) fn from_r(style: &Robj) -> Result<GraphStyle, String> {
+        let style = NamedList::new(style, "style")?;
+        return Ok(GraphStyle {
+            edge_color: style.string("edge_color")?,
+            cycle_color: style.string("cycle_color")?,
+            longest_path_color: style.string("longest_path_color")?,
+            component_colors: style.strings("component_colors")?,
+            vertex_shapes: style.strings("vertex_shapes")?.iter().map(|s| VertexShape::from_name(s))
+                .collect::<Result<Vec<VertexShape>, String>>()?,
+        });
+    }
+
+    /// Returns the shape of a vertex
+    pub(crate) fn vertex_shape(&self, label: &str) -> VertexShape {
+        let length = label.chars().count().max(1);
+        return self.vertex_shapes.get(length - 1).or(self.vertex_shapes.last()).copied().unwrap_or(VertexShape::Circle);
+    }
+}
+
+/// A graph prepared for an export, with the highlighted edges
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct GraphView {
+    pub(crate) vertices: Vec<String>,
+    /// The distinct edges in the order of their first occurrence
+    pub(crate) edges: Vec<(String, String)>,
+    /// The number of parallel edges between the same pair of vertices, i.e. the number of times
+    /// the edge is reported by the graph. Since the word of an edge is the concatenation of its vertices,
+    /// this is 1 for each edge of a code without duplicated words.
+    pub(crate) multiplicity: BTreeMap<(String, String), usize>,
+    /// The edges which are part of a cycle
+    pub(crate) cycle_edges: BTreeSet<(String, String)>,
+    /// The edges which are part of a longest path
+    pub(crate) longest_path_edges: BTreeSet<(String, String)>,
+}
+
+impl GraphView {
+    /// Returns the view of a graph
+    ///
+    /// # Arguments
+    /// * `g` a graph
+    /// * `show_cycles` if true the edges of all cycles are highlighted
+    /// * `show_longest_path` if true the edges of all longest paths are highlighted
+    pub(crate) fn new(g: &CircGraph, show_cycles: bool, show_longest_path: bool) -> GraphView {
+        let cycle_edges = match show_cycles {
+            true => cycles_sub_graph(g).map(|s_g| edge_pairs(&s_g)).unwrap_or_default(),
+            false => vec![],
+        };
+        let longest_path_edges = match show_longest_path {
+            true => longest_paths_sub_graph(g).map(|s_g| edge_pairs(&s_g)).unwrap_or_default(),
+            false => vec![],
+        };
+
+        return GraphView::from_edges(g.get_vertices(), edge_pairs(g), cycle_edges, longest_path_edges);
+    }
+
+    /// Returns the view of a graph given by its edges, e.g. of a graph which is not a [CircGraph]
+    ///
+    /// # Arguments
+    /// * `vertices` the vertices
+    /// * `edges` the edges `(from, to)`, parallel edges are listed repeatedly
+    /// * `cycle_edges` the highlighted edges of cycles
+    /// * `longest_path_edges` the highlighted edges of longest paths
+    pub(crate) fn from_edges(vertices: Vec<String>, edges: Vec<(String, String)>,
+                             cycle_edges: Vec<(String, String)>, longest_path_edges: Vec<(String, String)>) -> GraphView {
+        let mut distinct = Vec::new();
+        let mut multiplicity = BTreeMap::new();
+        for e in edges {
+            let m = multiplicity.entry(e.clone()).or_insert(0);
+            if *m == 0 {
+                distinct.push(e);
+            }
+            *m += 1;
+        }
+
+        return GraphView {
+            vertices,
+            edges: distinct,
+            multiplicity,
+            cycle_edges: cycle_edges.into_iter().collect(),
+            longest_path_edges: longest_path_edges.into_iter().collect(),
+        };
+    }
+
+    /// Returns the number of parallel edges between the vertices of an edge
+    pub(crate) fn edge_multiplicity(&self, e: &(String, String)) -> usize {
+        return self.multiplicity.get(e).copied().unwrap_or(1);
+    }
+
+    /// Returns the color of an edge and true if the edge is highlighted
+    ///
+    /// Edges of cycles take precedence over edges of longest paths, the other edges are colored by their i-component.
+    pub(crate) fn edge_color<'a>(&self, e: &(String, String), style: &'a GraphStyle) -> (&'a str, bool) {
+        if self.cycle_edges.contains(e) {
+            return (&style.cycle_color, true);
+        }
+        if self.longest_path_edges.contains(e) {
+            return (&style.longest_path_color, true);
+        }
+        if style.component_colors.is_empty() {
+            return (&style.edge_color, false);
+        }
+        let i = e.0.chars().count().max(1);
+        return (&style.component_colors[(i - 1) % style.component_colors.len()], false);
+    }
+
+    /// Returns the index of a vertex
+    fn vertex_index(&self, v: &str) -> usize {
+        return self.vertices.iter().position(|x| x == v).unwrap_or(0);
+    }
+
+    /// Returns the position of each vertex on a circle with the radius `radius`
+    pub(crate) fn circular_layout(&self, radius: f64) -> Vec<(f64, f64)> {
+        let n = self.vertices.len().max(1) as f64;
+        return (0..self.vertices.len())
+            .map(|i| {
+                let angle = 90.0 - 360.0 * i as f64 / n;
+                (radius * angle.to_radians().cos(), radius * angle.to_radians().sin())
+            })
+            .collect();
+    }
+
+    /// Returns the layer of each vertex, its breadth-first distance from the sources
+    ///
+    /// Sources are vertices without incoming edges. Vertices which are not reachable from a source
+    /// (e.g. on cycles) start new searches in the order of the vertices.
+    pub(crate) fn layers(&self) -> Vec<usize> {
+        let n = self.vertices.len();
+        let mut successors = vec![vec![]; n];
+        let mut has_incoming = vec![false; n];
+        for (from, to) in &self.edges {
+            let (f, t) = (self.vertex_index(from), self.vertex_index(to));
+            successors[f].push(t);
+            if f != t {
+                has_incoming[t] = true;
+            }
+        }
+
+        let mut layer: Vec<Option<usize>> = vec![None; n];
+        let starts = (0..n).filter(|v| !has_incoming[*v]).chain(0..n).collect::<Vec<usize>>();
+        for start in starts {
+            if layer[start].is_some() {
+                continue;
+            }
+            layer[start] = Some(0);
+            let mut queue = VecDeque::from([start]);
+            while let Some(v) = queue.pop_front() {
+                for &w in &successors[v] {
+                    if layer[w].is_none() {
+                        layer[w] = layer[v].map(|l| l + 1);
+                        queue.push_back(w);
+                    }
+                }
+            }
+        }
+
+        return layer.into_iter().map(|l| l.unwrap_or(0)).collect();
+    }
+
+    /// Returns the position of each vertex in a drawing of the size `width` x `height`
+    pub(crate) fn positions(&self, layout: Layout, width: f64, height: f64) -> Vec<(f64, f64)> {
+        match layout {
+            Layout::Circular => {
+                let radius = 0.4 * width.min(height);
+                return self.circular_layout(radius).into_iter()
+                    .map(|(x, y)| (width / 2.0 + x, height / 2.0 - y))
+                    .collect();
+            }
+            Layout::Layered => {
+                let layers = self.layers();
+                let n_layers = layers.iter().copied().max().map_or(1, |m| m + 1);
+                let mut per_layer = vec![0usize; n_layers];
+                let mut slot = vec![0usize; layers.len()];
+                for (v, l) in layers.iter().enumerate() {
+                    slot[v] = per_layer[*l];
+                    per_layer[*l] += 1;
+                }
+
+                return layers.iter().enumerate()
+                    .map(|(v, l)| (width * (slot[v] as f64 + 1.0) / (per_layer[*l] as f64 + 1.0),
+                                   height * (*l as f64 + 1.0) / (n_layers as f64 + 1.0)))
+                    .collect();
+            }
+        }
+    }
+
+    /// Returns the SVG drawing of the graph
+    ///
+    /// # Arguments
+    /// * `layout` the placement of the vertices
+    /// * `width` the width of the drawing in pixels
+    /// * `height` the height of the drawing in pixels
+    /// * `style` the colors and shapes
+    pub(crate) fn to_svg(&self, layout: Layout, width: f64, height: f64, style: &GraphStyle) -> String {
+        const RADIUS: f64 = 18.0;
+        let positions = self.positions(layout, width, height);
+        let colors = self.edges.iter().map(|e| self.edge_color(e, style).0).collect::<Vec<&str>>();
+        let mut markers = colors.clone();
+        markers.sort();
+        markers.dedup();
+
+        let mut svg = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">\n", width, height);
+        svg.push_str("  <defs>\n");
+        for (id, color) in markers.iter().enumerate() {
+            svg.push_str(&format!("    <marker id=\"arrow-{}\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" markerWidth=\"6\" markerHeight=\"6\" orient=\"auto\">\
+<path d=\"M 0 0 L 10 5 L 0 10 z\" fill=\"{}\"/></marker>\n", id, color));
+        }
+        svg.push_str("  </defs>\n");
+
+        for (e, color) in self.edges.iter().zip(colors.iter()) {
+            let id = markers.iter().position(|m| m == color).unwrap_or(0);
+            let (from, to) = (positions[self.vertex_index(&e.0)], positions[self.vertex_index(&e.1)]);
+            let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+            let length = (dx * dx + dy * dy).sqrt();
+            if length < 1e-9 {
+                svg.push_str(&format!("  <path d=\"M {:.1} {:.1} C {:.1} {:.1} {:.1} {:.1} {:.1} {:.1}\" fill=\"none\" stroke=\"{}\" marker-end=\"url(#arrow-{})\"/>\n",
+                                      from.0 - RADIUS / 2.0, from.1 - RADIUS, from.0 - 2.0 * RADIUS, from.1 - 3.0 * RADIUS,
+                                      from.0 + 2.0 * RADIUS, from.1 - 3.0 * RADIUS, from.0 + RADIUS / 2.0, from.1 - RADIUS, color, id));
+                continue;
+            }
+            let (ux, uy) = (dx / length, dy / length);
+            svg.push_str(&format!("  <line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"{}\" stroke-width=\"{}\" marker-end=\"url(#arrow-{})\"/>\n",
+                                  from.0 + ux * RADIUS, from.1 + uy * RADIUS, to.0 - ux * RADIUS, to.1 - uy * RADIUS, color,
+                                  self.edge_multiplicity(e), id));
+        }
+        for (v, (x, y)) in self.vertices.iter().zip(positions.iter()) {
+            match style.vertex_shape(v) {
+                VertexShape::Circle => svg.push_str(&format!("  <circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{}\" fill=\"white\" stroke=\"black\"/>\n", x, y, RADIUS)),
+                VertexShape::Box => svg.push_str(&format!("  <rect x=\"{:.1}\" y=\"{:.1}\" width=\"{}\" height=\"{}\" fill=\"white\" stroke=\"black\"/>\n",
+                                                          x - RADIUS, y - RADIUS, 2.0 * RADIUS, 2.0 * RADIUS)),
+                VertexShape::Diamond => svg.push_str(&format!("  <polygon points=\"{:.1},{:.1} {:.1},{:.1} {:.1},{:.1} {:.1},{:.1}\" fill=\"white\" stroke=\"black\"/>\n",
+                                                              x, y - RADIUS, x + RADIUS, y, x, y + RADIUS, x - RADIUS, y)),
+            }
+            svg.push_str(&format!("  <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" dominant-baseline=\"central\" font-family=\"monospace\" font-size=\"11\">{}</text>\n",
+                                  x, y, v));
+        }
+        svg.push_str("</svg>\n");
+
+        return svg;
+    }
+
+    /// Returns the graph in the GML format (e.g. for igraph or Cytoscape)
+    ///
+    /// Each edge has the attributes `cycle` and `longest_path` (0 or 1) for the membership in the highlighted edges
+    /// and its `multiplicity`. The style is stored in the `graphics` attributes of the vertices and edges.
+    ///
+    /// # Arguments
+    /// * `style` the colors and shapes
+    pub(crate) fn to_gml(&self, style: &GraphStyle) -> String {
+        let mut gml = String::from("graph [\n  directed 1\n");
+        for (i, v) in self.vertices.iter().enumerate() {
+            gml.push_str(&format!("  node [\n    id {}\n    label \"{}\"\n    graphics [\n      type \"{}\"\n    ]\n  ]\n",
+                                  i, v, style.vertex_shape(v).gml_name()));
+        }
+        for e in &self.edges {
+            gml.push_str(&format!("  edge [\n    source {}\n    target {}\n    label \"{}{}\"\n    cycle {}\n    longest_path {}\n    multiplicity {}\n    graphics [\n      fill \"{}\"\n    ]\n  ]\n",
+                                  self.vertex_index(&e.0), self.vertex_index(&e.1), e.0, e.1,
+                                  self.cycle_edges.contains(e) as u8, self.longest_path_edges.contains(e) as u8, self.edge_multiplicity(e),
+                                  self.edge_color(e, style).0));
+        }
+        gml.push_str("]\n");
+
+        return gml;
+    }
+
+    /// Returns the graph in the DOT format of graphviz
+    ///
+    /// Highlighted edges are drawn bold, parallel edges are drawn once with the multiplicity as label.
+    ///
+    /// # Arguments
+    /// * `style` the colors and shapes
+    pub(crate) fn to_dot(&self, style: &GraphStyle) -> String {
+        let mut dot = String::from("digraph representing_graph {\n");
+        for (i, v) in self.vertices.iter().enumerate() {
+            dot.push_str(&format!("    v{} [label = \"{}\", shape = {}];\n", i, v, style.vertex_shape(v).dot_name()));
+        }
+        for e in &self.edges {
+            let (color, highlighted) = self.edge_color(e, style);
+            let mut attributes = vec![format!("color = \"{}\"", color)];
+            if highlighted {
+                attributes.push("style = bold".to_string());
+            }
+            if self.edge_multiplicity(e) > 1 {
+                attributes.push(format!("label = \"x{}\"", self.edge_multiplicity(e)));
+            }
+            dot.push_str(&format!("    v{} -> v{} [{}];\n", self.vertex_index(&e.0), self.vertex_index(&e.1), attributes.join(", ")));
+        }
+        dot.push_str("}\n");
+
+        return dot;
+    }
+
+    /// Returns the TikZ figure of the graph
+    ///
+    /// The vertices are placed on a circle, highlighted edges are drawn thick.
+    /// The shape diamond requires the TikZ library `shapes.geometric`, which is loaded by a standalone document.
+    ///
+    /// # Arguments
+    /// * `radius` the radius of the circle in cm
+    /// * `standalone` if true a complete LaTeX document is returned, otherwise only the tikzpicture
+    /// * `style` the colors and shapes
+    pub(crate) fn to_tikz(&self, radius: f64, standalone: bool, style: &GraphStyle) -> String {
+        let mut tikz = String::new();
+        if standalone {
+            tikz.push_str("\\documentclass[tikz]{standalone}\n\\usetikzlibrary{arrows.meta, shapes.geometric}\n\\begin{document}\n");
+        }
+        tikz.push_str("\\begin{tikzpicture}[>={Stealth}, vertex/.style={draw, inner sep=1pt, font=\\footnotesize\\ttfamily}]\n");
+        for (i, (x, y)) in self.circular_layout(radius).iter().enumerate() {
+            tikz.push_str(&format!("  \\node[vertex, {}] (v{}) at ({:.3}, {:.3}) {{{}}};\n",
+                                   style.vertex_shape(&self.vertices[i]).tikz_name(), i, x, y, self.vertices[i]));
+        }
+        for e in &self.edges {
+            let color = match self.edge_color(e, style) {
+                (color, true) => format!("{}, thick", color),
+                (color, false) => color.to_string(),
+            };
+            let (from, to) = (self.vertex_index(&e.0), self.vertex_index(&e.1));
+            let bend = if from == to { "loop above" } else { "bend left=10" };
+            let label = match self.edge_multiplicity(e) {
+                1 => String::new(),
+                m => format!(" node[auto, font=\\scriptsize] {{$\\times {}$}}", m),
+            };
+            tikz.push_str(&format!("  \\path (v{}) edge[->, {}, {}]{} (v{});\n", from, color, bend, label, to));
+        }
+        tikz.push_str("\\end{tikzpicture}\n");
+        if standalone {
+            tikz.push_str("\\end{document}\n");
+        }
+
+        return tikz;
+    }
+}
+
+impl OverlapGraph {
+    /// Returns the view of the graph for the exports
+    ///
+    /// # Arguments
+    /// * `show_cycles` if true the edges of all cycles are highlighted
+    /// * `show_longest_path` if true the edges of all longest paths are highlighted
+    pub(crate) fn view(&self, show_cycles: bool, show_longest_path: bool) -> GraphView {
+        let (cycle_edges, longest_path_edges) = self.highlighted_edges();
+        return GraphView::from_edges(self.vertices.clone(), self.edges.clone(),
+                                     if show_cycles { cycle_edges } else { vec![] },
+                                     if show_longest_path { longest_path_edges } else { vec![] });
+    }
+}
+
+/// Parses the style passed from R (see \link{graph_style}) or stops with an R error
+///
+/// # Arguments
+/// * `style` the list passed from R
+pub(crate) fn style_from_r(style: &Robj) -> GraphStyle {
+    match GraphStyle::from_r(style) {
+        Ok(style) => return style,
+        Err(e) => {
+            r_error!("Style is not correct: {}", e);
+        }
+    }
+}
+
+/// Returns the TikZ figure of the graph associated to a code
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param show_cycles A boolean, if true all edges in all cyclic paths are highlighted
+/// @param show_longest_path A boolean, if true all edges in all longest paths are highlighted
+/// @param standalone A boolean, if true a complete LaTeX document is returned
+/// @param style A list, the style of the graph (see \link{graph_style})
+///
+/// @return A String, the TikZ code.
+///
+/// @seealso \link{get_representing_graph_tikz}
+///
+#[extendr]
+fn get_representing_graph_tikz_obj(tuples: Vec<String>, show_cycles: bool, show_longest_path: bool, standalone: bool,
+                                   style: Robj) -> String {
+    return guard(|| {
+        let style = style_from_r(&style);
+        let code = new_code_from_vec(tuples);
+        let g = match associated_graph(&code) {
+            Ok(graph) => graph,
+            Err(e) => {
+                r_error!("Graph is corrupted: {}", e);
+            }
+        };
+
+        return GraphView::new(&g, show_cycles, show_longest_path).to_tikz(3.0, standalone, &style);
+    });
+}
+
+/// Returns the SVG drawing of the graph associated to a code
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param show_cycles A boolean, if true all edges in all cyclic paths are highlighted
+/// @param show_longest_path A boolean, if true all edges in all longest paths are highlighted
+/// @param layout A String, "circular" or "layered"
+/// @param width A numeric value, the width in pixels
+/// @param height A numeric value, the height in pixels
+/// @param style A list, the style of the graph (see \link{graph_style})
+///
+/// @return A String, the SVG document.
+///
+/// @seealso \link{get_representing_graph_svg}
+///
+#[extendr]
+fn get_representing_graph_svg_obj(tuples: Vec<String>, show_cycles: bool, show_longest_path: bool, layout: String, width: f64, height: f64,
+                                  style: Robj) -> String {
+    return guard(|| {
+        let layout = match Layout::from_name(&layout) {
+            Ok(layout) => layout,
+            Err(e) => {
+                r_error!("Layout is not correct: {}", e);
+            }
+        };
+        let style = style_from_r(&style);
+        let code = new_code_from_vec(tuples);
+        let g = match associated_graph(&code) {
+            Ok(graph) => graph,
+            Err(e) => {
+                r_error!("Graph is corrupted: {}", e);
+            }
+        };
+
+        return GraphView::new(&g, show_cycles, show_longest_path).to_svg(layout, width, height, &style);
+    });
+}
+
+/// Returns the graph associated to a code in the GML format
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param style A list, the style of the graph (see \link{graph_style})
+///
+/// @return A String, the GML document.
+///
+/// @seealso \link{get_representing_graph_gml}
+///
+#[extendr]
+fn get_representing_graph_gml_obj(tuples: Vec<String>, style: Robj) -> String {
+    return guard(|| {
+        let style = style_from_r(&style);
+        let code = new_code_from_vec(tuples);
+        let g = match associated_graph(&code) {
+            Ok(graph) => graph,
+            Err(e) => {
+                r_error!("Graph is corrupted: {}", e);
+            }
+        };
+
+        return GraphView::new(&g, true, true).to_gml(&style);
+    });
+}
+
+/// Returns the graph associated to a code in the DOT format
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param show_cycles A boolean, if true all edges in all cyclic paths are highlighted
+/// @param show_longest_path A boolean, if true all edges in all longest paths are highlighted
+/// @param style A list, the style of the graph (see \link{graph_style})
+///
+/// @return A String, the DOT document.
+///
+/// @seealso \link{get_representing_graph_dot}
+///
+#[extendr]
+fn get_representing_graph_dot_obj(tuples: Vec<String>, show_cycles: bool, show_longest_path: bool, style: Robj) -> String {
+    return guard(|| {
+        let style = style_from_r(&style);
+        let code = new_code_from_vec(tuples);
+        let g = match associated_graph(&code) {
+            Ok(graph) => graph,
+            Err(e) => {
+                r_error!("Graph is corrupted: {}", e);
+            }
+        };
+
+        return GraphView::new(&g, show_cycles, show_longest_path).to_dot(&style);
+    });
+}
+
+/// Returns the overlap graph of a code in an export format
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param format A String, "dot", "gml", "svg" or "tikz"
+/// @param show_cycles A boolean, if true all edges in all cyclic paths are highlighted
+/// @param show_longest_path A boolean, if true all edges in all longest paths are highlighted
+/// @param style A list, the style of the graph (see \link{graph_style}), the component colors are used by word length
+///
+/// @return A String, the document.
+///
+/// @seealso \link{get_overlap_graph_export}
+///
+#[extendr]
+fn get_overlap_graph_export_obj(tuples: Vec<String>, format: String, show_cycles: bool, show_longest_path: bool,
+                                style: Robj) -> String {
+    return guard(|| {
+        let style = style_from_r(&style);
+        let code = new_code_from_vec(tuples);
+        let view = OverlapGraph::new(&code.get_code()).view(show_cycles, show_longest_path);
+        match format.as_str() {
+            "dot" => return view.to_dot(&style),
+            "gml" => return view.to_gml(&style),
+            "svg" => return view.to_svg(Layout::Circular, 600.0, 600.0, &style),
+            "tikz" => return view.to_tikz(3.0, false, &style),
+            _ => {
+                r_error!("Format is not correct: unknown format '{}', expected dot, gml, svg or tikz", format);
+            }
+        }
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod graph_export;
+    fn get_representing_graph_tikz_obj;
+    fn get_representing_graph_svg_obj;
+    fn get_representing_graph_gml_obj;
+    fn get_representing_graph_dot_obj;
+    fn get_overlap_graph_export_obj;
+}
@@ -0,0 +1,735 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+use extendr_api::prelude::*;
+
+use crate::gcat::{associated_graph, component, CircGraph};
+use crate::graph::edge_pairs;
+use crate::guard::guard;
+use crate::lib_utils::{component_index_from_r, new_code_from_vec};
+use crate::limits::{Budget, SearchLimits};
+
+/// A graph with vertices addressed by their index, for structural algorithms
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct IndexedGraph {
+    pub(crate) vertices: Vec<String>,
+    /// The distinct successors of each vertex
+    pub(crate) successors: Vec<Vec<usize>>,
+}
+
+impl IndexedGraph {
+    /// Returns the indexed graph of a graph (the vertices keep their order)
+    ///
+    /// # Arguments
+    /// * `g` a graph
+    pub(crate) fn new(g: &CircGraph) -> IndexedGraph {
+        return IndexedGraph::from_edges(g.get_vertices(), &edge_pairs(g));
+    }
+
+    /// Returns the indexed graph of a list of edges (the vertices keep their order)
+    ///
+    /// Edges with an unknown vertex are ignored.
+    ///
+    /// # Arguments
+    /// * `vertices` the vertices
+    /// * `edges` the edges `(from, to)`
+    pub(crate) fn from_edges(vertices: Vec<String>, edges: &[(String, String)]) -> IndexedGraph {
+        let mut successors = vec![vec![]; vertices.len()];
+        for (from, to) in edges {
+            let f = vertices.iter().position(|v| v == from);
+            let t = vertices.iter().position(|v| v == to);
+            if let (Some(f), Some(t)) = (f, t) {
+                if !successors[f].contains(&t) {
+                    successors[f].push(t);
+                }
+            }
+        }
+
+        return IndexedGraph { vertices, successors };
+    }
+
+    /// Returns the strongly connected components in reverse topological order (Tarjan's algorithm)
+    ///
+    /// Each component is a list of vertex indices. The algorithm is iterative, so large graphs do not overflow the stack.
+    pub(crate) fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        let n = self.vertices.len();
+        let mut index = vec![usize::MAX; n];
+        let mut low = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut stack = Vec::new();
+        let mut components = Vec::new();
+        let mut next_index = 0;
+
+        for root in 0..n {
+            if index[root] != usize::MAX {
+                continue;
+            }
+            // (vertex, index of the next successor to visit)
+            let mut call_stack = vec![(root, 0)];
+            index[root] = next_index;
+            low[root] = next_index;
+            next_index += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            while let Some((v, i)) = call_stack.pop() {
+                if i < self.successors[v].len() {
+                    call_stack.push((v, i + 1));
+                    let w = self.successors[v][i];
+                    if index[w] == usize::MAX {
+                        index[w] = next_index;
+                        low[w] = next_index;
+                        next_index += 1;
+                        stack.push(w);
+                        on_stack[w] = true;
+                        call_stack.push((w, 0));
+                    } else if on_stack[w] {
+                        low[v] = low[v].min(index[w]);
+                    }
+                    continue;
+                }
+
+                if low[v] == index[v] {
+                    let mut component = Vec::new();
+                    while let Some(w) = stack.pop() {
+                        on_stack[w] = false;
+                        component.push(w);
+                        if w == v {
+                            break;
+                        }
+                    }
+                    component.sort_unstable();
+                    components.push(component);
+                }
+                if let Some((parent, _)) = call_stack.last() {
+                    low[*parent] = low[*parent].min(low[v]);
+                }
+            }
+        }
+
+        return components;
+    }
+
+    /// Returns the reachability matrix (transitive closure) as bitset rows
+    ///
+    /// Bit `w` of row `v` (word `w / 64`, bit `w % 64`) is set iff there is a path with at least one edge
+    /// from `v` to `w`, so the diagonal marks the vertices on cycles. The rows are computed once per
+    /// strongly connected component in reverse topological order.
+    pub(crate) fn reachability_matrix(&self) -> Vec<Vec<u64>> {
+        let n = self.vertices.len();
+        let blocks = n.div_ceil(64);
+        let mut rows = vec![vec![0u64; blocks]; n];
+        let mut component_of = vec![0; n];
+        let components = self.strongly_connected_components();
+        for (c, members) in components.iter().enumerate() {
+            for &v in members {
+                component_of[v] = c;
+            }
+        }
+
+        // Tarjan's algorithm returns the components in reverse topological order, successors first
+        for (c, members) in components.iter().enumerate() {
+            let mut row = vec![0u64; blocks];
+            let nontrivial = members.len() > 1 || self.successors[members[0]].contains(&members[0]);
+            if nontrivial {
+                for &v in members {
+                    row[v / 64] |= 1 << (v % 64);
+                }
+            }
+            for &v in members {
+                for &w in &self.successors[v] {
+                    if component_of[w] != c {
+                        row[w / 64] |= 1 << (w % 64);
+                        for (b, bits) in rows[w].iter().enumerate() {
+                            row[b] |= bits;
+                        }
+                    }
+                }
+            }
+            for &v in members {
+                rows[v] = row.clone();
+            }
+        }
+
+        return rows;
+    }
+
+    /// Returns the condensation of the graph, the DAG of its strongly connected components
+    pub(crate) fn condensation(&self) -> Condensation {
+        let mut components = self.strongly_connected_components();
+        components.reverse();
+        let mut component_of = vec![0; self.vertices.len()];
+        for (c, members) in components.iter().enumerate() {
+            for &v in members {
+                component_of[v] = c;
+            }
+        }
+
+        let mut edges = Vec::new();
+        for (v, succ) in self.successors.iter().enumerate() {
+            for &w in succ {
+                let e = (component_of[v], component_of[w]);
+                if e.0 != e.1 && !edges.contains(&e) {
+                    edges.push(e);
+                }
+            }
+        }
+        edges.sort_unstable();
+
+        return Condensation {
+            nontrivial: components.iter()
+                .map(|c| c.len() > 1 || self.successors[c[0]].contains(&c[0]))
+                .collect(),
+            members: components.into_iter()
+                .map(|c| c.into_iter().map(|v| self.vertices[v].clone()).collect())
+                .collect(),
+            edges,
+        };
+    }
+
+    /// Returns the vertices in topological order, i.e. every edge points from an earlier to a later vertex
+    ///
+    /// Among the vertices which are ready, the lexicographically smallest comes first, so the order is unique.
+    /// Returns an error naming a vertex on a cycle if the graph is cyclic.
+    pub(crate) fn topological_order(&self) -> Result<Vec<String>, String> {
+        let mut degrees = self.in_degrees();
+        let mut ready = (0..self.vertices.len())
+            .filter(|v| degrees[*v] == 0)
+            .map(|v| Reverse((self.vertices[v].clone(), v)))
+            .collect::<BinaryHeap<Reverse<(String, usize)>>>();
+        let mut order = Vec::new();
+        while let Some(Reverse((label, v))) = ready.pop() {
+            order.push(label);
+            for &w in &self.successors[v] {
+                degrees[w] -= 1;
+                if degrees[w] == 0 {
+                    ready.push(Reverse((self.vertices[w].clone(), w)));
+                }
+            }
+        }
+
+        if order.len() < self.vertices.len() {
+            let v = (0..self.vertices.len()).find(|v| degrees[*v] > 0).unwrap_or(0);
+            return Err(format!("The graph is cyclic, e.g. the vertex '{}' lies on or behind a cycle", self.vertices[v]));
+        }
+
+        return Ok(order);
+    }
+
+    /// Returns the neighbours of each vertex ignoring the orientation of the edges
+    pub(crate) fn undirected(&self) -> Vec<Vec<usize>> {
+        let mut neighbours = self.successors.clone();
+        for (v, succ) in self.successors.iter().enumerate() {
+            for &w in succ {
+                if !neighbours[w].contains(&v) {
+                    neighbours[w].push(v);
+                }
+            }
+        }
+
+        return neighbours;
+    }
+
+    /// Returns true if the vertices can be colored with two colors such that no edge connects
+    /// two vertices of the same color (the orientation of the edges is ignored, a loop is never bipartite)
+    pub(crate) fn is_bipartite(&self) -> bool {
+        let neighbours = self.undirected();
+        let mut color: Vec<Option<bool>> = vec![None; self.vertices.len()];
+        for start in 0..self.vertices.len() {
+            if color[start].is_some() {
+                continue;
+            }
+            color[start] = Some(false);
+            let mut queue = VecDeque::from([start]);
+            while let Some(v) = queue.pop_front() {
+                for &w in &neighbours[v] {
+                    match color[w] {
+                        None => {
+                            color[w] = color[v].map(|c| !c);
+                            queue.push_back(w);
+                        }
+                        Some(c) if Some(c) == color[v] => return false,
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        return true;
+    }
+
+    /// Returns true if the graph is connected if the orientation of the edges is ignored
+    pub(crate) fn is_weakly_connected(&self) -> bool {
+        if self.vertices.is_empty() {
+            return true;
+        }
+
+        let neighbours = self.undirected();
+        let mut seen = vec![false; self.vertices.len()];
+        seen[0] = true;
+        let mut queue = VecDeque::from([0]);
+        while let Some(v) = queue.pop_front() {
+            for &w in &neighbours[v] {
+                if !seen[w] {
+                    seen[w] = true;
+                    queue.push_back(w);
+                }
+            }
+        }
+
+        return seen.into_iter().all(|s| s);
+    }
+
+    /// Returns the in-degree of each vertex
+    pub(crate) fn in_degrees(&self) -> Vec<usize> {
+        let mut degrees = vec![0; self.vertices.len()];
+        for succ in &self.successors {
+            for &w in succ {
+                degrees[w] += 1;
+            }
+        }
+
+        return degrees;
+    }
+
+    /// Returns the vertices without incoming edges
+    pub(crate) fn sources(&self) -> Vec<String> {
+        let degrees = self.in_degrees();
+        return (0..self.vertices.len()).filter(|v| degrees[*v] == 0).map(|v| self.vertices[v].clone()).collect();
+    }
+
+    /// Returns the vertices without outgoing edges
+    pub(crate) fn sinks(&self) -> Vec<String> {
+        return (0..self.vertices.len()).filter(|v| self.successors[*v].is_empty()).map(|v| self.vertices[v].clone()).collect();
+    }
+
+    /// Returns the number of weakly connected components
+    pub(crate) fn weak_component_count(&self) -> usize {
+        let neighbours = self.undirected();
+        let mut seen = vec![false; self.vertices.len()];
+        let mut count = 0;
+        for start in 0..self.vertices.len() {
+            if seen[start] {
+                continue;
+            }
+            count += 1;
+            seen[start] = true;
+            let mut queue = VecDeque::from([start]);
+            while let Some(v) = queue.pop_front() {
+                for &w in &neighbours[v] {
+                    if !seen[w] {
+                        seen[w] = true;
+                        queue.push_back(w);
+                    }
+                }
+            }
+        }
+
+        return count;
+    }
+
+    /// Returns the number of edges of a shortest cycle (a breadth-first search from each vertex), `None` if the graph is acyclic
+    pub(crate) fn girth(&self) -> Option<usize> {
+        let mut girth: Option<usize> = None;
+        for start in 0..self.vertices.len() {
+            let mut dist = vec![usize::MAX; self.vertices.len()];
+            dist[start] = 0;
+            let mut queue = VecDeque::from([start]);
+            'search: while let Some(v) = queue.pop_front() {
+                if girth.is_some_and(|g| dist[v] + 1 >= g) {
+                    break;
+                }
+                for &w in &self.successors[v] {
+                    if w == start {
+                        girth = Some(dist[v] + 1);
+                        break 'search;
+                    }
+                    if dist[w] == usize::MAX {
+                        dist[w] = dist[v] + 1;
+                        queue.push_back(w);
+                    }
+                }
+            }
+        }
+
+        return girth;
+    }
+
+    /// Returns the number of edges of a longest path, `None` if the graph is cyclic
+    pub(crate) fn longest_path_length(&self) -> Option<usize> {
+        let mut degrees = self.in_degrees();
+        let mut dist = vec![0; self.vertices.len()];
+        let mut ready = (0..self.vertices.len()).filter(|v| degrees[*v] == 0).collect::<Vec<usize>>();
+        let mut visited = 0;
+        while let Some(v) = ready.pop() {
+            visited += 1;
+            for &w in &self.successors[v] {
+                dist[w] = dist[w].max(dist[v] + 1);
+                degrees[w] -= 1;
+                if degrees[w] == 0 {
+                    ready.push(w);
+                }
+            }
+        }
+        if visited < self.vertices.len() {
+            return None;
+        }
+
+        return Some(dist.into_iter().max().unwrap_or(0));
+    }
+
+    /// Returns the number of simple cycles found within the budget
+    ///
+    /// Each cycle is counted once from its smallest vertex, the cycles are not stored.
+    ///
+    /// # Arguments
+    /// * `budget` the limits of the search, each cycle is a result
+    pub(crate) fn count_cycles(&self, budget: &mut Budget) -> usize {
+        // Returns false if the search has to stop
+        fn dfs(start: usize, v: usize, succ: &[Vec<usize>], on_path: &mut [bool], count: &mut usize, budget: &mut Budget) -> bool {
+            for &w in &succ[v] {
+                if !budget.visit() {
+                    return false;
+                }
+                if w == start {
+                    if !budget.found() {
+                        return false;
+                    }
+                    *count += 1;
+                } else if w > start && !on_path[w] {
+                    on_path[w] = true;
+                    let go_on = dfs(start, w, succ, on_path, count, budget);
+                    on_path[w] = false;
+                    if !go_on {
+                        return false;
+                    }
+                }
+            }
+
+            return true;
+        }
+
+        let mut count = 0;
+        let mut on_path = vec![false; self.vertices.len()];
+        for start in 0..self.vertices.len() {
+            on_path[start] = true;
+            let go_on = dfs(start, start, &self.successors, &mut on_path, &mut count, budget);
+            on_path[start] = false;
+            if !go_on {
+                break;
+            }
+        }
+
+        return count;
+    }
+
+    /// Returns all edges `(from, to)` which are part of a cycle
+    ///
+    /// An edge is part of a cycle iff both vertices belong to the same strongly connected component.
+    pub(crate) fn cycle_edges(&self) -> Vec<(usize, usize)> {
+        let mut component_of = vec![0; self.vertices.len()];
+        for (c, members) in self.strongly_connected_components().iter().enumerate() {
+            for &v in members {
+                component_of[v] = c;
+            }
+        }
+
+        return (0..self.vertices.len())
+            .flat_map(|v| self.successors[v].iter().map(move |w| (v, *w)))
+            .filter(|(v, w)| component_of[*v] == component_of[*w])
+            .collect();
+    }
+
+    /// Returns all edges `(from, to)` which are part of a longest path, `None` if the graph is cyclic
+    ///
+    /// An edge is part of a longest path iff the longest path ending in `from` and the longest path
+    /// starting in `to` are joined by the edge to a path of maximal length.
+    pub(crate) fn longest_path_edges(&self) -> Option<Vec<(usize, usize)>> {
+        let max = self.longest_path_length()?;
+        let n = self.vertices.len();
+        let order = self.topological_order().ok()?.iter()
+            .filter_map(|label| self.vertices.iter().position(|v| v == label))
+            .collect::<Vec<usize>>();
+        let mut ending = vec![0; n];
+        let mut starting = vec![0; n];
+        for &v in &order {
+            for &w in &self.successors[v] {
+                ending[w] = ending[w].max(ending[v] + 1);
+            }
+        }
+        for &v in order.iter().rev() {
+            for &w in &self.successors[v] {
+                starting[v] = starting[v].max(starting[w] + 1);
+            }
+        }
+
+        return Some((0..n)
+            .flat_map(|v| self.successors[v].iter().map(move |w| (v, *w)))
+            .filter(|(v, w)| max > 0 && ending[*v] + 1 + starting[*w] == max)
+            .collect());
+    }
+
+    /// Returns the metrics of the graph
+    ///
+    /// # Arguments
+    /// * `budget` the limits of the cycle count
+    pub(crate) fn metrics(&self, budget: &mut Budget) -> GraphMetrics {
+        let girth = self.girth();
+        return GraphMetrics {
+            vertices: self.vertices.len(),
+            edges: self.successors.iter().map(|s| s.len()).sum(),
+            components: self.weak_component_count(),
+            sources: self.sources().len(),
+            sinks: self.sinks().len(),
+            cyclic: girth.is_some(),
+            girth,
+            longest_path: if girth.is_some() { None } else { self.longest_path_length() },
+            cycles: if girth.is_some() { self.count_cycles(budget) } else { 0 },
+        };
+    }
+}
+
+/// The metrics of a graph
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct GraphMetrics {
+    pub(crate) vertices: usize,
+    /// The number of distinct edges
+    pub(crate) edges: usize,
+    /// The number of weakly connected components
+    pub(crate) components: usize,
+    pub(crate) sources: usize,
+    pub(crate) sinks: usize,
+    pub(crate) cyclic: bool,
+    /// The number of edges of a shortest cycle, `None` if the graph is acyclic
+    pub(crate) girth: Option<usize>,
+    /// The number of edges of a longest path, `None` if the graph is cyclic
+    pub(crate) longest_path: Option<usize>,
+    /// The number of simple cycles (a lower bound if the count was stopped by a limit)
+    pub(crate) cycles: usize,
+}
+
+/// The condensation of a graph: the DAG of its strongly connected components
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Condensation {
+    /// The member vertices of each strongly connected component, in topological order of the components
+    pub(crate) members: Vec<Vec<String>>,
+    /// The distinct edges `(from, to)` between the components
+    pub(crate) edges: Vec<(usize, usize)>,
+    /// True if the component contains a cycle (more than one vertex or a loop)
+    pub(crate) nontrivial: Vec<bool>,
+}
+
+/// Returns the graph associated to a code as indexed graph, stops in R on errors
+fn graph_from_r(tuples: Vec<String>) -> IndexedGraph {
+    let code = new_code_from_vec(tuples);
+    match associated_graph(&code) {
+        Ok(g) => return IndexedGraph::new(&g),
+        Err(e) => {
+            r_error!("Graph is corrupted: {}", e);
+        }
+    }
+}
+
+/// Returns the i-component of the graph associated to a code as indexed graph, stops in R on errors
+fn component_from_r(tuples: Vec<String>, i: i32) -> IndexedGraph {
+    let code = new_code_from_vec(tuples);
+    let g = match associated_graph(&code) {
+        Ok(graph) => graph,
+        Err(e) => {
+            r_error!("Graph is corrupted: {}", e);
+        }
+    };
+
+    match component(&g, component_index_from_r(i)) {
+        Ok(c) => return IndexedGraph::new(&c),
+        Err(e) => {
+            r_error!("Graph is corrupted: {}", e);
+        }
+    }
+}
+
+/// Checks if an i-component of the graph associated to a code is bipartite
+///
+/// A graph is bipartite if its vertices can be colored with two colors such that no edge
+/// connects two vertices of the same color. The orientation of the edges is ignored.
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param i An integer, the component index
+///
+/// @return Boolean value. True if the component is bipartite.
+///
+/// @examples
+/// code <- gcatbase::code(c("ACGC", "CGGG", "AC"))
+/// is_component_bipartite(code, 1)
+///
+/// @seealso \link{get_component_structure}
+///
+/// @export
+#[extendr]
+fn is_component_bipartite(tuples: Vec<String>, i: i32) -> bool {
+    return guard(|| {
+        return component_from_r(tuples, i).is_bipartite();
+    });
+}
+
+/// Returns structural properties of an i-component of the graph associated to a code
+///
+/// This function supports computational experiments on the structure of the
+/// i-components, see \link{get_component_of_representing_graph}.
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param i An integer, the component index
+///
+/// @return A list with the booleans bipartite and weakly_connected (orientation of the edges ignored)
+/// and the String vectors sources (vertices without incoming edges) and sinks (vertices without outgoing edges).
+///
+/// @examples
+/// code <- gcatbase::code(c("ACGC", "CGGG", "AC"))
+/// get_component_structure(code, 1)
+///
+/// @seealso \link{is_component_bipartite}
+///
+/// @export
+#[extendr]
+fn get_component_structure(tuples: Vec<String>, i: i32) -> Robj {
+    return guard(|| {
+        let g = component_from_r(tuples, i);
+        return list!(bipartite = g.is_bipartite(),
+                      weakly_connected = g.is_weakly_connected(),
+                      sources = g.sources(),
+                      sinks = g.sinks());
+    });
+}
+
+/// Returns the condensation of the graph associated to a code
+///
+/// The condensation contracts each strongly connected component (SCC) of the graph to a single vertex,
+/// which results in a directed acyclic graph. A code is circular iff its graph has no cycles, i.e.
+/// iff no SCC is nontrivial, so the members of the nontrivial SCCs show the prefixes and suffixes which
+/// make a large code fail circularity.
+///
+/// @param tuples A gcatbase::gcat.code object
+///
+/// @return A list with the member vertices of each SCC (members, a list of String vectors in topological order),
+/// a boolean vector (nontrivial) which is true for SCCs containing a cycle and the edges between the SCCs
+/// as consecutive pairs of SCC indices starting with 1 (edges).
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGA", "CA"))
+/// get_condensation(code)
+///
+/// @seealso \link{get_cyclic_paths}
+///
+/// @export
+#[extendr]
+fn get_condensation(tuples: Vec<String>) -> Robj {
+    return guard(|| {
+        let c = graph_from_r(tuples).condensation();
+
+        return list!(members = c.members.iter().map(|m| m.iter().collect_robj()).collect::<Vec<Robj>>(),
+        nontrivial = c.nontrivial,
+        edges = c.edges.iter().flat_map(|e| [e.0 as i32 + 1, e.1 as i32 + 1]).collect::<Vec<i32>>());
+    });
+}
+
+/// Returns the vertices of the graph associated to a code in topological order
+///
+/// In a topological order every edge points from an earlier to a later vertex. Such an order exists iff the graph
+/// is acyclic, i.e. iff the code is circular (see \link{is_code_circular}). Among the possible orders the
+/// lexicographically smallest vertex which is ready comes first. The order presents acyclic graphs in a readable way
+/// and is the base of the longest path computation. The function stops with an error if the graph is cyclic.
+///
+/// @param tuples A gcatbase::gcat.code object
+///
+/// @return A String vector, the vertices in topological order.
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGG", "AC"))
+/// get_topological_order(code)
+///
+/// @seealso \link{get_longest_paths}
+///
+/// @export
+#[extendr]
+fn get_topological_order(tuples: Vec<String>) -> Vec<String> {
+    return guard(|| {
+        match graph_from_r(tuples).topological_order() {
+            Ok(order) => return order,
+            Err(e) => {
+                r_error!("Graph is cyclic: {}", e);
+            }
+        }
+    });
+}
+
+/// Returns the reachability matrix of the graph associated to a code
+///
+/// @param tuples A gcatbase::gcat.code object
+///
+/// @return A list with the vertices (vertices) and the matrix entries in row major order (reachable).
+///
+/// @seealso \link{get_reachability_matrix}
+///
+#[extendr]
+fn get_reachability_matrix_obj(tuples: Vec<String>) -> Robj {
+    return guard(|| {
+        let g = graph_from_r(tuples);
+
+        let n = g.vertices.len();
+        let rows = g.reachability_matrix();
+        let reachable = (0..n * n).map(|i| (rows[i / n][(i % n) / 64] >> (i % n % 64)) & 1 == 1).collect::<Vec<bool>>();
+        return list!(vertices = g.vertices, reachable = reachable);
+    });
+}
+
+/// Returns the metrics of a graph as R list, see \link{graph_metrics}
+///
+/// # Arguments
+/// * `m` the metrics
+/// * `truncated` true if the limit stopped the cycle count
+pub(crate) fn metrics_robj(m: &GraphMetrics, truncated: bool) -> Robj {
+    return list!(vertices = m.vertices as i32,
+    edges = m.edges as i32,
+    components = m.components as i32,
+    sources = m.sources as i32,
+    sinks = m.sinks as i32,
+    cyclic = m.cyclic,
+    girth = m.girth.map_or(-1, |g| g as i32),
+    longest_path = m.longest_path.map_or(-1, |l| l as i32),
+    cycles = m.cycles as f64,
+    truncated = truncated);
+}
+
+/// Returns the metrics of the graph associated to a code
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param max_millis A numeric value, the maximal run time of the cycle count in milliseconds (non-positive: unlimited)
+///
+/// @return A list with the metrics (vertices, edges, components, sources, sinks, cyclic, girth, longest_path, cycles),
+/// girth and longest_path are -1 if they are not defined. The boolean truncated is true if the limit stopped the cycle count.
+///
+/// @seealso \link{graph_metrics}
+///
+#[extendr]
+fn graph_metrics_obj(tuples: Vec<String>, max_millis: f64) -> Robj {
+    return guard(|| {
+        let g = graph_from_r(tuples);
+
+        let mut budget = Budget::new(SearchLimits::from_r(max_millis, 0.0, 0.0));
+        let m = g.metrics(&mut budget);
+        return metrics_robj(&m, budget.is_truncated());
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod graph_structure;
+    fn is_component_bipartite;
+    fn get_component_structure;
+    fn get_condensation;
+    fn get_topological_order;
+    fn get_reachability_matrix_obj;
+    fn graph_metrics_obj;
+}
@@ -0,0 +1,86 @@
+// The panic guard of the R entry points. Each exported function runs its body in [guard],
+// so an error raised with `r_error!` or any other panic surfaces as an R error with its message.
+
+use std::any::Any;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Once;
+
+use extendr_api::prelude::*;
+
+/// An error of an R entry point, raised by `r_error!` and turned into an R error by [guard]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RError(pub(crate) String);
+
+/// Stops the R entry point with an R error, the arguments are formatted like `format!`
+///
+/// The error unwinds to the [guard] of the entry point, so all values of the entry point
+/// are dropped before the R error is raised.
+macro_rules! r_error {
+    ($($arg:tt)*) => {
+        std::panic::panic_any(crate::guard::RError(format!($($arg)*)))
+    };
+}
+
+/// Returns the message of a panic payload
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(e) = payload.downcast_ref::<RError>() {
+        return e.0.clone();
+    }
+
+    return match payload.downcast_ref::<&str>() {
+        Some(s) => s.to_string(),
+        None => payload.downcast_ref::<String>().cloned().unwrap_or_else(|| "unknown panic".to_string()),
+    };
+}
+
+/// Runs the body of an R entry point and returns its error message if it stopped
+///
+/// Errors raised with `r_error!` keep their message, any other panic is reported as internal error.
+///
+/// # Arguments
+/// * `f` the body of the entry point
+pub(crate) fn catch_error<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    static QUIET_HOOK: Once = Once::new();
+    QUIET_HOOK.call_once(|| {
+        // Errors raised with r_error! are expected, they are reported by R and not printed as panics
+        let default_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if info.payload().downcast_ref::<RError>().is_none() {
+                default_hook(info);
+            }
+        }));
+    });
+
+    return catch_unwind(AssertUnwindSafe(f)).map_err(|payload| match payload.downcast_ref::<RError>() {
+        Some(e) => e.0.clone(),
+        None => format!("Internal error: {}", panic_message(payload.as_ref())),
+    });
+}
+
+/// Runs the body of an R entry point, an error or a panic of the body stops with an R error
+///
+/// # Arguments
+/// * `f` the body of the entry point
+pub(crate) fn guard<T>(f: impl FnOnce() -> T) -> T {
+    match catch_error(f) {
+        Ok(result) => return result,
+        Err(message) => {
+            throw_r_error(message);
+            unreachable!("throw_r_error does not return")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors_keep_their_message() {
+        assert_eq!(catch_error(|| 1), Ok(1));
+        assert_eq!(catch_error(|| -> i32 { r_error!("Code is not correct: {}", "empty word") }),
+                   Err("Code is not correct: empty word".to_string()));
+        assert_eq!(catch_error(|| -> i32 { panic!("index out of bounds") }),
+                   Err("Internal error: index out of bounds".to_string()));
+    }
+}
@@ -0,0 +1,225 @@
+use std::sync::OnceLock;
+
+use extendr_api::prelude::*;
+
+use crate::alphabet::Alphabet;
+use crate::gcat::CircCode;
+use crate::guard::guard;
+use crate::lib_utils::{all_words_of_length, is_circular_words, new_code_from_vec};
+use crate::transform::{canonical_rotation, rotations, words_reverse_complement, words_to_dna};
+
+/// The maximal self-complementary C3 code X of Arquès and Michel (1996) found in genes
+const X0: [&str; 20] = ["AAC", "AAT", "ACC", "ATC", "ATT", "CAG", "CTC", "CTG", "GAA", "GAC",
+    "GAG", "GAT", "GCC", "GGC", "GGT", "GTA", "GTC", "GTT", "TAC", "TTC"];
+
+/// A known code together with its family
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct KnownCode {
+    /// The family, e.g. `"X"` or `"maximal_C3"`
+    pub(crate) family: &'static str,
+    /// The name of the code within its family
+    pub(crate) name: String,
+    /// The words of the code (sorted)
+    pub(crate) words: Vec<String>,
+}
+
+/// How a code relates to a known code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Relation {
+    Equal,
+    /// The code is a proper subset of the known code
+    Subset,
+    /// The code is a proper superset of the known code
+    Superset,
+}
+
+impl std::fmt::Display for Relation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Relation::Equal => write!(f, "equal"),
+            Relation::Subset => write!(f, "subset"),
+            Relation::Superset => write!(f, "superset"),
+        }
+    }
+}
+
+/// Returns the circular permutation `X_k` of the code X, i.e. each word rotated by `k` positions
+///
+/// # Arguments
+/// * `k` the rotation (0, 1 or 2)
+pub(crate) fn code_x(k: usize) -> Vec<String> {
+    let mut words = X0.iter().map(|w| rotations(w)[k % 3].clone()).collect::<Vec<String>>();
+    words.sort();
+    return words;
+}
+
+/// Returns the classic comma-free code {xyz : x < y >= z} of 20 trinucleotides for A < C < G < T
+pub(crate) fn comma_free_classic() -> Vec<String> {
+    return all_words_of_length(Alphabet::dna().letters(), 3).into_iter()
+        .filter(|w| {
+            let c = w.chars().collect::<Vec<char>>();
+            c[0] < c[1] && c[1] >= c[2]
+        })
+        .collect();
+}
+
+/// Returns the 216 maximal self-complementary C3 codes (sorted)
+///
+/// A self-complementary code with 20 trinucleotides contains exactly one word of each of the
+/// 20 conjugacy classes of non-periodic trinucleotides, and the classes are paired by the
+/// reverse complement. The search chooses one word per pair of classes, prunes non-circular
+/// partial codes and checks C3 for complete codes. The codes are computed once.
+pub(crate) fn maximal_c3_codes() -> &'static Vec<Vec<String>> {
+    static CODES: OnceLock<Vec<Vec<String>>> = OnceLock::new();
+    return CODES.get_or_init(|| {
+        let mut classes = all_words_of_length(Alphabet::dna().letters(), 3).into_iter()
+            .filter(|w| rotations(w).iter().filter(|r| *r == w).count() == 1)
+            .map(|w| canonical_rotation(&w))
+            .collect::<Vec<String>>();
+        classes.sort();
+        classes.dedup();
+        let pairs = classes.iter()
+            .filter(|c| **c <= canonical_rotation(&words_reverse_complement(&[(*c).clone()])[0]))
+            .cloned()
+            .collect::<Vec<String>>();
+
+        fn search(pairs: &[String], idx: usize, current: &mut Vec<String>, codes: &mut Vec<Vec<String>>) {
+            if idx == pairs.len() {
+                if CircCode::new_from_vec(current.clone()).is_ok_and(|c| c.is_cn_circular()) {
+                    let mut code = current.clone();
+                    code.sort();
+                    codes.push(code);
+                }
+                return;
+            }
+
+            for w in rotations(&pairs[idx]) {
+                let rc = words_reverse_complement(std::slice::from_ref(&w)).remove(0);
+                current.push(w);
+                current.push(rc);
+                if is_circular_words(current) {
+                    search(pairs, idx + 1, current, codes);
+                }
+                current.pop();
+                current.pop();
+            }
+        }
+
+        let mut codes = Vec::new();
+        search(&pairs, 0, &mut Vec::new(), &mut codes);
+        codes.sort();
+        return codes;
+    });
+}
+
+/// Returns all known codes
+pub(crate) fn known_codes() -> Vec<KnownCode> {
+    let mut codes = (0..3)
+        .map(|k| KnownCode { family: "X", name: format!("X{}", k), words: code_x(k) })
+        .collect::<Vec<KnownCode>>();
+    codes.push(KnownCode { family: "comma_free", name: "xyz_ordered".to_string(), words: comma_free_classic() });
+    codes.extend(maximal_c3_codes().iter().enumerate()
+        .map(|(i, words)| KnownCode { family: "maximal_C3", name: format!("C3_{}", i + 1), words: words.clone() }));
+    return codes;
+}
+
+/// Returns all known codes the set of words is a subset or superset of
+///
+/// # Arguments
+/// * `words` a set of words (DNA or RNA)
+pub(crate) fn classify(words: &[String]) -> Vec<(KnownCode, Relation)> {
+    let mut words = words_to_dna(words);
+    words.sort();
+    words.dedup();
+
+    return known_codes().into_iter()
+        .filter_map(|known| {
+            let subset = words.iter().all(|w| known.words.binary_search(w).is_ok());
+            let superset = known.words.iter().all(|w| words.binary_search(w).is_ok());
+            match (subset, superset) {
+                (true, true) => Some((known, Relation::Equal)),
+                (true, false) => Some((known, Relation::Subset)),
+                (false, true) => Some((known, Relation::Superset)),
+                (false, false) => None,
+            }
+        })
+        .collect();
+}
+
+/// Relates a code to known code families
+///
+/// This function reports which known codes the given code is a subset or superset of.
+/// The known codes are the code X of Arquès and Michel and its circular permutations X1 and X2
+/// (family "X"), the 216 maximal self-complementary C3 codes (family "maximal_C3", named C3_1 to C3_216
+/// in lexicographic order) and the classic comma-free code \{xyz : x < y >= z\} for A < C < G < T
+/// (family "comma_free"). This relates e.g. genome-derived codes to the theory.
+/// DNA and RNA input are both accepted.
+///
+/// @param tuples A gcatbase::gcat.code object
+///
+/// @return A list with the family (family), the name (name) of each matching known code and the
+/// relation (relation) of the code to it: "equal", "subset" or "superset".
+///
+/// @examples
+/// code <- gcatbase::code(c("AAC", "GTT", "GAC"))
+/// classify_code(code)
+///
+/// @seealso \link{is_code_max_self_complementary_c3}
+///
+/// @export
+#[extendr]
+fn classify_code(tuples: Vec<String>) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let matches = classify(&code.get_code());
+        return list!(family = matches.iter().map(|m| m.0.family).collect::<Vec<&str>>(),
+        name = matches.iter().map(|m| m.0.name.clone()).collect::<Vec<String>>(),
+        relation = matches.iter().map(|m| m.1.to_string()).collect::<Vec<String>>());
+    });
+}
+
+/// Returns the words of a known code
+///
+/// @param name A String, the name of the known code, e.g. "X0" or "C3_1"
+///
+/// @return A String vector, the sorted words of the code.
+///
+/// @seealso \link{known_code}
+///
+#[extendr]
+fn known_code_obj(name: String) -> Vec<String> {
+    return guard(|| {
+        match known_codes().into_iter().find(|k| k.name == name) {
+            Some(known) => return known.words,
+            None => {
+                r_error!("Known code is not correct: there is no known code '{}'", name);
+            }
+        }
+    });
+}
+
+/// Returns the families, names and sizes of all known codes
+///
+/// @return A list with the family (family), the name (name) and the number of words (size) of each known code.
+///
+/// @seealso \link{list_known_codes}
+///
+#[extendr]
+fn list_known_codes_obj() -> Robj {
+    return guard(|| {
+        let codes = known_codes();
+        return list!(family = codes.iter().map(|k| k.family).collect::<Vec<&str>>(),
+        name = codes.iter().map(|k| k.name.clone()).collect::<Vec<String>>(),
+        size = codes.iter().map(|k| k.words.len() as i32).collect::<Vec<i32>>());
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod known_codes;
+    fn classify_code;
+    fn known_code_obj;
+    fn list_known_codes_obj;
+}
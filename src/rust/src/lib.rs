@@ -1,12 +1,97 @@
+// Explicit returns are the style of this crate
+#![allow(clippy::needless_return)]
+
 use extendr_api::prelude::*;
 
 extern crate rust_gcatcirc_lib;
 
+#[macro_use]
+mod trace;
+#[macro_use]
+mod guard;
+use guard::guard;
+
+/// Declares a module which is only compiled with a feature
+///
+/// Without the feature an empty module of the same name is registered, so the
+/// `extendr_module!` of the crate is the same for all features.
+macro_rules! feature_module {
+    ($feature:literal, $name:ident) => {
+        #[cfg(feature = $feature)]
+        mod $name;
+        #[cfg(not(feature = $feature))]
+        mod $name {
+            use extendr_api::prelude::*;
+            extendr_module! {
+                mod $name;
+            }
+        }
+    };
+}
+
+pub mod gcat;
+use gcat::CircCode;
 mod lib_utils;
-use lib_utils::new_code_from_vec;
+use lib_utils::{new_code_from_symbols, new_code_from_vec, new_code_from_vec_with_alphabet, parse_code_str, remove_duplicates};
+
+mod word_graph;
+use word_graph::shortest_cycle_within;
+mod random;
+mod limits;
+mod cache;
+mod checkpoint;
+use cache::{cached_bool, cached_int};
+use limits::{Budget, SearchLimits};
 
 mod graph;
-use graph::*;
+
+mod transform;
+use transform::{shift_words, words_to_dna};
+mod sequence;
+mod mixed_length;
+mod genetic_code;
+mod simulation;
+mod monoid;
+mod automaton;
+feature_module!("automata", dfa);
+use automaton::{is_unambiguous, FlowerAutomaton};
+mod search;
+mod combinatorics;
+mod subsets;
+mod comma_free;
+use comma_free::suffix_prefix_overlap;
+mod scan;
+mod known_codes;
+mod explain;
+mod certificate;
+use certificate::ambiguity_certificates_limited;
+mod compare;
+mod annotation;
+mod coding;
+feature_module!("exports", export);
+mod decoder;
+feature_module!("exports", graph_export);
+mod graph_structure;
+mod longest_path;
+mod parallel;
+mod orf;
+feature_module!("fasta", fasta);
+mod classification;
+mod code_set;
+mod completion;
+mod frame_retrieval;
+mod trie;
+mod overlap;
+mod overlap_graph;
+mod alphabet;
+mod symbols;
+#[cfg(feature = "testing")]
+#[doc(hidden)]
+pub mod testing;
+#[cfg(feature = "fuzzing")]
+#[doc(hidden)]
+pub mod fuzzing;
+
 /// Checks whether the set of words is a code or not
 ///
 /// This function returns true if a set of words is by
@@ -24,8 +109,74 @@ use graph::*;
 /// @export
 #[extendr]
 pub fn is_code(tuples: Vec<String>) -> bool {
-    let code = new_code_from_vec(tuples);
-    return code.is_code();
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        return cached_bool(&code.get_code(), "is_code", || code.is_code());
+    });
+}
+
+/// The algorithm used to test whether a set of words is a code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CodeTestBackend {
+    /// Recursion on the representing graph ([rust_gcatcirc_lib::code::CircCode::is_code])
+    Graph,
+    /// Unambiguity test of the flower automaton ([automaton::is_unambiguous])
+    Automaton,
+}
+
+impl CodeTestBackend {
+    /// Parses a backend name (`"graph"` or `"automaton"`)
+    pub(crate) fn from_name(name: &str) -> Result<CodeTestBackend, String> {
+        match name.to_lowercase().as_str() {
+            "graph" => Ok(CodeTestBackend::Graph),
+            "automaton" => Ok(CodeTestBackend::Automaton),
+            _ => Err(format!("Unknown backend '{}', expected 'graph' or 'automaton'", name)),
+        }
+    }
+
+    /// Returns true if the words of the code form a code
+    ///
+    /// # Arguments
+    /// * `code` a code
+    pub(crate) fn is_code(&self, code: &CircCode) -> bool {
+        return match self {
+            CodeTestBackend::Graph => code.is_code(),
+            CodeTestBackend::Automaton => is_unambiguous(&FlowerAutomaton::new(&code.get_code())),
+        };
+    }
+}
+
+/// Checks whether the set of words is a code using a selectable algorithm
+///
+/// This function returns true if a set of words is by
+/// definition a code, see \link{is_code}. Two algorithms are available:\cr
+/// "graph": the recursion on the representing graph used by \link{is_code},\cr
+/// "automaton": the unambiguity test of the flower automaton (product construction).
+/// It scales better for codes with many long words and can be used to cross-check the results.
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param backend A String, either "graph" or "automaton"
+///
+/// @return A Boolean. If true the code is a code
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGG", "AC"))
+/// is_code_using(code, "automaton")
+///
+/// @seealso \link{is_code}
+///
+/// @export
+#[extendr]
+fn is_code_using(tuples: Vec<String>, backend: String) -> bool {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        match CodeTestBackend::from_name(&backend) {
+            Ok(backend) => return backend.is_code(&code),
+            Err(e) => {
+                r_error!("Backend is not correct: {}", e);
+            }
+        }
+    });
 }
 
 /// If a set of words is not a code it returns all ambiguous sequences.
@@ -48,8 +199,40 @@ pub fn is_code(tuples: Vec<String>) -> bool {
 /// @export
 #[extendr]
 fn all_ambiguous_sequences(tuples: Vec<String>) -> Vec<String> {
-    let code = new_code_from_vec(tuples);
-    return code.all_ambiguous_sequences().1;
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        return code.all_ambiguous_sequences().1;
+    });
+}
+
+/// Returns the ambiguous sequences found within limits
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param max_millis A numeric value, the maximal run time in milliseconds (non-positive: unlimited)
+/// @param max_sequences A numeric value, the maximal number of sequences (non-positive: unlimited)
+/// @param max_nodes A numeric value, the maximal number of visited search nodes (non-positive: unlimited)
+/// @param max_megabytes A numeric value, the maximal memory of the sequences in megabytes (non-positive: unlimited)
+///
+/// @return A list with the sequences (sequences), their certificates (certificates), a boolean (truncated)
+/// which is true if a limit stopped the search and the number of visited search nodes (nodes).
+///
+/// @seealso \link{all_ambiguous_sequences_limited}
+///
+#[extendr]
+fn all_ambiguous_sequences_limited_obj(tuples: Vec<String>, max_millis: f64, max_sequences: f64, max_nodes: f64, max_megabytes: f64) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let mut budget = Budget::new(SearchLimits::from_r(max_millis, max_sequences, max_nodes).with_max_megabytes(max_megabytes));
+        let certificates = ambiguity_certificates_limited(&code.get_code(), &mut budget);
+        if let Err(e) = budget.check() {
+            r_error!("Result is too large: ambiguous sequences: {}", e);
+        }
+
+        return list!(sequences = certificates.iter().map(|c| c.sequence.clone()).collect::<Vec<String>>(),
+        certificates = certificates.iter().map(|c| c.to_robj()).collect::<Vec<Robj>>(),
+        truncated = budget.is_truncated(),
+        nodes = budget.nodes() as f64);
+    });
 }
 
 /// Check if a code is circular.
@@ -74,8 +257,10 @@ fn all_ambiguous_sequences(tuples: Vec<String>) -> Vec<String> {
 /// @export
 #[extendr]
 fn is_code_circular(tuples: Vec<String>) -> bool {
-    let code = new_code_from_vec(tuples);
-    return code.is_circular();
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        return cached_bool(&code.get_code(), "is_circular", || code.is_circular());
+    });
 }
 
 /// This function checks if a code is k-circular.
@@ -97,8 +282,41 @@ fn is_code_circular(tuples: Vec<String>) -> bool {
 /// @export
 #[extendr]
 fn get_exact_k_circular(tuples: Vec<String>) -> u32 {
-    let code = new_code_from_vec(tuples);
-    return code.get_exact_k_circular();
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        return cached_int(&code.get_code(), "exact_k_circular", || code.get_exact_k_circular() as i64) as u32;
+    });
+}
+
+/// This function checks if a code is k-circular for a given k.
+///
+/// A code is k-circular if every concatenation of at most k words of \emph{X} written on a circle
+/// has only one partition into words of \emph{X}. The code is k-circular iff the representing graph
+/// has no cycle of at most 2k edges, which is checked by a breadth-first search. Unlike
+/// \link{get_exact_k_circular} only cycles up to this length are searched, so the check is fast for small k
+/// even for large codes.
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param k An integer, the number of words (k >= 1)
+///
+/// @return Boolean value. True if the code is k-circular.
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGA", "CA"))
+/// is_code_k_circular(code, 1)
+///
+/// @seealso \link{get_exact_k_circular}, \link{is_code_circular}
+///
+/// @export
+#[extendr]
+fn is_code_k_circular(tuples: Vec<String>, k: i32) -> bool {
+    return guard(|| {
+        if k < 1 {
+            r_error!("k is not correct: k must be at least 1, got {}", k);
+        }
+        let code = new_code_from_vec(tuples);
+        return shortest_cycle_within(&code.get_code(), 2 * k as usize).is_none();
+    });
 }
 
 /// This function checks if a code is K-Graph circular.
@@ -122,12 +340,16 @@ fn get_exact_k_circular(tuples: Vec<String>) -> u32 {
 /// @export
 #[extendr]
 fn get_k_graph_circular(tuples: Vec<String>) -> i32 {
-    let code = new_code_from_vec(tuples);
-    if let Some(v) = code.get_k_graph_circular() {
-        return v as i32
-    }
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        return cached_int(&code.get_code(), "k_graph_circular", || {
+            if let Some(v) = code.get_k_graph_circular() {
+                return v as i64
+            }
 
-    return -1
+            return -1
+        }) as i32
+    });
 }
 
 /// This function checks if a code is Cn-circular.
@@ -135,6 +357,7 @@ fn get_k_graph_circular(tuples: Vec<String>) -> i32 {
 /// A code is cn circular if all circular permutations of the code (of all tuples) are circular codes again.
 /// In total, this function checks 'x' circular permutations where 'x' is the least
 /// common multiple of all tuple lengths used. This is an extended property of circular codes.
+/// DNA and RNA input are both accepted, U and T are the same letter.
 ///
 /// @param tuples A gcatbase::gcat.code object
 ///
@@ -149,8 +372,10 @@ fn get_k_graph_circular(tuples: Vec<String>) -> i32 {
 /// @export
 #[extendr]
 fn is_code_cn_circular(tuples: Vec<String>) -> bool {
-    let code = new_code_from_vec(tuples);
-    return code.is_cn_circular();
+    return guard(|| {
+        let code = new_code_from_vec(words_to_dna(&tuples));
+        return cached_bool(&code.get_code(), "is_cn_circular", || code.is_cn_circular());
+    });
 }
 
 /// Check if a code is comma free.
@@ -178,8 +403,10 @@ fn is_code_cn_circular(tuples: Vec<String>) -> bool {
 /// @export
 #[extendr]
 fn is_code_comma_free(tuples: Vec<String>) -> bool {
-    let code = new_code_from_vec(tuples);
-    return code.is_comma_free();
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        return code.is_comma_free();
+    });
 }
 
 /// Check if a code is strong comma free.
@@ -187,7 +414,9 @@ fn is_code_comma_free(tuples: Vec<String>) -> bool {
 /// This function checks if a code is strong comma free.
 /// Strong comma free codes are a more restrictive codes from the circular code family.
 /// A strong comma free code \emph{X} is a code in which no nonempty suffix of any word from \emph{X}
-/// is a nonempty prefix of any word from \emph{X}.\cr
+/// is a nonempty prefix of any word from \emph{X}.
+/// The check directly compares all proper suffixes with all proper prefixes, see
+/// \link{strong_comma_free_violation} for the violating words.\cr
 /// This is an extended property of the circular codes. See \link{is_code_comma_free} for more details.\cr
 /// For more info on this subject read:\cr
 /// \link{https://www.ncbi.nlm.nih.gov/pmc/articles/PMC5492142/},\cr
@@ -202,34 +431,162 @@ fn is_code_comma_free(tuples: Vec<String>) -> bool {
 /// code <- gcatbase::code(c("ACG", "CGG", "AC"))
 /// is_code_strong_comma_free(code)
 ///
-/// @seealso \link{is_code_circular}, \link{is_code_comma_free}
+/// @seealso \link{is_code_circular}, \link{is_code_comma_free}, \link{strong_comma_free_violation}
 ///
 /// @export
 #[extendr]
 fn is_code_strong_comma_free(tuples: Vec<String>) -> bool {
-    let code = new_code_from_vec(tuples);
-    return code.is_strong_comma_free();
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        return suffix_prefix_overlap(&code.get_code()).is_none();
+    });
 }
 
-/// Shifts each tuple by `sh` positions
+/// Shifts each tuple by each of the shift values
 ///
-/// Under the concept shift is understood a circular permutation, i.e.
-/// let \emph{X}={123, 332}, then c.shift(2) results in {312, 233}
+/// The code is validated once, each shift value is applied to the original code. A word is shifted by
+/// the shift value modulo its length, negative values shift to the right.
 ///
 /// @param tuples A gcatbase::gcat.code object
-/// @param sh A integer, the shift index, i.e. the number of shifts.
+/// @param sh An integer vector, the shift values
+///
+/// @return A list with the shifted codes, one for each shift value.
+///
+/// @seealso \link{circular_shift}
+///
+#[extendr]
+fn circular_shift_obj(tuples: Vec<String>, sh: Vec<i32>) -> Vec<Robj> {
+    return guard(|| {
+        let code = new_code_from_vec(tuples).get_code();
+        return sh.iter().map(|s| shift_words(&code, *s).iter().collect_robj()).collect();
+    });
+}
+
+/// Creates a code over a declared alphabet
+///
+/// This function checks that every word of the code only uses letters
+/// of the given alphabet. Unlike the other functions of this package,
+/// the alphabet is not inferred from the words, so typos like \emph{ACX}
+/// are rejected with a precise error instead of silently extending the alphabet.
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param alphabet A String vector of single letters, e.g. c("A", "C", "G", "T"), or the name of a preset, see \link{alphabet_preset}
+///
+/// @return A String vector with all words of the code.
 ///
-/// @return Boolean value. True if the code is circular.
 /// @examples
 /// code <- gcatbase::code(c("ACG", "CGG", "AC"))
-/// circular_shift(code, 2)
+/// code_over_alphabet(code, c("A", "C", "G", "T"))
+///
+/// @export
+#[extendr]
+fn code_over_alphabet(tuples: Vec<String>, alphabet: Vec<String>) -> Vec<String> {
+    return guard(|| {
+        let code = new_code_from_vec_with_alphabet(tuples, &alphabet);
+        return code.get_code()
+    });
+}
+
+/// Creates a code over declared symbols
+///
+/// This function supports alphabets whose symbols consist of several characters, e.g. codons or
+/// labeled bases like "m6A". Each word is split into the declared symbols (see \link{tokenize_words})
+/// and each multi-character symbol is replaced by a single letter. Thus, the encoded code can be
+/// analysed with all functions of this package: tuple lengths, the graph associated to the code and
+/// circular shifts count symbols instead of characters. The results can be made readable
+/// again with \link{decode_symbols}.
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param symbols A String vector, the declared symbols, e.g. c("A", "C", "G", "U", "m6A")
+///
+/// @return A String vector with all encoded words of the code.
+///
+/// @examples
+/// symbols <- c("A", "C", "G", "U", "m6A")
+/// code <- code_over_symbols(c("m6ACG", "CGm6A"), symbols)
+/// decode_symbols(unlist(get_longest_paths(code)), symbols)
 ///
 /// @export
 #[extendr]
-fn circular_shift(tuples: Vec<String>, sh: i32) -> Vec<String> {
-    let mut code = new_code_from_vec(tuples);
-    code.shift(sh);
-    return code.get_code()
+fn code_over_symbols(tuples: Vec<String>, symbols: Vec<String>) -> Vec<String> {
+    return guard(|| {
+        let code = new_code_from_symbols(tuples, &symbols);
+        return code.get_code()
+    });
+}
+
+/// Creates a code from a delimiter separated string
+///
+/// This function parses strings like "ACG, CGG, AC" or "ACG|CGG|AC".
+/// Words can be separated by commas, semicolons, vertical bars or whitespaces and the
+/// list can be enclosed in braces, e.g. "\{ACG, CGG\}". This is useful to read codes from
+/// configuration files or command line arguments.
+///
+/// @param s A String, the delimiter separated words
+///
+/// @return A String vector with all words of the code.
+///
+/// @examples
+/// code_from_str("ACG, CGG, AC")
+///
+/// @export
+#[extendr]
+fn code_from_str(s: String) -> Vec<String> {
+    return guard(|| {
+        match parse_code_str(&s) {
+            Ok(words) => return new_code_from_vec(words).get_code(),
+            Err(e) => {
+                r_error!("Code is not correct: {}", e);
+            }
+        }
+    });
+}
+
+/// Returns all duplicated words of a set of words
+///
+/// All functions of this package remove duplicated words before a code is analysed,
+/// since duplicates would distort graph multiplicities and statistics.
+/// This function reports which words were duplicated. If \emph{strict}
+/// is true, the function stops with an error if any word is duplicated.
+///
+/// @param tuples A String vector, a set of words
+/// @param strict A boolean, if true duplicated words are an error
+///
+/// @return A String vector with all duplicated words (each listed once).
+///
+/// @examples
+/// duplicated_words(c("ACG", "CGG", "ACG", "AC"), FALSE)
+///
+/// @export
+#[extendr]
+fn duplicated_words(tuples: Vec<String>, strict: bool) -> Vec<String> {
+    return guard(|| {
+        let (_, duplicates) = remove_duplicates(tuples);
+        if strict && !duplicates.is_empty() {
+            r_error!("Code is not correct: duplicated words {}", duplicates.join(", "));
+        }
+
+        return duplicates;
+    });
+}
+
+/// Enables the trace log
+///
+/// If the package is compiled with the Rust feature \emph{trace}, all graph constructions,
+/// cycle searches and enumerations are logged to stderr together with the number of explored states.
+/// This helps to diagnose performance issues.
+///
+/// @return A Boolean. True if the log was enabled, false if the feature is not compiled in or the log is already enabled.
+///
+/// @examples
+/// enable_trace_log()
+///
+/// @export
+#[extendr]
+fn enable_trace_log() -> bool {
+    return guard(|| {
+        return trace::init_trace_log();
+    });
 }
 
 // Macro to generate exports.
@@ -238,13 +595,83 @@ fn circular_shift(tuples: Vec<String>, sh: i32) -> Vec<String> {
 extendr_module! {
     mod gcatcirc; // like R package name
     fn all_ambiguous_sequences;
+    fn all_ambiguous_sequences_limited_obj;
     fn is_code;
-    fn circular_shift;
+    fn is_code_using;
+    fn circular_shift_obj;
+    fn code_over_alphabet;
+    fn code_over_symbols;
+    fn code_from_str;
+    fn duplicated_words;
+    fn enable_trace_log;
     fn is_code_circular;
     fn is_code_comma_free;
     fn is_code_strong_comma_free;
     fn is_code_cn_circular;
     fn get_exact_k_circular;
+    fn is_code_k_circular;
     fn get_k_graph_circular;
     use graph;
+    use transform;
+    use sequence;
+    use mixed_length;
+    use genetic_code;
+    use simulation;
+    use monoid;
+    use dfa;
+    use search;
+    use combinatorics;
+    use subsets;
+    use comma_free;
+    use scan;
+    use cache;
+    use known_codes;
+    use explain;
+    use certificate;
+    use compare;
+    use annotation;
+    use coding;
+    use export;
+    use decoder;
+    use graph_export;
+    use graph_structure;
+    use longest_path;
+    use parallel;
+    use orf;
+    use fasta;
+    use classification;
+    use code_set;
+    use completion;
+    use frame_retrieval;
+    use trie;
+    use overlap;
+    use overlap_graph;
+    use alphabet;
+    use symbols;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(w: &[&str]) -> Vec<String> {
+        return w.iter().map(|w| w.to_string()).collect();
+    }
+
+    #[test]
+    fn code_test_backends_agree_on_the_example_codes() {
+        let mut examples = vec![
+            words(&["ACG", "CGG", "AC"]),
+            words(&["ACG", "CGA", "CA"]),
+            words(&["ACG", "CGU"]),
+            words(&["A", "AC", "CA"]),
+            words(&["AB", "BA", "A"]),
+            known_codes::comma_free_classic(),
+        ];
+        examples.extend((0..3).map(known_codes::code_x));
+        for example in examples {
+            let code = CircCode::new_from_vec(example.clone()).unwrap();
+            assert_eq!(CodeTestBackend::Automaton.is_code(&code), CodeTestBackend::Graph.is_code(&code), "{:?}", example);
+        }
+    }
 }
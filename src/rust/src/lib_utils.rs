@@ -1,19 +1,203 @@
+use std::collections::BTreeMap;
+
 use extendr_api::prelude::*;
-use rust_gcatcirc_lib::code;
 
-/// Returns a new [rust_gcatcirc_lib::code::CircCode]
+use crate::alphabet::Alphabet;
+use crate::gcat::{guarded, new_code, CircCode};
+use crate::symbols::Symbols;
+
+/// Returns a new [CircCode]
 ///
 /// Establishes all used tuple lengths and stores them into `tuple_length`. It also collects the `alphabet`.
+/// All duplicated words are removed first (see [remove_duplicates]).
 ///
 /// # Arguments
 /// * `code` a set of words
-pub(crate) fn new_code_from_vec(code: Vec<String>) -> code::CircCode {
-    match code::CircCode::new_from_vec(code) {
+pub(crate) fn new_code_from_vec(code: Vec<String>) -> CircCode {
+    let (code, _) = remove_duplicates(code);
+    match new_code(code) {
         Ok(code) => return code,
         Err(e) => {
-            rprintln!("Code is not correct: {}", e);
-            R!(stop("Code is not correct")).unwrap();
-            return code::CircCode::default()
+            r_error!("Code is not correct: {}", e);
         },
     }
 }
+
+/// Returns a new [CircCode] over a declared alphabet
+///
+/// Unlike [new_code_from_vec] the alphabet is not inferred from the words. Any word
+/// containing a letter outside of `alphabet` stops the execution with a precise error.
+///
+/// # Arguments
+/// * `code` a set of words
+/// * `alphabet` the allowed letters or the name of a preset, see [Alphabet::from_r]
+pub(crate) fn new_code_from_vec_with_alphabet(code: Vec<String>, alphabet: &[String]) -> CircCode {
+    if let Err(e) = Alphabet::from_r(alphabet).and_then(|a| a.validate(&code)) {
+        r_error!("Code is not correct: {}", e);
+    }
+
+    return new_code_from_vec(code);
+}
+
+/// Returns a new [CircCode] over declared symbols
+///
+/// Each word is split into the symbols (see [Symbols::tokenize]) and each multi-character symbol is
+/// replaced by a single letter, so the words of the code are the encoded words (see [Symbols::encode]).
+/// A word which can not be split unambiguously stops the execution with a precise error.
+///
+/// # Arguments
+/// * `code` a set of words
+/// * `symbols` the declared symbols, e.g. `["A", "C", "G", "U", "m6A"]`
+pub(crate) fn new_code_from_symbols(code: Vec<String>, symbols: &[String]) -> CircCode {
+    match Symbols::new(symbols).and_then(|s| s.encode_all(&code)) {
+        Ok(words) => return new_code_from_vec(words),
+        Err(e) => {
+            r_error!("Code is not correct: {}", e);
+        }
+    }
+}
+
+/// Returns the index of an i-component passed from R, a negative index stops with an R error
+///
+/// # Arguments
+/// * `i` the component index
+pub(crate) fn component_index_from_r(i: i32) -> u32 {
+    if i < 0 {
+        r_error!("Component index is not correct: it must not be negative, got {}", i);
+    }
+
+    return i as u32;
+}
+
+/// The entries of a named list passed from R, e.g. the options of an analysis
+///
+/// Entry points with many parameters take related options as a single list built by the R wrapper
+/// (e.g. the style of `graph_style`), so the options can be parsed into a struct and passed on together.
+pub(crate) struct NamedList {
+    entries: BTreeMap<String, Robj>,
+}
+
+impl NamedList {
+    /// Returns the entries of a list, an error if the object is not a named list
+    ///
+    /// # Arguments
+    /// * `list` the list passed from R
+    /// * `what` the name of the list in error messages, e.g. `"style"`
+    pub(crate) fn new(list: &Robj, what: &str) -> Result<NamedList, String> {
+        return match list.as_named_list_iter() {
+            Some(entries) => Ok(NamedList { entries: entries.map(|(k, v)| (k.to_string(), v)).collect() }),
+            None => Err(format!("The {} is not a named list", what)),
+        };
+    }
+
+    /// Returns an entry
+    fn get(&self, key: &str) -> Result<&Robj, String> {
+        return self.entries.get(key).ok_or(format!("The entry '{}' is missing", key));
+    }
+
+    /// Returns a character entry
+    #[cfg_attr(not(feature = "exports"), allow(dead_code))]
+    pub(crate) fn string(&self, key: &str) -> Result<String, String> {
+        return self.get(key)?.as_str().map(|s| s.to_string()).ok_or(format!("The entry '{}' is not a String", key));
+    }
+
+    /// Returns a character vector entry
+    #[cfg_attr(not(feature = "exports"), allow(dead_code))]
+    pub(crate) fn strings(&self, key: &str) -> Result<Vec<String>, String> {
+        return self.get(key)?.as_string_vector().ok_or(format!("The entry '{}' is not a String vector", key));
+    }
+
+    /// Returns a numeric entry (integer or double)
+    pub(crate) fn number(&self, key: &str) -> Result<f64, String> {
+        let value = self.get(key)?;
+        return value.as_real().or_else(|| value.as_integer().map(|i| i as f64))
+            .ok_or(format!("The entry '{}' is not a number", key));
+    }
+
+    /// Returns a logical entry
+    pub(crate) fn flag(&self, key: &str) -> Result<bool, String> {
+        return self.get(key)?.as_logical().map(|b| b.to_bool()).ok_or(format!("The entry '{}' is not a boolean", key));
+    }
+}
+
+/// Parses a delimiter separated list of words, e.g. `"ACG, CGG, AC"` or `"ACG|CGG|AC"`
+///
+/// Words can be separated by `,`, `;`, `|` or whitespace. The list can be enclosed in braces
+/// (`"{ACG, CGG}"`). Empty entries are ignored.
+///
+/// # Arguments
+/// * `s` the string to parse
+pub(crate) fn parse_code_str(s: &str) -> Result<Vec<String>, String> {
+    let s = s.trim();
+    let s = s.strip_prefix('{').map_or(s, |r| r.strip_suffix('}').unwrap_or(r));
+    let words = s.split(|c: char| c == ',' || c == ';' || c == '|' || c.is_whitespace())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_string())
+        .collect::<Vec<String>>();
+
+    if words.is_empty() {
+        return Err(format!("'{}' contains no words", s));
+    }
+
+    return Ok(words);
+}
+
+/// Removes all duplicated words
+///
+/// Unlike `Vec::dedup` duplicates need not be adjacent. The first occurrence of each word is kept,
+/// so the order of the words is preserved. Returns the distinct words and the duplicated words (each listed once).
+///
+/// # Arguments
+/// * `code` a set of words
+pub(crate) fn remove_duplicates(code: Vec<String>) -> (Vec<String>, Vec<String>) {
+    let mut seen = std::collections::HashSet::new();
+    let mut distinct = Vec::with_capacity(code.len());
+    let mut duplicates = Vec::new();
+    for word in code {
+        if seen.contains(&word) {
+            if !duplicates.contains(&word) {
+                duplicates.push(word);
+            }
+        } else {
+            seen.insert(word.clone());
+            distinct.push(word);
+        }
+    }
+
+    return (distinct, duplicates);
+}
+
+/// Returns all letters used by the words (sorted)
+///
+/// # Arguments
+/// * `code` a set of words
+pub(crate) fn alphabet_of(code: &[String]) -> Vec<char> {
+    let mut letters = code.iter().flat_map(|w| w.chars()).collect::<Vec<char>>();
+    letters.sort_unstable();
+    letters.dedup();
+    return letters;
+}
+
+/// Returns all words of length `l` over the alphabet (in lexicographic order of the alphabet)
+///
+/// # Arguments
+/// * `alphabet` a set of letters
+/// * `l` the word length
+pub(crate) fn all_words_of_length(alphabet: &[char], l: usize) -> Vec<String> {
+    let mut words = vec![String::new()];
+    for _ in 0..l {
+        words = words.iter().flat_map(|w| alphabet.iter().map(move |c| format!("{}{}", w, c))).collect();
+    }
+
+    return words;
+}
+
+/// Returns true if the words form a circular code
+///
+/// Unlike [new_code_from_vec] an incorrect code does not stop the execution but results in `false`.
+///
+/// # Arguments
+/// * `code` a set of words
+pub(crate) fn is_circular_words(code: &[String]) -> bool {
+    return guarded(|| CircCode::new_from_vec(code.to_vec()).is_ok_and(|c| c.is_circular())).unwrap_or(false);
+}
@@ -0,0 +1,159 @@
+use std::time::{Duration, Instant};
+
+/// Limits for exhaustive searches
+///
+/// A value of `None` means unlimited.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct SearchLimits {
+    /// The maximal run time
+    pub(crate) max_time: Option<Duration>,
+    /// The maximal number of results (e.g. paths)
+    pub(crate) max_results: Option<usize>,
+    /// The maximal number of visited search nodes
+    pub(crate) max_nodes: Option<usize>,
+    /// The maximal estimated memory of the results in bytes, exceeding it is an error ([LimitError::ResultTooLarge])
+    pub(crate) max_bytes: Option<usize>,
+}
+
+impl SearchLimits {
+    /// Returns limits from values passed from R, non-positive values mean unlimited
+    pub(crate) fn from_r(max_millis: f64, max_results: f64, max_nodes: f64) -> SearchLimits {
+        return SearchLimits {
+            max_time: if max_millis > 0.0 { Some(Duration::from_millis(max_millis as u64)) } else { None },
+            max_results: if max_results > 0.0 { Some(max_results as usize) } else { None },
+            max_nodes: if max_nodes > 0.0 { Some(max_nodes as usize) } else { None },
+            max_bytes: None,
+        };
+    }
+
+    /// Returns the limits with a memory budget for the results passed from R, non-positive values mean unlimited
+    pub(crate) fn with_max_megabytes(self, max_megabytes: f64) -> SearchLimits {
+        return SearchLimits {
+            max_bytes: if max_megabytes > 0.0 { Some((max_megabytes * 1024.0 * 1024.0) as usize) } else { None },
+            ..self
+        };
+    }
+}
+
+/// A search which was aborted because a limit was exceeded and the partial result is useless
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum LimitError {
+    /// The results exceed the memory budget [SearchLimits::max_bytes]
+    ResultTooLarge { limit: usize },
+}
+
+impl std::fmt::Display for LimitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LimitError::ResultTooLarge { limit } => write!(f, "The results exceed the memory budget of {} bytes", limit),
+        }
+    }
+}
+
+/// Bounds of the depth-first path searches
+///
+/// In contrast to [SearchLimits], which stop a search after a certain amount of work,
+/// these options restrict the searched paths, so a search can end as soon as the bound of interest is exceeded.
+/// A value of `None` means unbounded.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct PathOptions {
+    /// Only paths with at most this number of vertices are searched
+    pub(crate) max_depth: Option<usize>,
+    /// At most this number of successors of each vertex are followed (the result is partial if successors are skipped)
+    pub(crate) max_branches: Option<usize>,
+    /// The search stops at the first cycle with more than this number of vertices
+    pub(crate) stop_above_length: Option<usize>,
+}
+
+impl PathOptions {
+    /// Returns options from values passed from R, non-positive values mean unbounded
+    pub(crate) fn from_r(max_depth: i32, max_branches: i32, stop_above_length: i32) -> PathOptions {
+        return PathOptions {
+            max_depth: if max_depth > 0 { Some(max_depth as usize) } else { None },
+            max_branches: if max_branches > 0 { Some(max_branches as usize) } else { None },
+            stop_above_length: if stop_above_length > 0 { Some(stop_above_length as usize) } else { None },
+        };
+    }
+}
+
+/// Tracks the work of a search against its [SearchLimits]
+#[derive(Debug, Clone)]
+pub(crate) struct Budget {
+    limits: SearchLimits,
+    start: Instant,
+    nodes: usize,
+    results: usize,
+    /// The estimated memory of the results in bytes
+    bytes: usize,
+    truncated: bool,
+    /// A part of the search space was skipped, but the search goes on
+    partial: bool,
+}
+
+impl Budget {
+    /// Starts a new budget
+    pub(crate) fn new(limits: SearchLimits) -> Budget {
+        return Budget { limits, start: Instant::now(), nodes: 0, results: 0, bytes: 0, truncated: false, partial: false };
+    }
+
+    /// Counts a visited search node, returns false if the search has to stop
+    pub(crate) fn visit(&mut self) -> bool {
+        self.nodes += 1;
+        if self.limits.max_nodes.is_some_and(|m| self.nodes > m)
+            || (self.nodes.is_multiple_of(1024) && self.limits.max_time.is_some_and(|m| self.start.elapsed() > m)) {
+            self.truncated = true;
+        }
+
+        return !self.truncated;
+    }
+
+    /// Counts a new result, returns false if the limit of results is reached and the result has to be dropped
+    pub(crate) fn found(&mut self) -> bool {
+        if self.limits.max_results.is_some_and(|m| self.results >= m) {
+            self.truncated = true;
+            return false;
+        }
+
+        self.results += 1;
+        return true;
+    }
+
+    /// Counts a new result with an estimated memory of `bytes`, returns false if the result has to be dropped
+    ///
+    /// If the results exceed the memory budget the search has to stop, see [Budget::check].
+    pub(crate) fn found_sized(&mut self, bytes: usize) -> bool {
+        if !self.found() {
+            return false;
+        }
+        self.bytes += bytes;
+        if self.limits.max_bytes.is_some_and(|m| self.bytes > m) {
+            self.truncated = true;
+            return false;
+        }
+
+        return true;
+    }
+
+    /// Returns an error if the search was aborted because the results exceed the memory budget
+    pub(crate) fn check(&self) -> Result<(), LimitError> {
+        match self.limits.max_bytes {
+            Some(limit) if self.bytes > limit => Err(LimitError::ResultTooLarge { limit }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Marks the result as partial if a search skipped a part of the search space but goes on
+    pub(crate) fn mark_partial(&mut self) {
+        self.partial = true;
+    }
+
+    /// True if the search was stopped by a limit, i.e. the result is partial
+    pub(crate) fn is_truncated(&self) -> bool {
+        return self.truncated || self.partial;
+    }
+
+    /// Returns the number of visited search nodes
+    pub(crate) fn nodes(&self) -> usize {
+        return self.nodes;
+    }
+}
@@ -0,0 +1,243 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::{Mutex, OnceLock};
+
+use extendr_api::prelude::*;
+
+use crate::guard::guard;
+use crate::lib_utils::new_code_from_vec;
+use crate::word_graph::word_edges;
+
+/// The state of a vertex during the memoized search
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Memo {
+    /// The vertex is on the stack of the current search
+    Visiting,
+    /// The number of edges of a longest path starting in the vertex, `None` if a cycle is reachable
+    Done(Option<usize>),
+}
+
+/// The representing graph G(X) with memoized longest-path lengths
+///
+/// The length of a longest path starting in a vertex only depends on the part of the graph reachable from the vertex.
+/// If a word is added or removed only the memoized values of the vertices which reach one of its edges are discarded,
+/// so repeated queries after small edits of a code are cheap.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LongestPathMemo {
+    words: BTreeSet<String>,
+    /// The number of words inducing each edge
+    edges: BTreeMap<(String, String), usize>,
+    successors: BTreeMap<String, BTreeSet<String>>,
+    predecessors: BTreeMap<String, BTreeSet<String>>,
+    memo: HashMap<String, Memo>,
+}
+
+impl LongestPathMemo {
+    /// Returns the memo of the graph G(X)
+    ///
+    /// # Arguments
+    /// * `words` a set of words
+    pub(crate) fn new(words: &[String]) -> LongestPathMemo {
+        let mut memo = LongestPathMemo::default();
+        for word in words {
+            memo.insert_word(word);
+        }
+
+        return memo;
+    }
+
+    /// Returns the words of the code
+    pub(crate) fn words(&self) -> &BTreeSet<String> {
+        return &self.words;
+    }
+
+    /// Adds a word and its edges to the graph
+    pub(crate) fn insert_word(&mut self, word: &str) {
+        if !self.words.insert(word.to_string()) {
+            return;
+        }
+        for e in word_edges(&[word.to_string()]) {
+            self.invalidate(&e.from);
+            let count = self.edges.entry((e.from.clone(), e.to.clone())).or_insert(0);
+            *count += 1;
+            if *count == 1 {
+                self.successors.entry(e.to.clone()).or_default();
+                self.predecessors.entry(e.from.clone()).or_default();
+                self.successors.entry(e.from.clone()).or_default().insert(e.to.clone());
+                self.predecessors.entry(e.to).or_default().insert(e.from);
+            }
+        }
+    }
+
+    /// Removes a word and its edges from the graph
+    pub(crate) fn remove_word(&mut self, word: &str) {
+        if !self.words.remove(word) {
+            return;
+        }
+        for e in word_edges(&[word.to_string()]) {
+            self.invalidate(&e.from);
+            let key = (e.from.clone(), e.to.clone());
+            let count = self.edges.get(&key).copied().unwrap_or(0);
+            if count > 1 {
+                self.edges.insert(key, count - 1);
+                continue;
+            }
+            self.edges.remove(&key);
+            if let Some(s) = self.successors.get_mut(&e.from) {
+                s.remove(&e.to);
+            }
+            if let Some(p) = self.predecessors.get_mut(&e.to) {
+                p.remove(&e.from);
+            }
+            for v in [&e.from, &e.to] {
+                if self.successors.get(v).is_some_and(|s| s.is_empty()) && self.predecessors.get(v).is_some_and(|p| p.is_empty()) {
+                    self.successors.remove(v);
+                    self.predecessors.remove(v);
+                    self.memo.remove(v);
+                }
+            }
+        }
+    }
+
+    /// Discards the memoized values of `v` and of all vertices which reach `v`
+    fn invalidate(&mut self, v: &str) {
+        let mut stack = vec![v.to_string()];
+        while let Some(u) = stack.pop() {
+            if self.memo.remove(&u).is_some() {
+                if let Some(p) = self.predecessors.get(&u) {
+                    stack.extend(p.iter().cloned());
+                }
+            }
+        }
+    }
+
+    /// Returns the number of edges of a longest path starting in `v`
+    ///
+    /// Returns `None` if a cycle is reachable from `v` (the longest paths are not defined).
+    pub(crate) fn longest_from(&mut self, v: &str) -> Option<usize> {
+        if let Some(Memo::Done(length)) = self.memo.get(v) {
+            return *length;
+        }
+
+        // Iterative depth-first search, a vertex is finished when all successors are finished
+        let mut stack = vec![(v.to_string(), false)];
+        while let Some((u, expanded)) = stack.pop() {
+            if expanded {
+                let length = self.successors.get(&u).into_iter().flatten()
+                    .map(|s| match self.memo.get(s) {
+                        Some(Memo::Done(l)) => l.map(|l| l + 1),
+                        _ => None,
+                    })
+                    .try_fold(0, |max: usize, l| l.map(|l| max.max(l)));
+                self.memo.insert(u, Memo::Done(length));
+                continue;
+            }
+            match self.memo.get(&u) {
+                Some(Memo::Done(_)) => continue,
+                Some(Memo::Visiting) => {
+                    // A back edge closes a cycle
+                    self.memo.insert(u, Memo::Done(None));
+                    continue;
+                }
+                None => {}
+            }
+            self.memo.insert(u.clone(), Memo::Visiting);
+            stack.push((u.clone(), true));
+            for s in self.successors.get(&u).into_iter().flatten() {
+                if !matches!(self.memo.get(s), Some(Memo::Done(_))) {
+                    stack.push((s.clone(), false));
+                }
+            }
+        }
+
+        return match self.memo.get(v) {
+            Some(Memo::Done(length)) => *length,
+            _ => None,
+        };
+    }
+
+    /// Returns the number of edges of the longest paths of G(X)
+    ///
+    /// Returns `None` if G(X) contains a cycle or has no edges, like [crate::word_graph::longest_paths_limited].
+    pub(crate) fn length(&mut self) -> Option<usize> {
+        let vertices = self.successors.keys().cloned().collect::<Vec<String>>();
+        let mut max = 0;
+        for v in vertices {
+            max = max.max(self.longest_from(&v)?);
+        }
+
+        return if max == 0 { None } else { Some(max) };
+    }
+
+    /// Returns true if G(X) contains no cycle, i.e. the code is circular
+    ///
+    /// Unlike [LongestPathMemo::length] a graph without edges is acyclic.
+    pub(crate) fn is_acyclic(&mut self) -> bool {
+        let vertices = self.successors.keys().cloned().collect::<Vec<String>>();
+        return vertices.iter().all(|v| self.longest_from(v).is_some());
+    }
+}
+
+/// The memo of the last queried code
+fn last_memo() -> &'static Mutex<LongestPathMemo> {
+    static MEMO: OnceLock<Mutex<LongestPathMemo>> = OnceLock::new();
+    return MEMO.get_or_init(|| Mutex::new(LongestPathMemo::default()));
+}
+
+/// Returns the length of the longest paths of G(X) and reuses the memo of the last queried code
+///
+/// The memo is updated by the words added and removed since the last query.
+///
+/// # Arguments
+/// * `words` a set of words
+pub(crate) fn longest_path_length(words: &[String]) -> Option<usize> {
+    let words = words.iter().cloned().collect::<BTreeSet<String>>();
+    let mut memo = match last_memo().lock() {
+        Ok(memo) => memo,
+        Err(_) => return LongestPathMemo::new(&words.into_iter().collect::<Vec<String>>()).length(),
+    };
+
+    let removed = memo.words().difference(&words).cloned().collect::<Vec<String>>();
+    let added = words.difference(memo.words()).cloned().collect::<Vec<String>>();
+    for word in &removed {
+        memo.remove_word(word);
+    }
+    for word in &added {
+        memo.insert_word(word);
+    }
+
+    return memo.length();
+}
+
+/// Returns the length of the longest paths of the graph associated to a code
+///
+/// The length (number of edges) of the longest paths is computed without enumerating the paths
+/// (see \link{get_longest_paths}). The lengths of the longest paths starting in each vertex are memoized,
+/// so repeated queries after small edits of a code (e.g. adding or removing a word) only recompute
+/// the vertices which reach the changed edges.
+///
+/// @param tuples A gcatbase::gcat.code object
+///
+/// @return An integer, the number of edges of the longest paths. -1 if the graph contains a cycle or has no edges.
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGG", "AC"))
+/// get_longest_path_length(code)
+///
+/// @seealso \link{get_longest_paths}
+///
+/// @export
+#[extendr]
+fn get_longest_path_length(tuples: Vec<String>) -> i32 {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        return longest_path_length(&code.get_code()).map_or(-1, |l| l as i32);
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod longest_path;
+    fn get_longest_path_length;
+}
@@ -0,0 +1,133 @@
+use extendr_api::prelude::*;
+
+use crate::guard::guard;
+use crate::lib_utils::new_code_from_vec;
+use crate::word_graph::{word_edges, WordEdge};
+
+/// Returns all words of length `l`
+///
+/// # Arguments
+/// * `words` a set of words
+/// * `l` the word length
+pub(crate) fn words_of_length(words: &[String], l: usize) -> Vec<String> {
+    return words.iter().filter(|w| w.chars().count() == l).cloned().collect();
+}
+
+/// Returns all distinct word lengths (sorted)
+///
+/// # Arguments
+/// * `words` a set of words
+pub(crate) fn word_lengths(words: &[String]) -> Vec<usize> {
+    let mut lengths = words.iter().map(|w| w.chars().count()).collect::<Vec<usize>>();
+    lengths.sort_unstable();
+    lengths.dedup();
+    return lengths;
+}
+
+/// Returns all pairs of consecutive edges in G(X) which originate from words of different lengths
+///
+/// A pair `(e1, e2)` with `e1 = [u, v]` and `e2 = [v, w]` is a junction where a path
+/// can switch between words of different lengths.
+///
+/// # Arguments
+/// * `words` a set of words
+pub(crate) fn cross_length_junctions(words: &[String]) -> Vec<(WordEdge, WordEdge)> {
+    let edges = word_edges(words);
+    let mut junctions = Vec::new();
+    for e1 in &edges {
+        for e2 in edges.iter().filter(|e2| e2.from == e1.to) {
+            if e1.word.chars().count() != e2.word.chars().count() {
+                junctions.push((e1.clone(), e2.clone()));
+            }
+        }
+    }
+
+    return junctions;
+}
+
+/// Returns all words of a code with a given length
+///
+/// Mixed codes contain words of different lengths, e.g. dinucleotides and trinucleotides.
+/// This function returns the sub code of all words of length \emph{l}.
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param l An integer, the word length
+///
+/// @return A String vector, the sub code of all words of length l.
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGG", "AC"))
+/// sub_code_of_length(code, 3)
+///
+/// @seealso \link{cross_length_junctions_of_code}
+///
+/// @export
+#[extendr]
+fn sub_code_of_length(tuples: Vec<String>, l: i32) -> Vec<String> {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        return words_of_length(&code.get_code(), l.max(0) as usize);
+    });
+}
+
+/// Returns where words of different lengths interact in the representing graph
+///
+/// In the graph associated to a mixed code, a path can continue with an edge
+/// of a word of another length whenever both edges share a vertex.
+/// This function lists all such pairs of consecutive edges \emph{[u, v]} and \emph{[v, w]}
+/// which originate from words of different lengths.
+///
+/// @param tuples A gcatbase::gcat.code object
+///
+/// @return A list with the shared vertices (vertex), the incoming words (word_in),
+/// the outgoing words (word_out) and their lengths (length_in, length_out). Entry i of each vector belongs to the i-th junction.
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGG", "AC"))
+/// cross_length_junctions_of_code(code)
+///
+/// @seealso \link{sub_code_of_length}
+///
+/// @export
+#[extendr]
+fn cross_length_junctions_of_code(tuples: Vec<String>) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let junctions = cross_length_junctions(&code.get_code());
+
+        return list!(vertex = junctions.iter().map(|(e1, _)| e1.to.clone()).collect::<Vec<String>>(),
+        word_in = junctions.iter().map(|(e1, _)| e1.word.clone()).collect::<Vec<String>>(),
+        word_out = junctions.iter().map(|(_, e2)| e2.word.clone()).collect::<Vec<String>>(),
+        length_in = junctions.iter().map(|(e1, _)| e1.word.chars().count() as i32).collect::<Vec<i32>>(),
+        length_out = junctions.iter().map(|(_, e2)| e2.word.chars().count() as i32).collect::<Vec<i32>>());
+    });
+}
+
+/// Returns all word lengths used by a code
+///
+/// @param tuples A gcatbase::gcat.code object
+///
+/// @return An integer vector with all distinct word lengths (sorted).
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGG", "AC"))
+/// word_lengths_of_code(code)
+///
+/// @export
+#[extendr]
+fn word_lengths_of_code(tuples: Vec<String>) -> Vec<i32> {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        return word_lengths(&code.get_code()).into_iter().map(|l| l as i32).collect();
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod mixed_length;
+    fn sub_code_of_length;
+    fn cross_length_junctions_of_code;
+    fn word_lengths_of_code;
+}
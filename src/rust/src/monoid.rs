@@ -0,0 +1,214 @@
+use extendr_api::prelude::*;
+
+use crate::guard::guard;
+use crate::lib_utils::new_code_from_vec;
+
+/// Returns the number of factorizations of length `0..=n` over the words (generating series coefficients)
+///
+/// The i-th entry is the number of sequences of words whose concatenation has length `i`.
+/// If the words form a code, each word of X* has a unique factorization, so the i-th entry
+/// is the number of words of length `i` in X*. The values are floating point numbers since they grow exponentially.
+///
+/// # Arguments
+/// * `words` a set of words
+/// * `n` the maximal length
+pub(crate) fn growth_series(words: &[String], n: usize) -> Vec<f64> {
+    let lengths = words.iter().map(|w| w.chars().count()).filter(|l| *l > 0).collect::<Vec<usize>>();
+    let mut counts = vec![0.0; n + 1];
+    counts[0] = 1.0;
+    for i in 1..=n {
+        counts[i] = lengths.iter().filter(|l| **l <= i).map(|l| counts[i - l]).sum();
+    }
+
+    return counts;
+}
+
+/// Returns the number of words of length `n` in X*
+///
+/// # Arguments
+/// * `words` a set of words forming a code
+/// * `n` the word length
+pub(crate) fn words_of_length_in_star(words: &[String], n: usize) -> f64 {
+    return growth_series(words, n)[n];
+}
+
+/// A lazy enumeration of the concatenations of words up to a length bound
+///
+/// The concatenations are ordered by their length, concatenations of the same length by their factorization
+/// (in the order of the words). If the words form a code, every word of X* is generated exactly once,
+/// otherwise words with several factorizations are repeated.
+#[derive(Debug, Clone)]
+pub(crate) struct StarWords {
+    words: Vec<String>,
+    lengths: Vec<usize>,
+    max_len: usize,
+    /// The length of the currently generated concatenations
+    target: usize,
+    /// The next word index to try at each depth
+    frames: Vec<usize>,
+    /// The indices of the currently chosen words
+    chosen: Vec<usize>,
+    len: usize,
+}
+
+impl StarWords {
+    /// Returns the enumeration of the words of X* up to the length `max_len`
+    ///
+    /// # Arguments
+    /// * `words` a set of words, empty words are ignored
+    /// * `max_len` the maximal length
+    pub(crate) fn new(words: &[String], max_len: usize) -> StarWords {
+        let words = words.iter().filter(|w| !w.is_empty()).cloned().collect::<Vec<String>>();
+        return StarWords {
+            lengths: words.iter().map(|w| w.chars().count()).collect(),
+            words,
+            max_len,
+            target: 0,
+            frames: vec![],
+            chosen: vec![],
+            len: 0,
+        };
+    }
+}
+
+impl Iterator for StarWords {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        loop {
+            if self.frames.is_empty() {
+                if self.target > self.max_len {
+                    return None;
+                }
+                self.target += 1;
+                self.frames.push(0);
+                if self.target == 1 {
+                    // The empty word
+                    return Some(String::new());
+                }
+                continue;
+            }
+
+            let depth = self.frames.len() - 1;
+            let i = self.frames[depth];
+            if i == self.words.len() {
+                self.frames.pop();
+                if let Some(w) = self.chosen.pop() {
+                    self.len -= self.lengths[w];
+                }
+                continue;
+            }
+
+            self.frames[depth] += 1;
+            let target = self.target - 1;
+            if self.len + self.lengths[i] > target {
+                continue;
+            }
+            if self.len + self.lengths[i] == target {
+                let word = self.chosen.iter().chain(std::iter::once(&i)).map(|w| self.words[*w].as_str()).collect::<String>();
+                return Some(word);
+            }
+
+            self.chosen.push(i);
+            self.len += self.lengths[i];
+            self.frames.push(0);
+        }
+    }
+}
+
+/// Returns the number of words of length n generated by a code
+///
+/// The set \emph{X*} contains all concatenations of words of a code \emph{X}.
+/// This function counts the words of length \emph{n} in \emph{X*} by
+/// dynamic programming over the lengths. The result is only exact if \emph{X} is a code
+/// (see \link{is_code}), otherwise it counts factorizations instead of words.
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param n An integer, the length
+///
+/// @return A numeric value, the number of words of length n in X*.
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGG", "AC"))
+/// count_words_of_length(code, 6)
+///
+/// @seealso \link{growth_series_of_code}
+///
+/// @export
+#[extendr]
+fn count_words_of_length(tuples: Vec<String>, n: i32) -> f64 {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        return words_of_length_in_star(&code.get_code(), n.max(0) as usize);
+    });
+}
+
+/// Returns the growth series of the monoid generated by a code
+///
+/// The growth (generating) series of \emph{X*} is
+/// \emph{sum_i a_i z^i = 1 / (1 - sum_(w in X) z^|w|)} where \emph{a_i} is the
+/// number of words of length \emph{i} in \emph{X*}. Comparing the coefficients of
+/// candidate codes compares their information capacity.
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param n An integer, the maximal length
+///
+/// @return A numeric vector with the n + 1 coefficients a_0, ..., a_n.
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGG", "AC"))
+/// growth_series_of_code(code, 10)
+///
+/// @seealso \link{count_words_of_length}
+///
+/// @export
+#[extendr]
+fn growth_series_of_code(tuples: Vec<String>, n: i32) -> Vec<f64> {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        return growth_series(&code.get_code(), n.max(0) as usize);
+    });
+}
+
+/// Returns the words generated by a code up to a length
+///
+/// The set \emph{X*} contains all concatenations of words of a code \emph{X} (including the empty word).
+/// This function enumerates the words of \emph{X*} up to the length \emph{max_len}
+/// ordered by their length, e.g. to verify definitions exhaustively on small examples or to generate
+/// test sequences. If \emph{X} is not a code (see \link{is_code}), words with several factorizations are repeated.
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param max_len An integer, the maximal length
+/// @param max_count An integer, the maximal number of returned words (non-positive: unlimited)
+///
+/// @return A String vector with the words of X* up to the length max_len.
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGG", "AC"))
+/// star_words_up_to_length(code, 6, 0)
+///
+/// @seealso \link{count_words_of_length}
+///
+/// @export
+#[extendr]
+fn star_words_up_to_length(tuples: Vec<String>, max_len: i32, max_count: i32) -> Vec<String> {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let words = StarWords::new(&code.get_code(), max_len.max(0) as usize);
+        if max_count > 0 {
+            return words.take(max_count as usize).collect();
+        }
+
+        return words.collect();
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod monoid;
+    fn count_words_of_length;
+    fn growth_series_of_code;
+    fn star_words_up_to_length;
+}
@@ -0,0 +1,179 @@
+use extendr_api::prelude::*;
+
+use crate::genetic_code::GeneticCode;
+use crate::guard::guard;
+use crate::lib_utils::new_code_from_vec;
+use crate::scan::AhoCorasick;
+
+/// An open reading frame: a start codon followed by codons up to and including the first stop codon
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Orf {
+    /// The position of the start codon (character index starting with 0)
+    pub(crate) start: usize,
+    /// The position after the stop codon
+    pub(crate) end: usize,
+    /// The frame, i.e. `start` modulo 3
+    pub(crate) frame: usize,
+    /// The number of codons including the start and the stop codon
+    pub(crate) codons: usize,
+}
+
+/// Returns all open reading frames of the forward strand, ordered by their start
+///
+/// In each of the three frames an ORF starts at the first `ATG` (or `AUG`) after the last stop codon
+/// and ends with the next stop codon of the genetic code. ORFs without a stop codon are ignored.
+///
+/// # Arguments
+/// * `seq` a DNA or RNA sequence
+/// * `genetic_code` the codon table defining the stop codons
+/// * `min_codons` the minimal number of codons of an ORF
+pub(crate) fn find_orfs(seq: &str, genetic_code: &GeneticCode, min_codons: usize) -> Vec<Orf> {
+    let chars = seq.chars().collect::<Vec<char>>();
+    let mut orfs = Vec::new();
+    for frame in 0..3 {
+        let mut open = None;
+        let mut p = frame;
+        while p + 3 <= chars.len() {
+            let codon = chars[p..p + 3].iter().collect::<String>();
+            let upper = codon.to_ascii_uppercase();
+            if open.is_none() && (upper == "ATG" || upper == "AUG") {
+                open = Some(p);
+            }
+            if let (Some(start), Some('*')) = (open, genetic_code.translate(&codon)) {
+                let codons = (p + 3 - start) / 3;
+                if codons >= min_codons {
+                    orfs.push(Orf { start, end: p + 3, frame, codons });
+                }
+                open = None;
+            }
+            p += 3;
+        }
+    }
+    orfs.sort_by_key(|o| o.start);
+
+    return orfs;
+}
+
+/// The coverage of a sequence by the codons of a code inside and outside of ORFs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct OrfCoverage {
+    /// The number of codons of each ORF which are code words
+    pub(crate) orf_hits: Vec<usize>,
+    /// The number of codons of all ORFs which are code words
+    pub(crate) inside_hits: usize,
+    /// The number of codons of all ORFs
+    pub(crate) inside_codons: usize,
+    /// The number of tuples outside of all ORFs (in all frames) which are code words
+    pub(crate) outside_hits: usize,
+    /// The number of tuples outside of all ORFs (in all frames)
+    pub(crate) outside_tuples: usize,
+}
+
+/// Returns the coverage of a sequence by the codons of a code inside and outside of ORFs
+///
+/// The codons of an ORF are read in its frame. Outside of the ORFs there is no reading frame,
+/// so all tuples of length 3 which do not overlap an ORF are counted. Only words of length 3 are considered.
+///
+/// # Arguments
+/// * `ac` the automaton of the code
+/// * `seq` a sequence
+/// * `orfs` the ORFs of the sequence
+pub(crate) fn orf_coverage(ac: &AhoCorasick, seq: &str, orfs: &[Orf]) -> OrfCoverage {
+    let len = seq.chars().count();
+    let mut hit = vec![false; len];
+    for (start, idx) in ac.find_all(seq) {
+        if ac.lengths[idx] == 3 {
+            hit[start] = true;
+        }
+    }
+    let mut inside = vec![false; len];
+    for orf in orfs {
+        inside[orf.start..orf.end].iter_mut().for_each(|i| *i = true);
+    }
+
+    let orf_hits = orfs.iter().map(|o| (o.start..o.end).step_by(3).filter(|p| hit[*p]).count()).collect::<Vec<usize>>();
+    let outside = (0..len.saturating_sub(2)).filter(|p| !inside[*p..*p + 3].iter().any(|i| *i)).collect::<Vec<usize>>();
+
+    return OrfCoverage {
+        inside_hits: orf_hits.iter().sum(),
+        inside_codons: orfs.iter().map(|o| o.codons).sum(),
+        orf_hits,
+        outside_hits: outside.iter().filter(|p| hit[**p]).count(),
+        outside_tuples: outside.len(),
+    };
+}
+
+/// Returns the genetic code of a NCBI translation table id or stops with an R error
+fn genetic_code_from_r(table_id: i32) -> GeneticCode {
+    match GeneticCode::ncbi(table_id.max(0) as u32) {
+        Ok(g) => return g,
+        Err(e) => {
+            r_error!("Genetic code is not correct: {}", e);
+        }
+    }
+}
+
+/// Returns all open reading frames of a sequence
+///
+/// @param seq A String, the sequence
+/// @param min_codons An integer, the minimal number of codons of an ORF
+/// @param table_id An integer, the NCBI translation table id
+///
+/// @return A list with the start positions (start, starting with 1), the end positions (end, inclusive),
+/// the frames (frame) and the number of codons (codons) of all ORFs.
+///
+/// @seealso \link{find_orfs}
+///
+#[extendr]
+fn find_orfs_obj(seq: String, min_codons: i32, table_id: i32) -> Robj {
+    return guard(|| {
+        let genetic_code = genetic_code_from_r(table_id);
+        let orfs = find_orfs(&seq, &genetic_code, min_codons.max(1) as usize);
+
+        return list!(start = orfs.iter().map(|o| o.start as i32 + 1).collect::<Vec<i32>>(),
+        end = orfs.iter().map(|o| o.end as i32).collect::<Vec<i32>>(),
+        frame = orfs.iter().map(|o| o.frame as i32).collect::<Vec<i32>>(),
+        codons = orfs.iter().map(|o| o.codons as i32).collect::<Vec<i32>>());
+    });
+}
+
+/// Returns the coverage of a sequence by a code inside and outside of its open reading frames
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param seq A String, the sequence
+/// @param min_codons An integer, the minimal number of codons of an ORF
+/// @param table_id An integer, the NCBI translation table id
+///
+/// @return A list with the ORFs (start, end, frame, codons), the number of codons of each ORF which are code words (hits)
+/// and the totals inside (inside_hits, inside_codons) and outside (outside_hits, outside_tuples) of the ORFs.
+///
+/// @seealso \link{orf_code_coverage}
+///
+#[extendr]
+fn orf_code_coverage_obj(tuples: Vec<String>, seq: String, min_codons: i32, table_id: i32) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let genetic_code = genetic_code_from_r(table_id);
+        let orfs = find_orfs(&seq, &genetic_code, min_codons.max(1) as usize);
+        let coverage = orf_coverage(&AhoCorasick::new(&code.get_code()), &seq, &orfs);
+
+        return list!(start = orfs.iter().map(|o| o.start as i32 + 1).collect::<Vec<i32>>(),
+        end = orfs.iter().map(|o| o.end as i32).collect::<Vec<i32>>(),
+        frame = orfs.iter().map(|o| o.frame as i32).collect::<Vec<i32>>(),
+        codons = orfs.iter().map(|o| o.codons as i32).collect::<Vec<i32>>(),
+        hits = coverage.orf_hits.iter().map(|h| *h as i32).collect::<Vec<i32>>(),
+        inside_hits = coverage.inside_hits as f64,
+        inside_codons = coverage.inside_codons as f64,
+        outside_hits = coverage.outside_hits as f64,
+        outside_tuples = coverage.outside_tuples as f64);
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod orf;
+    fn find_orfs_obj;
+    fn orf_code_coverage_obj;
+}
@@ -0,0 +1,106 @@
+use extendr_api::prelude::*;
+
+use crate::gcat::CircCode;
+use crate::guard::guard;
+use crate::lib_utils::new_code_from_vec;
+
+/// Returns the lengths of all proper suffixes of `u` which are proper prefixes of `v` (ascending)
+///
+/// # Arguments
+/// * `u` the word of the suffix
+/// * `v` the word of the prefix
+pub(crate) fn overlap_lengths(u: &str, v: &str) -> Vec<usize> {
+    let u = u.chars().collect::<Vec<char>>();
+    let v = v.chars().collect::<Vec<char>>();
+    return (1..u.len().min(v.len()))
+        .filter(|l| u[u.len() - l..] == v[..*l])
+        .collect();
+}
+
+/// The suffix-prefix overlaps of all ordered pairs of words of a code
+///
+/// The entry `(i, j)` holds the lengths of all proper suffixes of word `i` which are proper
+/// prefixes of word `j`. A code is strong comma-free iff all entries are empty, and each overlap
+/// of length `l` is an edge of G(X) from the suffix of length `|w_i| - l` to the prefix of length `l`
+/// followed by the suffix of the same length.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct OverlapMatrix {
+    words: Vec<String>,
+    overlaps: Vec<Vec<Vec<usize>>>,
+}
+
+impl OverlapMatrix {
+    /// Returns the overlap matrix of a set of words
+    ///
+    /// # Arguments
+    /// * `words` a set of words
+    pub(crate) fn new(words: &[String]) -> OverlapMatrix {
+        let overlaps = words.iter()
+            .map(|u| words.iter().map(|v| overlap_lengths(u, v)).collect())
+            .collect();
+        return OverlapMatrix { words: words.to_vec(), overlaps };
+    }
+
+    /// Returns the words of the rows and columns
+    pub(crate) fn words(&self) -> &[String] {
+        return &self.words;
+    }
+
+    /// Returns the overlap lengths of the suffixes of word `i` with the prefixes of word `j` (ascending)
+    pub(crate) fn get(&self, i: usize, j: usize) -> &[usize] {
+        return &self.overlaps[i][j];
+    }
+
+    /// Returns the longest overlap of word `i` with word `j`, 0 if there is none
+    pub(crate) fn longest(&self, i: usize, j: usize) -> usize {
+        return self.overlaps[i][j].last().copied().unwrap_or(0);
+    }
+
+    /// Returns all overlaps as triples `(i, j, length)` by row, column and length
+    pub(crate) fn entries(&self) -> impl Iterator<Item = (usize, usize, usize)> + '_ {
+        return self.overlaps.iter().enumerate()
+            .flat_map(|(i, row)| row.iter().enumerate()
+                .flat_map(move |(j, ls)| ls.iter().map(move |l| (i, j, *l))));
+    }
+}
+
+/// Codes which have an overlap matrix
+pub(crate) trait Overlaps {
+    fn overlap_matrix(&self) -> OverlapMatrix;
+}
+
+impl Overlaps for CircCode {
+    fn overlap_matrix(&self) -> OverlapMatrix {
+        return OverlapMatrix::new(&self.get_code());
+    }
+}
+
+/// Returns the suffix-prefix overlaps of all ordered pairs of words of a code
+///
+/// @param tuples A gcatbase::gcat.code object
+///
+/// @return A list with the words of the rows and columns (words) and one entry per overlap: the word
+/// of the suffix (from), the word of the prefix (to) and the length of the overlap (length).
+///
+/// @seealso \link{overlap_matrix}
+///
+#[extendr]
+fn overlap_matrix_obj(tuples: Vec<String>) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let matrix = code.overlap_matrix();
+        let entries = matrix.entries().collect::<Vec<(usize, usize, usize)>>();
+        return list!(words = matrix.words().to_vec(),
+        from = entries.iter().map(|e| matrix.words()[e.0].clone()).collect::<Vec<String>>(),
+        to = entries.iter().map(|e| matrix.words()[e.1].clone()).collect::<Vec<String>>(),
+        length = entries.iter().map(|e| e.2 as i32).collect::<Vec<i32>>());
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod overlap;
+    fn overlap_matrix_obj;
+}
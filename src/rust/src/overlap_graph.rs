@@ -0,0 +1,122 @@
+use extendr_api::prelude::*;
+
+use crate::graph_structure::{metrics_robj, IndexedGraph};
+use crate::guard::guard;
+use crate::lib_utils::new_code_from_vec;
+use crate::limits::{Budget, SearchLimits};
+use crate::overlap::OverlapMatrix;
+
+/// Edges `(from, to)` labeled with their words
+type LabeledEdges = Vec<(String, String)>;
+
+/// The overlap graph of a code
+///
+/// The vertices are the words of the code. There is an edge from `u` to `v` iff a proper suffix of `u`
+/// is a proper prefix of `v` (see [OverlapMatrix]), so words which overlap with themselves have a loop.
+/// Unlike G(X), whose vertices are the prefixes and suffixes of the words, each edge stands for a pair
+/// of words which can be chained with a shared part, as in De Bruijn graphs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct OverlapGraph {
+    pub(crate) vertices: Vec<String>,
+    /// The edges `(from, to)` by row and column of the overlap matrix
+    pub(crate) edges: LabeledEdges,
+}
+
+impl OverlapGraph {
+    /// Returns the overlap graph of a set of words
+    ///
+    /// # Arguments
+    /// * `words` a set of words
+    pub(crate) fn new(words: &[String]) -> OverlapGraph {
+        let matrix = OverlapMatrix::new(words);
+        let edges = (0..words.len())
+            .flat_map(|i| (0..words.len()).map(move |j| (i, j)))
+            .filter(|(i, j)| !matrix.get(*i, *j).is_empty())
+            .map(|(i, j)| (words[i].clone(), words[j].clone()))
+            .collect();
+
+        return OverlapGraph { vertices: words.to_vec(), edges };
+    }
+
+    /// Returns the graph for the structural algorithms
+    pub(crate) fn indexed(&self) -> IndexedGraph {
+        return IndexedGraph::from_edges(self.vertices.clone(), &self.edges);
+    }
+
+    /// Returns the edges of all cycles and of all longest paths
+    ///
+    /// The longest paths are empty if the graph is cyclic.
+    pub(crate) fn highlighted_edges(&self) -> (LabeledEdges, LabeledEdges) {
+        let g = self.indexed();
+        let label = |(v, w): (usize, usize)| (g.vertices[v].clone(), g.vertices[w].clone());
+        let cycle_edges = g.cycle_edges().into_iter().map(label).collect();
+        let longest_path_edges = g.longest_path_edges().unwrap_or_default().into_iter().map(label).collect();
+        return (cycle_edges, longest_path_edges);
+    }
+}
+
+/// Returns the overlap graph of a code
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param show_cycles A boolean, if true all edges in all cyclic paths are listed separately
+/// @param show_longest_path A boolean, if true all edges in all longest paths are listed separately
+///
+/// @return A list with the same entries as \link{get_representing_graph}: the vertices (vertices), the other edges (edges),
+/// the edges of cycles (circular_path_edges) and the edges of longest paths (longest_path_edges).
+/// Each edge is given by two consecutive entries.
+///
+/// @seealso \link{get_overlap_graph}
+///
+#[extendr]
+fn get_overlap_graph_obj(tuples: Vec<String>, show_cycles: bool, show_longest_path: bool) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let g = OverlapGraph::new(&code.get_code());
+        let (mut cycle_edges, mut longest_path_edges) = g.highlighted_edges();
+        if !show_cycles {
+            cycle_edges.clear();
+        }
+        if !show_longest_path {
+            longest_path_edges.clear();
+        }
+        let flatten = |edges: &[(String, String)]| edges.iter().flat_map(|e| [e.0.clone(), e.1.clone()]).collect::<Vec<String>>();
+        let edges = g.edges.iter()
+            .filter(|e| !cycle_edges.contains(e) && !longest_path_edges.contains(e))
+            .cloned()
+            .collect::<Vec<(String, String)>>();
+
+        return list!(vertices = g.vertices.clone(),
+        edges = flatten(&edges),
+        circular_path_edges = flatten(&cycle_edges),
+        longest_path_edges = flatten(&longest_path_edges));
+    });
+}
+
+/// Returns the metrics of the overlap graph of a code
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param max_millis A numeric value, the maximal run time of the cycle count in milliseconds (non-positive: unlimited)
+///
+/// @return A list with the metrics as \link{graph_metrics_obj}.
+///
+/// @seealso \link{overlap_graph_metrics}
+///
+#[extendr]
+fn overlap_graph_metrics_obj(tuples: Vec<String>, max_millis: f64) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let g = OverlapGraph::new(&code.get_code()).indexed();
+        let mut budget = Budget::new(SearchLimits::from_r(max_millis, 0.0, 0.0));
+        let m = g.metrics(&mut budget);
+        return metrics_robj(&m, budget.is_truncated());
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod overlap_graph;
+    fn get_overlap_graph_obj;
+    fn overlap_graph_metrics_obj;
+}
@@ -0,0 +1,125 @@
+// Parallel execution of the analyses. Without the `parallel` feature (rayon) all
+// analyses run sequentially in the calling thread.
+
+#[cfg(feature = "parallel")]
+use std::sync::{Arc, Mutex, OnceLock};
+
+use extendr_api::prelude::*;
+
+use crate::guard::guard;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+#[cfg(feature = "parallel")]
+use rayon::{ThreadPool, ThreadPoolBuilder};
+
+/// The thread pool of the parallel analyses, `None` uses the global pool of rayon (all cores)
+#[cfg(feature = "parallel")]
+fn pool() -> &'static Mutex<Option<Arc<ThreadPool>>> {
+    static POOL: OnceLock<Mutex<Option<Arc<ThreadPool>>>> = OnceLock::new();
+    return POOL.get_or_init(|| Mutex::new(None));
+}
+
+/// Sets the number of threads of the parallel analyses
+///
+/// # Arguments
+/// * `n` the number of threads, 0 uses all cores
+#[cfg(feature = "parallel")]
+pub(crate) fn set_threads(n: usize) -> Result<(), String> {
+    let new_pool = match n {
+        0 => None,
+        n => Some(Arc::new(ThreadPoolBuilder::new().num_threads(n).build().map_err(|e| e.to_string())?)),
+    };
+    if let Ok(mut pool) = pool().lock() {
+        *pool = new_pool;
+    }
+
+    return Ok(());
+}
+
+#[cfg(not(feature = "parallel"))]
+pub(crate) fn set_threads(_n: usize) -> Result<(), String> {
+    return Ok(());
+}
+
+/// Returns the number of threads of the parallel analyses
+#[cfg(feature = "parallel")]
+pub(crate) fn threads() -> usize {
+    return match pool().lock().ok().and_then(|p| p.clone()) {
+        Some(pool) => pool.current_num_threads(),
+        None => rayon::current_num_threads(),
+    };
+}
+
+#[cfg(not(feature = "parallel"))]
+pub(crate) fn threads() -> usize {
+    return 1;
+}
+
+/// Applies `f` to all items in the configured thread pool and returns the results in the order of the items
+///
+/// # Arguments
+/// * `items` the inputs
+/// * `f` the function
+#[cfg(feature = "parallel")]
+pub(crate) fn par_map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync + Send) -> Vec<R> {
+    let run = || items.par_iter().map(f).collect::<Vec<R>>();
+    return match pool().lock().ok().and_then(|p| p.clone()) {
+        Some(pool) => pool.install(run),
+        None => run(),
+    };
+}
+
+#[cfg(not(feature = "parallel"))]
+pub(crate) fn par_map<T, R>(items: &[T], f: impl Fn(&T) -> R) -> Vec<R> {
+    return items.iter().map(f).collect();
+}
+
+/// Sets the number of threads of the parallel analyses
+///
+/// @param n An integer, the number of threads. Non-positive values use all cores.
+///
+/// @return The number of threads before the call.
+///
+/// @seealso \link{set_thread_count}
+///
+#[extendr]
+fn set_thread_count_obj(n: i32) -> i32 {
+    return guard(|| {
+        let previous = threads() as i32;
+        if let Err(e) = set_threads(n.max(0) as usize) {
+            r_error!("Thread pool can not be created: {}", e);
+        }
+
+        return previous;
+    });
+}
+
+/// Returns the number of threads of the parallel analyses
+///
+/// Parallel analyses (e.g. \link{scan_sequence}, \link{export_sequence_features}) use all cores
+/// unless a number of threads is set by \link{set_thread_count}. It is 1 if the package
+/// was built without the feature \emph{parallel}.
+///
+/// @return An integer, the number of threads.
+///
+/// @examples
+/// get_thread_count()
+///
+/// @seealso \link{set_thread_count}
+///
+/// @export
+#[extendr]
+fn get_thread_count() -> i32 {
+    return guard(|| {
+        return threads() as i32;
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod parallel;
+    fn set_thread_count_obj;
+    fn get_thread_count;
+}
@@ -0,0 +1,38 @@
+/// A small deterministic pseudo random number generator (SplitMix64)
+///
+/// The generator is seeded explicitly so that simulations are reproducible.
+#[derive(Debug, Clone)]
+pub(crate) struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Returns a new generator for the given seed
+    pub(crate) fn new(seed: u64) -> Rng {
+        return Rng { state: seed };
+    }
+
+    /// Returns the internal state, [Rng::new] with this state continues the sequence of random numbers
+    pub(crate) fn state(&self) -> u64 {
+        return self.state;
+    }
+
+    /// Returns the next random number
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        return z ^ (z >> 31);
+    }
+
+    /// Returns a random number in `0..n` (`n` must be positive)
+    pub(crate) fn below(&mut self, n: usize) -> usize {
+        return (self.next_u64() % n as u64) as usize;
+    }
+
+    /// Returns a random number in `[0, 1)`
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        return (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+    }
+}
@@ -0,0 +1,324 @@
+use std::collections::VecDeque;
+
+use extendr_api::prelude::*;
+
+use crate::guard::guard;
+use crate::lib_utils::new_code_from_vec;
+use crate::parallel::par_map;
+use crate::transform::words_reverse_complement;
+use crate::trie::CodeTrie;
+
+/// The number of characters of a sequence which are scanned by one task
+pub(crate) const SCAN_CHUNK: usize = 1 << 20;
+
+/// An Aho-Corasick automaton over a set of words
+///
+/// Finds all (overlapping) occurrences of all words in a sequence in linear time.
+#[derive(Debug, Clone)]
+pub(crate) struct AhoCorasick {
+    pub(crate) lengths: Vec<usize>,
+    /// The goto function, the trie of the words
+    trie: CodeTrie,
+    fail: Vec<usize>,
+    /// The indices of all words ending in each state
+    output: Vec<Vec<usize>>,
+}
+
+impl AhoCorasick {
+    /// Returns the automaton of a set of words
+    ///
+    /// The states are the nodes of the [CodeTrie] of the words.
+    ///
+    /// # Arguments
+    /// * `words` a set of words
+    pub(crate) fn new(words: &[String]) -> AhoCorasick {
+        let trie = CodeTrie::from_words(words);
+        let mut output = (0..trie.node_count()).map(|s| trie.word_at(s).into_iter().collect()).collect::<Vec<Vec<usize>>>();
+
+        let mut fail = vec![0; trie.node_count()];
+        let mut queue = trie.children(CodeTrie::ROOT).map(|(_, n)| n).collect::<VecDeque<usize>>();
+        while let Some(state) = queue.pop_front() {
+            for (c, next) in trie.children(state) {
+                queue.push_back(next);
+                let mut f = fail[state];
+                while f != 0 && trie.step(f, c).is_none() {
+                    f = fail[f];
+                }
+                fail[next] = trie.step(f, c).filter(|s| *s != next).unwrap_or(0);
+                let inherited = output[fail[next]].clone();
+                output[next].extend(inherited);
+            }
+        }
+
+        return AhoCorasick {
+            lengths: words.iter().map(|w| w.chars().count()).collect(),
+            trie,
+            fail,
+            output,
+        };
+    }
+
+    /// Returns the words of the automaton
+    pub(crate) fn words(&self) -> &[String] {
+        return self.trie.words();
+    }
+
+    /// Returns all occurrences `(start, word index)` in the characters, ordered by their end position
+    fn find_in(&self, chars: &[char]) -> Vec<(usize, usize)> {
+        let mut result = Vec::new();
+        let mut state = 0;
+        for (i, c) in chars.iter().enumerate() {
+            while state != 0 && self.trie.step(state, *c).is_none() {
+                state = self.fail[state];
+            }
+            state = self.trie.step(state, *c).unwrap_or(0);
+            for idx in &self.output[state] {
+                result.push((i + 1 - self.lengths[*idx], *idx));
+            }
+        }
+
+        return result;
+    }
+
+    /// Returns all occurrences `(start, word index)` in the sequence, ordered by their start position and word index
+    ///
+    /// Positions are character indices starting with 0. The sequence is scanned in parallel
+    /// in chunks of [SCAN_CHUNK] characters. Each chunk is extended by the length of the longest word
+    /// minus one, so occurrences crossing a chunk border are found, and reports only the occurrences
+    /// starting within the chunk. The merged result does not depend on the chunking.
+    ///
+    /// # Arguments
+    /// * `seq` a sequence
+    pub(crate) fn find_all(&self, seq: &str) -> Vec<(usize, usize)> {
+        let chars = seq.chars().collect::<Vec<char>>();
+        let overlap = self.lengths.iter().copied().max().unwrap_or(1).max(1) - 1;
+        let starts = (0..chars.len()).step_by(SCAN_CHUNK).collect::<Vec<usize>>();
+        let mut result = par_map(&starts, |start| {
+            let end = (start + SCAN_CHUNK).min(chars.len());
+            self.find_in(&chars[*start..(end + overlap).min(chars.len())]).into_iter()
+                .filter(|(s, _)| start + s < end)
+                .map(|(s, idx)| (start + s, idx))
+                .collect::<Vec<(usize, usize)>>()
+        }).concat();
+        result.sort_unstable();
+
+        return result;
+    }
+}
+
+/// Returns the frame modulus of a code, i.e. the length of its longest word
+pub(crate) fn frame_modulus(words: &[String]) -> usize {
+    return words.iter().map(|w| w.chars().count()).max().unwrap_or(1).max(1);
+}
+
+/// Returns the fraction of tuples of each frame which are words of the code
+///
+/// The i-th entry is the number of occurrences of words of length `n` starting at a position `p` with `p % n == i`
+/// divided by the number of tuples in the frame, where `n` is the length of the longest word.
+///
+/// # Arguments
+/// * `ac` the automaton of the code
+/// * `seq` a sequence
+pub(crate) fn frame_coverage(ac: &AhoCorasick, seq: &str) -> Vec<f64> {
+    let n = frame_modulus(ac.words());
+    let len = seq.chars().count();
+    let mut hits = vec![0usize; n];
+    for (start, idx) in ac.find_all(seq) {
+        if ac.lengths[idx] == n {
+            hits[start % n] += 1;
+        }
+    }
+
+    return (0..n).map(|f| {
+        let tuples = if len >= f + n { (len - f) / n } else { 0 };
+        if tuples == 0 { 0.0 } else { hits[f] as f64 / tuples as f64 }
+    }).collect();
+}
+
+/// Returns the inferred reading frame of each position of a sequence
+///
+/// A run is a maximal series of consecutive tuples of length `n` in one frame which are all words of the code,
+/// where `n` is the length of the longest word. By the frame retrieval property of circular codes a long run
+/// only occurs in the reading frame, so each position is assigned the frame (absolute, i.e. the start positions
+/// of the tuples modulo `n`) of the longest run covering it. Positions which are covered by no run of at least
+/// `min_words` words or by equally long runs of different frames are not assigned (None).
+///
+/// # Arguments
+/// * `ac` the automaton of the code
+/// * `seq` a sequence
+/// * `min_words` the minimal number of words of a run
+pub(crate) fn frame_track(ac: &AhoCorasick, seq: &str, min_words: usize) -> Vec<Option<usize>> {
+    let n = frame_modulus(ac.words());
+    let len = seq.chars().count();
+    let mut hit = vec![false; len];
+    for (start, idx) in ac.find_all(seq) {
+        if ac.lengths[idx] == n {
+            hit[start] = true;
+        }
+    }
+
+    // The length of the longest run covering each position and its frame (None if runs of different frames tie)
+    let mut best: Vec<(usize, Option<usize>)> = vec![(0, None); len];
+    for frame in 0..n.min(len) {
+        let mut p = frame;
+        while p < len {
+            if !hit[p] {
+                p += n;
+                continue;
+            }
+            let start = p;
+            while p < len && hit[p] {
+                p += n;
+            }
+            let words = (p - start) / n;
+            if words < min_words.max(1) {
+                continue;
+            }
+            for entry in best[start..(start + words * n).min(len)].iter_mut() {
+                if words > entry.0 {
+                    *entry = (words, Some(frame));
+                } else if words == entry.0 && entry.1 != Some(frame) {
+                    entry.1 = None;
+                }
+            }
+        }
+    }
+
+    return best.into_iter().map(|b| b.1).collect();
+}
+
+/// Returns the reverse complement of a DNA or RNA sequence
+pub(crate) fn reverse_complement(seq: &str) -> String {
+    return words_reverse_complement(&[seq.to_string()]).remove(0);
+}
+
+/// The strand of an occurrence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Strand {
+    Forward,
+    Reverse,
+}
+
+impl Strand {
+    /// Returns the name of the strand as in BED and GFF3 files
+    pub(crate) fn symbol(&self) -> &'static str {
+        match self {
+            Strand::Forward => return "+",
+            Strand::Reverse => return "-",
+        }
+    }
+}
+
+/// Returns all occurrences `(strand, start, frame, word index)` of the words on one or both strands
+///
+/// The start is the position of the first character on the forward strand (i.e. the leftmost position),
+/// the frame is the start position on the scanned strand modulo the length of the longest word. On the reverse
+/// strand the positions are counted from the start of the reverse complement.
+///
+/// # Arguments
+/// * `ac` the automaton of the code
+/// * `seq` a sequence
+/// * `both_strands` true if the reverse complement is scanned too
+pub(crate) fn find_on_strands(ac: &AhoCorasick, seq: &str, both_strands: bool) -> Vec<(Strand, usize, usize, usize)> {
+    let n = frame_modulus(ac.words());
+    let mut result = ac.find_all(seq).into_iter()
+        .map(|(start, idx)| (Strand::Forward, start, start % n, idx))
+        .collect::<Vec<(Strand, usize, usize, usize)>>();
+    if both_strands {
+        let len = seq.chars().count();
+        result.extend(ac.find_all(&reverse_complement(seq)).into_iter()
+            .map(|(start, idx)| (Strand::Reverse, len - start - ac.lengths[idx], start % n, idx)));
+    }
+
+    return result;
+}
+
+/// Returns the coverage of all frames of one or both strands
+///
+/// The first `n` entries are the coverages of the frames of the forward strand (see [frame_coverage]),
+/// the next `n` entries the coverages of the frames of the reverse complement.
+///
+/// # Arguments
+/// * `ac` the automaton of the code
+/// * `seq` a sequence
+/// * `both_strands` true if the reverse complement is analysed too
+pub(crate) fn strand_frame_coverage(ac: &AhoCorasick, seq: &str, both_strands: bool) -> Vec<f64> {
+    let mut coverage = frame_coverage(ac, seq);
+    if both_strands {
+        coverage.extend(frame_coverage(ac, &reverse_complement(seq)));
+    }
+
+    return coverage;
+}
+
+/// Returns all occurrences of the words of a code in a sequence
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param seq A String, the sequence
+/// @param both_strands A boolean, true if the reverse complement is scanned too
+///
+/// @return A list with the start positions (start, starting with 1), the words (word), the frames (frame)
+/// and the strands (strand) of all occurrences.
+///
+/// @seealso \link{scan_sequence}
+///
+#[extendr]
+fn scan_sequence_obj(tuples: Vec<String>, seq: String, both_strands: bool) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let ac = AhoCorasick::new(&code.get_code());
+        let hits = find_on_strands(&ac, &seq, both_strands);
+
+        return list!(start = hits.iter().map(|h| h.1 as i32 + 1).collect::<Vec<i32>>(),
+        word = hits.iter().map(|h| ac.words()[h.3].clone()).collect::<Vec<String>>(),
+        frame = hits.iter().map(|h| h.2 as i32).collect::<Vec<i32>>(),
+        strand = hits.iter().map(|h| h.0.symbol()).collect::<Vec<&str>>());
+    });
+}
+
+/// Returns the coverage of a sequence by a code in each frame
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param seq A String, the sequence
+/// @param both_strands A boolean, true if the reverse complement is analysed too
+///
+/// @return A numeric vector with the coverages of the frames of the forward strand followed by the frames of the reverse strand.
+///
+/// @seealso \link{sequence_frame_coverage}
+///
+#[extendr]
+fn sequence_frame_coverage_obj(tuples: Vec<String>, seq: String, both_strands: bool) -> Vec<f64> {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        return strand_frame_coverage(&AhoCorasick::new(&code.get_code()), &seq, both_strands);
+    });
+}
+
+/// Returns the inferred reading frame of each position of a sequence
+///
+/// @param tuples A gcatbase::gcat.code object, e.g. the code X0
+/// @param seq A String, the sequence
+/// @param min_words An integer, the minimal number of consecutive words which determine a frame
+///
+/// @return An integer vector, the i-th entry is the frame of the position i (-1 if not assigned).
+///
+/// @seealso \link{sequence_frame_track}
+///
+#[extendr]
+fn sequence_frame_track_obj(tuples: Vec<String>, seq: String, min_words: i32) -> Vec<i32> {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let track = frame_track(&AhoCorasick::new(&code.get_code()), &seq, min_words.max(1) as usize);
+        return track.iter().map(|f| f.map_or(-1, |f| f as i32)).collect();
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod scan;
+    fn scan_sequence_obj;
+    fn sequence_frame_coverage_obj;
+    fn sequence_frame_track_obj;
+}
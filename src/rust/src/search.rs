@@ -0,0 +1,312 @@
+use extendr_api::prelude::*;
+
+use crate::checkpoint::Checkpoint;
+use crate::guard::guard;
+use crate::lib_utils::{alphabet_of, all_words_of_length, is_circular_words, new_code_from_vec, NamedList};
+use crate::limits::{Budget, SearchLimits};
+use crate::mixed_length::word_lengths;
+use crate::random::Rng;
+use crate::sequence::{split_seq, RemainderPolicy};
+
+/// Returns all codes obtained by replacing one word by another word of the same length
+///
+/// The replacing words are all words over the alphabet of the code which are not already in the code.
+/// The neighbors are the move set of local search algorithms.
+///
+/// # Arguments
+/// * `words` a set of words
+/// * `alphabet` the alphabet of the replacing words
+pub(crate) fn neighbors(words: &[String], alphabet: &[char]) -> Vec<Vec<String>> {
+    let mut result = Vec::new();
+    for (i, word) in words.iter().enumerate() {
+        for candidate in all_words_of_length(alphabet, word.chars().count()) {
+            if words.contains(&candidate) {
+                continue;
+            }
+            let mut neighbor = words.to_vec();
+            neighbor[i] = candidate;
+            result.push(neighbor);
+        }
+    }
+
+    return result;
+}
+
+/// An objective function for the heuristic code search (higher is better)
+pub(crate) trait Objective {
+    fn score(&self, words: &[String]) -> f64;
+}
+
+/// The fraction of in-frame tuples of a set of sequences which are words of the code
+///
+/// Each sequence is read in frame 0 with the tuple length `n`.
+pub(crate) struct SequenceCoverage {
+    tuples: Vec<String>,
+}
+
+impl SequenceCoverage {
+    /// Returns the objective for the given target sequences
+    ///
+    /// # Arguments
+    /// * `sequences` the target sequences
+    /// * `n` the tuple length
+    pub(crate) fn new(sequences: &[String], n: usize) -> SequenceCoverage {
+        let tuples = sequences.iter()
+            .flat_map(|s| split_seq(s, n, 0, RemainderPolicy::Drop).map(|split| split.tuples).unwrap_or_default())
+            .collect();
+        return SequenceCoverage { tuples };
+    }
+}
+
+impl Objective for SequenceCoverage {
+    fn score(&self, words: &[String]) -> f64 {
+        if self.tuples.is_empty() {
+            return 0.0;
+        }
+
+        return self.tuples.iter().filter(|t| words.contains(t)).count() as f64 / self.tuples.len() as f64;
+    }
+}
+
+/// Options of the heuristic code search
+#[derive(Debug, Clone)]
+pub(crate) struct SearchOptions {
+    /// The number of proposed moves
+    pub(crate) iterations: usize,
+    /// The initial temperature of the simulated annealing, `0` results in hill climbing
+    pub(crate) temperature: f64,
+    /// The factor the temperature is multiplied with after each iteration
+    pub(crate) cooling: f64,
+    /// The seed of the random number generator
+    pub(crate) seed: u64,
+    /// If true only circular codes are accepted
+    pub(crate) require_circular: bool,
+}
+
+impl SearchOptions {
+    /// Returns the options passed from R as list with the entries iterations, temperature, cooling, seed and circular
+    ///
+    /// # Arguments
+    /// * `options` the list passed from R
+    pub(crate) fn from_r(options: &Robj) -> Result<SearchOptions, String> {
+        let options = NamedList::new(options, "search options")?;
+        return Ok(SearchOptions {
+            iterations: options.number("iterations")?.max(0.0) as usize,
+            temperature: options.number("temperature")?,
+            cooling: options.number("cooling")?,
+            seed: options.number("seed")? as i64 as u64,
+            require_circular: options.flag("circular")?,
+        });
+    }
+}
+
+/// The best code found by a heuristic search
+#[derive(Debug, Clone)]
+pub(crate) struct SearchResult {
+    pub(crate) words: Vec<String>,
+    pub(crate) score: f64,
+    /// The number of accepted moves
+    pub(crate) accepted: usize,
+}
+
+/// The resumable state of a search for a code maximizing the objective by simulated annealing
+///
+/// Starting from a start code, each iteration replaces a random word by a random word of the same length
+/// (see [neighbors]). Better codes are always accepted, worse codes with the probability
+/// `exp(delta / temperature)`, a temperature of `0` results in hill climbing. The result is reproducible for a given seed.
+///
+/// The state contains the random number generator, so a search stopped by its budget
+/// and resumed from a [Checkpoint] results in the same code as an uninterrupted search.
+#[derive(Debug, Clone)]
+pub(crate) struct AnnealState {
+    current: Vec<String>,
+    current_score: f64,
+    temperature: f64,
+    /// The number of finished iterations
+    iteration: usize,
+    rng: Rng,
+    pub(crate) best: SearchResult,
+}
+
+impl AnnealState {
+    /// The kind of the checkpoints of this search
+    pub(crate) const KIND: &'static str = "optimize_code";
+
+    /// Returns the state before the first iteration
+    pub(crate) fn new(start: &[String], objective: &dyn Objective, options: &SearchOptions) -> AnnealState {
+        let current_score = objective.score(start);
+        return AnnealState {
+            current: start.to_vec(),
+            current_score,
+            temperature: options.temperature,
+            iteration: 0,
+            rng: Rng::new(options.seed),
+            best: SearchResult { words: start.to_vec(), score: current_score, accepted: 0 },
+        };
+    }
+
+    /// True if all iterations are finished
+    pub(crate) fn is_done(&self, options: &SearchOptions) -> bool {
+        return self.iteration >= options.iterations;
+    }
+
+    /// Returns the state of the search
+    pub(crate) fn to_checkpoint(&self) -> Checkpoint {
+        let mut checkpoint = Checkpoint::new(Self::KIND);
+        checkpoint.set_words("current", &self.current);
+        checkpoint.set_f64("temperature", self.temperature);
+        checkpoint.set("iteration", self.iteration);
+        checkpoint.set("rng", self.rng.state());
+        checkpoint.set_words("best", &self.best.words);
+        checkpoint.set("accepted", self.best.accepted);
+        return checkpoint;
+    }
+
+    /// Restores a search from its state, the scores are recomputed with the objective
+    pub(crate) fn from_checkpoint(checkpoint: &Checkpoint, objective: &dyn Objective) -> Result<AnnealState, String> {
+        let current = checkpoint.get_words("current")?;
+        let best = checkpoint.get_words("best")?;
+        return Ok(AnnealState {
+            current_score: objective.score(&current),
+            current,
+            temperature: checkpoint.get_f64("temperature")?,
+            iteration: checkpoint.get_parsed("iteration")?,
+            rng: Rng::new(checkpoint.get_parsed("rng")?),
+            best: SearchResult { score: objective.score(&best), words: best, accepted: checkpoint.get_parsed("accepted")? },
+        });
+    }
+
+    /// Continues the search until all iterations are finished or the budget is exhausted
+    ///
+    /// Each iteration counts as one search node of the budget.
+    pub(crate) fn run(&mut self, objective: &dyn Objective, options: &SearchOptions, budget: &mut Budget) {
+        let alphabet = alphabet_of(&self.current);
+        if self.current.is_empty() || alphabet.is_empty() {
+            self.iteration = options.iterations;
+            return;
+        }
+
+        while !self.is_done(options) && budget.visit() {
+            self.iteration += 1;
+            let i = self.rng.below(self.current.len());
+            let l = self.current[i].chars().count();
+            let candidate = (0..l).map(|_| alphabet[self.rng.below(alphabet.len())]).collect::<String>();
+            if self.current.contains(&candidate) {
+                continue;
+            }
+
+            let mut proposal = self.current.clone();
+            proposal[i] = candidate;
+            if options.require_circular && !is_circular_words(&proposal) {
+                continue;
+            }
+
+            let score = objective.score(&proposal);
+            let delta = score - self.current_score;
+            if delta >= 0.0 || (self.temperature > 0.0 && self.rng.next_f64() < (delta / self.temperature).exp()) {
+                self.current = proposal;
+                self.current_score = score;
+                self.best.accepted += 1;
+                if self.current_score > self.best.score {
+                    self.best.words = self.current.clone();
+                    self.best.score = self.current_score;
+                }
+            }
+            self.temperature *= options.cooling;
+        }
+    }
+}
+
+/// Returns all neighbors of a code
+///
+/// A neighbor of a code \emph{X} is obtained by replacing one word of \emph{X} by
+/// another word of the same length (over the alphabet of \emph{X}) which is not in \emph{X}.
+/// The neighbors are the move set for local search and can be used to study the robustness
+/// of code properties. If \emph{circular_only} is true only circular neighbors are returned.
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param circular_only A boolean, if true only circular codes are returned
+///
+/// @return A list of String vectors, all neighbor codes.
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGG", "AC"))
+/// n <- neighbors_of_code(code, TRUE)
+///
+/// @export
+#[extendr]
+fn neighbors_of_code(tuples: Vec<String>, circular_only: bool) -> Vec<Robj> {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let words = code.get_code();
+        return neighbors(&words, &alphabet_of(&words)).into_iter()
+            .filter(|n| !circular_only || new_code_from_vec(n.clone()).is_circular())
+            .map(Robj::from)
+            .collect();
+    });
+}
+
+/// Searches a code covering a set of sequences.
+///
+/// Starting with a given code, this function searches a code of the same size which
+/// maximizes the coverage of the target sequences, i.e. the fraction of tuples (read in frame 0)
+/// which are words of the code. The search is a simulated annealing: each step replaces
+/// one random word by a random word of the same length. Worse codes are accepted with a
+/// probability depending on the temperature, which decreases by the factor \emph{cooling} each step.
+/// A temperature of 0 results in hill climbing. The same seed always results in the same code.
+///
+/// @param tuples A gcatbase::gcat.code object, the start code. All words must have the same length.
+/// @param sequences A String vector, the target sequences
+/// @param options A list with the number of steps (iterations), the initial temperature (temperature),
+/// the cooling factor (cooling), the seed of the random number generator (seed) and a boolean (circular),
+/// if true only circular codes are accepted
+/// @param max_millis A numeric value, the maximal run time in milliseconds (non-positive: unlimited)
+/// @param checkpoint A String, the state of a stopped search to resume. If empty a new search is started.
+///
+/// @return A list with the best code found (code), its coverage (score), the number of accepted steps (accepted)
+/// and the state to resume the search (checkpoint), empty if all steps are finished.
+///
+/// @seealso \link{optimize_code}
+///
+#[extendr]
+fn optimize_code_obj(tuples: Vec<String>, sequences: Vec<String>, options: Robj, max_millis: f64, checkpoint: String) -> Robj {
+    return guard(|| {
+        let options = match SearchOptions::from_r(&options) {
+            Ok(options) => options,
+            Err(e) => {
+                r_error!("Search options are not correct: {}", e);
+            }
+        };
+        let code = new_code_from_vec(tuples);
+        let words = code.get_code();
+        let lengths = word_lengths(&words);
+        if lengths.len() != 1 {
+            r_error!("Code is not correct: all words of the start code must have the same length");
+        }
+
+        let objective = SequenceCoverage::new(&sequences, lengths[0]);
+        let mut state = if checkpoint.is_empty() {
+            AnnealState::new(&words, &objective, &options)
+        } else {
+            match Checkpoint::parse(&checkpoint, AnnealState::KIND).and_then(|c| AnnealState::from_checkpoint(&c, &objective)) {
+                Ok(state) => state,
+                Err(e) => {
+                    r_error!("Checkpoint is not correct: {}", e);
+                }
+            }
+        };
+
+        state.run(&objective, &options, &mut Budget::new(SearchLimits::from_r(max_millis, 0.0, 0.0)));
+        let checkpoint = if state.is_done(&options) { String::new() } else { state.to_checkpoint().to_text() };
+        return list!(code = state.best.words, score = state.best.score, accepted = state.best.accepted as i32, checkpoint = checkpoint);
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod search;
+    fn neighbors_of_code;
+    fn optimize_code_obj;
+}
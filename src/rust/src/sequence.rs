@@ -0,0 +1,381 @@
+use extendr_api::prelude::*;
+
+use crate::guard::guard;
+
+/// Describes how trailing characters which do not fill a complete tuple are handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RemainderPolicy {
+    /// The trailing characters are discarded
+    Drop,
+    /// A trailing remainder is an error
+    Error,
+    /// The sequence is circular (e.g. a plasmid), the last tuple is completed with the first characters
+    Wrap,
+}
+
+impl RemainderPolicy {
+    /// Parses a policy name (`"drop"`, `"error"` or `"wrap"`)
+    pub(crate) fn from_name(name: &str) -> Result<RemainderPolicy, String> {
+        match name.to_lowercase().as_str() {
+            "drop" => Ok(RemainderPolicy::Drop),
+            "error" => Ok(RemainderPolicy::Error),
+            "wrap" => Ok(RemainderPolicy::Wrap),
+            _ => Err(format!("Unknown remainder policy '{}', expected 'drop', 'error' or 'wrap'", name)),
+        }
+    }
+}
+
+/// The tuples of a sequence and the characters which are not part of any tuple
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct SeqSplit {
+    pub(crate) tuples: Vec<String>,
+    /// The leading characters in front of the reading frame which are not part of any tuple
+    pub(crate) head: String,
+    /// The trailing characters which do not fill a complete tuple
+    pub(crate) tail: String,
+}
+
+impl SeqSplit {
+    /// Returns the number of discarded characters, i.e. of the head and the tail
+    pub(crate) fn discarded(&self) -> usize {
+        return self.head.chars().count() + self.tail.chars().count();
+    }
+}
+
+/// Splits a sequence into tuples of length `n` starting at offset `frame`
+///
+/// The leading `frame` characters and the trailing characters which do not fill a complete
+/// tuple are discarded, see [SeqSplit]. How the trailing characters are treated depends on `policy`:
+/// with [RemainderPolicy::Wrap] the last tuple is completed with the first characters of the sequence,
+/// so leading characters are only discarded if they are not needed for the completion.
+/// Returns an error if `n` is 0 or `frame` is not a position of the (non-empty) sequence.
+///
+/// # Arguments
+/// * `seq` a sequence
+/// * `n` the tuple length
+/// * `frame` the reading frame, i.e. the index of the first character of the first tuple
+/// * `policy` the handling of the trailing remainder
+pub(crate) fn split_seq(seq: &str, n: usize, frame: usize, policy: RemainderPolicy) -> Result<SeqSplit, String> {
+    let chars = seq.chars().collect::<Vec<char>>();
+    if n == 0 {
+        return Err("Tuple length must be positive".to_string());
+    }
+    if chars.is_empty() {
+        return Ok(SeqSplit::default());
+    }
+    if frame >= chars.len() {
+        return Err(format!("Frame {} is outside of the sequence of length {}", frame, chars.len()));
+    }
+
+    let remainder = (chars.len() - frame) % n;
+    let start = chars.len() - remainder;
+    let mut split = SeqSplit {
+        tuples: chars[frame..start].chunks_exact(n).map(|c| c.iter().collect()).collect(),
+        head: chars[..frame].iter().collect(),
+        tail: chars[start..].iter().collect(),
+    };
+    if remainder == 0 {
+        return Ok(split);
+    }
+
+    match policy {
+        RemainderPolicy::Drop => Ok(split),
+        RemainderPolicy::Error => Err(format!("Sequence of length {} leaves {} trailing character(s) in frame {} for tuple length {}",
+                                              chars.len(), remainder, frame, n)),
+        RemainderPolicy::Wrap => {
+            split.tuples.push((0..n).map(|i| chars[(start + i) % chars.len()]).collect());
+            split.head = chars[(n - remainder).min(frame)..frame].iter().collect();
+            split.tail = String::new();
+            Ok(split)
+        }
+    }
+}
+
+/// Returns the set of distinct tuples of length `n` in the reading frame `frame`, see [split_seq]
+///
+/// # Arguments
+/// * `seq` a sequence
+/// * `n` the tuple length
+/// * `frame` the reading frame
+/// * `policy` the handling of the trailing remainder
+pub(crate) fn code_words_from_seq(seq: &str, n: usize, frame: usize, policy: RemainderPolicy) -> Result<SeqSplit, String> {
+    let mut split = split_seq(seq, n, frame, policy)?;
+    split.tuples.sort();
+    split.tuples.dedup();
+    return Ok(split);
+}
+
+/// Returns the `n` sets of words induced by the reading frames 0..n
+///
+/// A frame behind the end of a short sequence induces no words.
+///
+/// # Arguments
+/// * `seq` a sequence
+/// * `n` the tuple length
+/// * `policy` the handling of the trailing remainder
+pub(crate) fn code_words_from_seq_all_frames(seq: &str, n: usize, policy: RemainderPolicy) -> Result<Vec<Vec<String>>, String> {
+    let len = seq.chars().count();
+    return (0..n).map(|frame| match frame < len {
+        true => code_words_from_seq(seq, n, frame, policy).map(|split| split.tuples),
+        false => Ok(vec![]),
+    }).collect();
+}
+
+/// Returns the in-frame k-mer spectrum of a sequence
+///
+/// Counts the tuples of length `n` in the reading frame `frame`, trailing characters are dropped.
+/// The result is sorted by decreasing count, ties are sorted lexicographically.
+///
+/// # Arguments
+/// * `seq` a sequence
+/// * `n` the tuple length
+/// * `frame` the reading frame
+pub(crate) fn kmer_spectrum(seq: &str, n: usize, frame: usize) -> Vec<(String, usize)> {
+    let split = split_seq(seq, n, frame, RemainderPolicy::Drop).unwrap_or_default();
+    let mut counts = std::collections::HashMap::new();
+    for t in split.tuples {
+        *counts.entry(t).or_insert(0) += 1;
+    }
+
+    let mut spectrum = counts.into_iter().collect::<Vec<(String, usize)>>();
+    spectrum.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    return spectrum;
+}
+
+/// Checks the tuple length passed from R
+fn tuple_length_from_r(n: i32) -> usize {
+    if n < 1 {
+        r_error!("Tuple length is not correct: it must be positive, got {}", n);
+    }
+
+    return n as usize;
+}
+
+/// Parses the remainder policy passed from R
+fn remainder_policy_from_r(remainder: &str) -> RemainderPolicy {
+    match RemainderPolicy::from_name(remainder) {
+        Ok(policy) => policy,
+        Err(e) => {
+            r_error!("Remainder policy is not correct: {}", e);
+        }
+    }
+}
+
+/// Checks the reading frame passed from R
+fn frame_from_r(frame: i32) -> usize {
+    if frame < 0 {
+        split_error_to_r(format!("Frame must not be negative: {}", frame));
+    }
+
+    return frame as usize;
+}
+
+/// Stops the execution if a sequence can not be split
+fn split_error_to_r(e: String) -> ! {
+    r_error!("Sequence can not be split: {}", e);
+}
+
+/// Returns the code induced by a reading frame of a sequence
+///
+/// The sequence is split into tuples of length \emph{n} starting at
+/// the character with index \emph{frame} (starting with 0).
+/// Trailing characters which do not fill a complete tuple are handled
+/// according to \emph{remainder}:
+/// "drop" discards them, "error" stops with an error and "wrap" treats the
+/// sequence as circular (e.g. a plasmid) and completes the last tuple with the first characters.
+///
+/// @param seq A String, the sequence
+/// @param n An integer, the tuple length
+/// @param frame An integer, the reading frame
+/// @param remainder A String, one of "drop", "error" or "wrap"
+///
+/// @return A list with the code (code), the number of discarded characters (discarded), the discarded
+/// characters in front of the frame (head) and the discarded trailing characters (tail).
+///
+/// @seealso \link{code_from_seq}, \link{code_from_sequence}
+///
+#[extendr]
+fn code_from_seq_obj(seq: String, n: i32, frame: i32, remainder: String) -> Robj {
+    return guard(|| {
+        let n = tuple_length_from_r(n);
+        let policy = remainder_policy_from_r(&remainder);
+        let frame = frame_from_r(frame);
+
+        match code_words_from_seq(&seq, n, frame, policy) {
+            Ok(split) => {
+                let discarded = split.discarded() as i32;
+                return list!(code = split.tuples, discarded = discarded, head = split.head, tail = split.tail)
+            }
+            Err(e) => {
+                split_error_to_r(e);
+            }
+        }
+    });
+}
+
+/// Returns the codes induced by all reading frames of a sequence
+///
+/// A sequence can be read in \emph{n} different reading frames if it
+/// is split into tuples of length \emph{n}. The i-th frame starts at the
+/// i-th character of the sequence (starting with frame 0). Trailing characters
+/// which do not fill a complete tuple are handled according to \emph{remainder},
+/// see \link{code_from_seq}.
+///
+/// @param seq A String, the sequence
+/// @param n An integer, the tuple length
+/// @param remainder A String, one of "drop", "error" or "wrap"
+///
+/// @return A list of String vectors. The i-th entry contains the code of the (i-1)-th frame.
+///
+/// @examples
+/// code_from_seq_all_frames("ACGCGGACG", 3, "drop")
+///
+/// @seealso \link{code_from_seq_frame_union}
+///
+/// @export
+#[extendr]
+fn code_from_seq_all_frames(seq: String, n: i32, remainder: String) -> Vec<Robj> {
+    return guard(|| {
+        let n = tuple_length_from_r(n);
+        let policy = remainder_policy_from_r(&remainder);
+
+        match code_words_from_seq_all_frames(&seq, n, policy) {
+            Ok(codes) => return codes.into_iter().map(Robj::from).collect(),
+            Err(e) => {
+                split_error_to_r(e);
+            }
+        }
+    });
+}
+
+/// Returns the union of the codes induced by all reading frames of a sequence
+///
+/// The sequence is split into tuples of length \emph{n} in each of the \emph{n}
+/// reading frames. The result contains every tuple which occurs in at least one frame.
+/// Trailing characters are handled according to \emph{remainder}, see \link{code_from_seq}.
+///
+/// @param seq A String, the sequence
+/// @param n An integer, the tuple length
+/// @param remainder A String, one of "drop", "error" or "wrap"
+///
+/// @return A String vector, the union of all frame codes.
+///
+/// @examples
+/// code_from_seq_frame_union("ACGCGGACG", 3, "drop")
+///
+/// @seealso \link{code_from_seq_all_frames}
+///
+/// @export
+#[extendr]
+fn code_from_seq_frame_union(seq: String, n: i32, remainder: String) -> Vec<String> {
+    return guard(|| {
+        let n = tuple_length_from_r(n);
+        let policy = remainder_policy_from_r(&remainder);
+
+        let mut words = match code_words_from_seq_all_frames(&seq, n, policy) {
+            Ok(codes) => codes.concat(),
+            Err(e) => {
+                split_error_to_r(e);
+            }
+        };
+        words.sort();
+        words.dedup();
+        return words;
+    });
+}
+
+/// Induces a code from the most frequent in-frame tuples of a sequence
+///
+/// The sequence (e.g. the concatenated genes of a genome) is split into tuples of length
+/// \emph{tuple_length} in the reading frame \emph{frame}, the tuples are counted and the
+/// \emph{top_n} most frequent tuples form the candidate code. This is the way the code X0 was
+/// originally discovered in genes. Ties are broken lexicographically, trailing characters are dropped.
+/// A negative \emph{top_n} or a frame outside of the sequence stops with an error, like \link{code_from_seq}.
+///
+/// @param seq A String, the sequence
+/// @param tuple_length An integer, the tuple length
+/// @param top_n An integer, the number of words of the code
+/// @param frame An integer, the reading frame
+///
+/// @return A list with the induced code (code) and the frequency table of all tuples
+/// with the tuples (word) and their counts (count), sorted by decreasing count.
+///
+/// @examples
+/// induce_code_from_sequence("ACGCGGACGAACACG", 3, 2, 0)
+///
+/// @seealso \link{code_from_seq}
+///
+/// @export
+#[extendr]
+fn induce_code_from_sequence(seq: String, tuple_length: i32, top_n: i32, frame: i32) -> Robj {
+    return guard(|| {
+        let n = tuple_length_from_r(tuple_length);
+        let frame = frame_from_r(frame);
+        let len = seq.chars().count();
+        if len > 0 && frame >= len {
+            split_error_to_r(format!("Frame {} is outside of the sequence of length {}", frame, len));
+        }
+        if top_n < 0 {
+            r_error!("Number of words is not correct: it must not be negative, got {}", top_n);
+        }
+
+        let spectrum = kmer_spectrum(&seq, n, frame);
+        let mut code = spectrum.iter().take(top_n as usize).map(|s| s.0.clone()).collect::<Vec<String>>();
+        code.sort();
+
+        return list!(code = code,
+        word = spectrum.iter().map(|s| s.0.clone()).collect::<Vec<String>>(),
+        count = spectrum.iter().map(|s| s.1 as i32).collect::<Vec<i32>>());
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod sequence;
+    fn code_from_seq_obj;
+    fn code_from_seq_all_frames;
+    fn code_from_seq_frame_union;
+    fn induce_code_from_sequence;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drop_discards_head_and_tail() {
+        let split = split_seq("AACGCGGAC", 3, 1, RemainderPolicy::Drop).unwrap();
+        assert_eq!(split.tuples, vec!["ACG", "CGG"]);
+        assert_eq!((split.head.as_str(), split.tail.as_str()), ("A", "AC"));
+        assert_eq!(split.discarded(), 3);
+    }
+
+    #[test]
+    fn error_rejects_a_tail_only() {
+        assert!(split_seq("ACGCG", 3, 0, RemainderPolicy::Error).is_err());
+        let split = split_seq("AACGCGG", 3, 1, RemainderPolicy::Error).unwrap();
+        assert_eq!(split.tuples, vec!["ACG", "CGG"]);
+        assert_eq!(split.discarded(), 1);
+    }
+
+    #[test]
+    fn wrap_completes_the_last_tuple_with_the_head() {
+        let split = split_seq("TACGCG", 3, 1, RemainderPolicy::Wrap).unwrap();
+        assert_eq!(split.tuples, vec!["ACG", "CGT"]);
+        assert_eq!(split.discarded(), 0);
+        let split = split_seq("TTACGC", 3, 2, RemainderPolicy::Wrap).unwrap();
+        assert_eq!(split.tuples, vec!["ACG", "CTT"]);
+        let split = split_seq("TTTACGC", 3, 3, RemainderPolicy::Wrap).unwrap();
+        assert_eq!(split.tuples, vec!["ACG", "CTT"]);
+        assert_eq!(split.head, "T");
+    }
+
+    #[test]
+    fn frame_outside_of_the_sequence_is_an_error() {
+        assert!(split_seq("ACG", 3, 3, RemainderPolicy::Drop).is_err());
+        assert!(split_seq("ACG", 0, 0, RemainderPolicy::Drop).is_err());
+        assert_eq!(split_seq("", 3, 0, RemainderPolicy::Drop).unwrap(), SeqSplit::default());
+    }
+}
@@ -0,0 +1,279 @@
+use extendr_api::prelude::*;
+
+use crate::guard::guard;
+use crate::lib_utils::new_code_from_vec;
+use crate::mixed_length::word_lengths;
+use crate::random::Rng;
+
+/// A mutated sequence together with the original position of each character
+#[derive(Debug, Clone)]
+pub(crate) struct MutatedSequence {
+    pub(crate) seq: Vec<char>,
+    /// The index of each character in the original sequence (`None` for inserted characters)
+    pub(crate) origin: Vec<Option<usize>>,
+    /// The positions (in `seq`) directly behind each mutation
+    pub(crate) mutations: Vec<usize>,
+}
+
+/// The result of a frame retrieval simulation
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RetrievalStatistics {
+    /// Number of characters read after each mutation until the frame was retrieved (`None` if it was not retrieved)
+    pub(crate) delays: Vec<Option<usize>>,
+    /// Number of mutations after which the correct frame was retrieved
+    pub(crate) correct: usize,
+}
+
+impl RetrievalStatistics {
+    /// Returns the mean delay of all retrieved frames (NaN if no frame was retrieved)
+    pub(crate) fn mean_delay(&self) -> f64 {
+        let retrieved = self.delays.iter().flatten().collect::<Vec<&usize>>();
+        if retrieved.is_empty() {
+            return f64::NAN;
+        }
+
+        return retrieved.iter().map(|d| **d as f64).sum::<f64>() / retrieved.len() as f64;
+    }
+}
+
+/// Returns a sequence of `n_words` randomly chosen words
+///
+/// # Arguments
+/// * `words` a set of words (must not be empty)
+/// * `n_words` the number of concatenated words
+/// * `rng` the random number generator
+pub(crate) fn random_concatenation(words: &[String], n_words: usize, rng: &mut Rng) -> String {
+    return (0..n_words).map(|_| words[rng.below(words.len())].as_str()).collect();
+}
+
+/// Returns a random sequence of `n_words` words
+///
+/// If `circular` is true the sequence is read as a circular sequence (e.g. a plasmid): it is rotated
+/// by a random number of characters, so that it does not necessarily start at a word boundary.
+/// Returns the sequence and the rotation (the index of the first character of the first word).
+///
+/// # Arguments
+/// * `words` a set of words (must not be empty)
+/// * `n_words` the number of concatenated words
+/// * `seed` the seed of the random number generator
+/// * `circular` if true the sequence is rotated
+pub(crate) fn random_sequence(words: &[String], n_words: usize, seed: u64, circular: bool) -> (String, usize) {
+    let mut rng = Rng::new(seed);
+    let chosen = (0..n_words).map(|_| &words[rng.below(words.len())]).collect::<Vec<&String>>();
+    let seq = chosen.iter().flat_map(|w| w.chars()).collect::<Vec<char>>();
+    if !circular || seq.is_empty() {
+        return (seq.iter().collect(), 0);
+    }
+
+    let rotation = rng.below(seq.len());
+    let mut boundary = 0;
+    for w in &chosen {
+        if boundary >= rotation {
+            break;
+        }
+        boundary += w.chars().count();
+    }
+
+    let rotated = seq[rotation..].iter().chain(seq[..rotation].iter()).collect();
+    return (rotated, (boundary - rotation) % seq.len());
+}
+
+/// Introduces random substitutions, insertions and deletions
+///
+/// Each character is mutated with probability `rate`. A mutation is a substitution by another letter of
+/// `alphabet`, an insertion of a random letter or a deletion, each with the same probability.
+///
+/// # Arguments
+/// * `seq` a sequence
+/// * `alphabet` the letters used for substitutions and insertions
+/// * `rate` the mutation probability per character
+/// * `rng` the random number generator
+pub(crate) fn mutate(seq: &str, alphabet: &[char], rate: f64, rng: &mut Rng) -> MutatedSequence {
+    let mut result = MutatedSequence { seq: vec![], origin: vec![], mutations: vec![] };
+    for (i, c) in seq.chars().enumerate() {
+        if alphabet.is_empty() || rng.next_f64() >= rate {
+            result.seq.push(c);
+            result.origin.push(Some(i));
+            continue;
+        }
+
+        match rng.below(3) {
+            0 => {
+                let others = alphabet.iter().filter(|a| **a != c).collect::<Vec<&char>>();
+                let s = if others.is_empty() { c } else { *others[rng.below(others.len())] };
+                result.seq.push(s);
+                result.origin.push(None);
+            }
+            1 => {
+                result.seq.push(alphabet[rng.below(alphabet.len())]);
+                result.origin.push(None);
+                result.seq.push(c);
+                result.origin.push(Some(i));
+            }
+            _ => {}
+        }
+        result.mutations.push(result.seq.len());
+    }
+
+    return result;
+}
+
+/// Returns the number of characters which have to be read from `start` until the reading frame is retrieved
+///
+/// A frame (offset `o` in `0..n`) is ruled out as soon as one complete tuple read in this frame is not a word of the code.
+/// The frame is retrieved when only a single frame is left. Returns the retrieved offset and the number of
+/// characters read, or `None` if the frame is not retrieved before `end`.
+///
+/// # Arguments
+/// * `seq` a sequence
+/// * `words` a set of words of length `n`
+/// * `n` the tuple length
+/// * `start` the first position
+/// * `end` the position where the search stops
+pub(crate) fn retrieve_frame(seq: &[char], words: &[String], n: usize, start: usize, end: usize) -> Option<(usize, usize)> {
+    let mut candidates = (0..n).collect::<Vec<usize>>();
+    let mut pos = start;
+    while pos + n <= end {
+        let tuple = seq[pos..pos + n].iter().collect::<String>();
+        let offset = (pos - start) % n;
+        if candidates.contains(&offset) && !words.contains(&tuple) {
+            candidates.retain(|o| *o != offset);
+        }
+
+        // Every frame has been checked at least once after the first n positions
+        if pos + 1 >= start + n && candidates.len() == 1 {
+            return Some((candidates[0], pos + n - start));
+        }
+
+        pos += 1;
+    }
+
+    return None;
+}
+
+/// Simulates mutations in a sequence built from a code and measures the frame retrieval after each mutation
+///
+/// # Arguments
+/// * `words` a set of words of a common length
+/// * `n_words` the number of words of the simulated sequence
+/// * `rate` the mutation probability per character
+/// * `seed` the seed of the random number generator
+pub(crate) fn frame_retrieval_statistics(words: &[String], n_words: usize, rate: f64, seed: u64) -> Result<RetrievalStatistics, String> {
+    let lengths = word_lengths(words);
+    if lengths.len() != 1 {
+        return Err("The simulation requires a non-empty code with words of a common length".to_string());
+    }
+    let n = lengths[0];
+
+    let mut alphabet = words.iter().flat_map(|w| w.chars()).collect::<Vec<char>>();
+    alphabet.sort_unstable();
+    alphabet.dedup();
+
+    let mut rng = Rng::new(seed);
+    let seq = random_concatenation(words, n_words, &mut rng);
+    let mutated = mutate(&seq, &alphabet, rate, &mut rng);
+
+    let mut stats = RetrievalStatistics::default();
+    for (i, start) in mutated.mutations.iter().enumerate() {
+        let end = mutated.mutations.get(i + 1).copied().unwrap_or(mutated.seq.len());
+        match retrieve_frame(&mutated.seq, words, n, *start, end) {
+            Some((offset, delay)) => {
+                let frame_start = start + offset;
+                let correct = (frame_start..end).step_by(n)
+                    .find_map(|p| mutated.origin[p])
+                    .is_some_and(|o| o % n == 0);
+                if correct {
+                    stats.correct += 1;
+                }
+                stats.delays.push(Some(delay));
+            }
+            None => stats.delays.push(None),
+        }
+    }
+
+    return Ok(stats);
+}
+
+/// Simulates the frame retrieval of a code after mutations.
+///
+/// A random sequence of \emph{n_words} words of the code is generated. Each nucleotide is
+/// mutated with probability \emph{rate} by a substitution, an insertion or a deletion.
+/// After each mutation the sequence is read until only one reading frame is
+/// left in which all tuples are words of the code (frame retrieval). Circular codes
+/// retrieve the frame within a bounded window, which quantifies their error-correcting capability.
+/// The code must consist of words of a common length.
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param n_words An integer, the number of words of the simulated sequence
+/// @param rate A numeric value, the mutation probability per nucleotide
+/// @param seed An integer, the seed of the random number generator
+///
+/// @return A list with the number of mutations (mutations), the number of mutations after which a frame was
+/// retrieved (retrieved) and after which the correct frame was retrieved (correct),
+/// the mean number of nucleotides read until the frame was retrieved (mean_delay) and
+/// all delays (delays, -1 if the frame was not retrieved before the next mutation).
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGG", "AAC"))
+/// simulate_frame_retrieval(code, 1000, 0.01, 42)
+///
+/// @export
+#[extendr]
+fn simulate_frame_retrieval(tuples: Vec<String>, n_words: i32, rate: f64, seed: i32) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        match frame_retrieval_statistics(&code.get_code(), n_words.max(0) as usize, rate, seed as u64) {
+            Ok(stats) => return list!(mutations = stats.delays.len() as i32,
+            retrieved = stats.delays.iter().flatten().count() as i32,
+            correct = stats.correct as i32,
+            mean_delay = stats.mean_delay(),
+            delays = stats.delays.iter().map(|d| d.map_or(-1, |d| d as i32)).collect::<Vec<i32>>()),
+            Err(e) => {
+                r_error!("Simulation failed: {}", e);
+            }
+        }
+    });
+}
+
+/// Generates a random sequence from a code.
+///
+/// The sequence is the concatenation of \emph{n_words} randomly chosen words of the code.
+/// Such sequences are positive controls for scanning and decoding.
+/// If \emph{circular} is true the sequence is treated as circular sequence and
+/// rotated by a random number of nucleotides, i.e. it does not necessarily start with a complete word.
+/// The same seed always results in the same sequence.
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param n_words An integer, the number of words
+/// @param seed An integer, the seed of the random number generator
+/// @param circular A boolean, if true the sequence is rotated randomly
+///
+/// @return A list with the sequence (seq) and the index (starting with 0) of the first nucleotide of the first complete word (frame).
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGG", "AC"))
+/// random_sequence_from_code(code, 10, 42, FALSE)
+///
+/// @export
+#[extendr]
+fn random_sequence_from_code(tuples: Vec<String>, n_words: i32, seed: i32, circular: bool) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let words = code.get_code();
+        if words.is_empty() {
+            r_error!("Code is not correct: the code is empty");
+        }
+
+        let (seq, frame) = random_sequence(&words, n_words.max(0) as usize, seed as u64, circular);
+        return list!(seq = seq, frame = frame as i32);
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod simulation;
+    fn simulate_frame_retrieval;
+    fn random_sequence_from_code;
+}
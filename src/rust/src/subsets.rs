@@ -0,0 +1,205 @@
+use extendr_api::prelude::*;
+
+use crate::guard::guard;
+use crate::lib_utils::{is_circular_words, new_code_from_vec};
+
+/// Returns a maximum size subset of the words which has the property `accept`
+///
+/// `accept` must be hereditary, i.e. every subset of an accepted set is accepted
+/// (e.g. circularity). The search is a branch and bound over all subsets and exact,
+/// but exponential in the worst case.
+///
+/// # Arguments
+/// * `words` a set of words
+/// * `accept` the property
+pub(crate) fn largest_subset_exact(words: &[String], accept: &dyn Fn(&[String]) -> bool) -> Vec<String> {
+    fn search(words: &[String], idx: usize, current: &mut Vec<String>, best: &mut Vec<String>, accept: &dyn Fn(&[String]) -> bool) {
+        if current.len() + (words.len() - idx) <= best.len() {
+            return;
+        }
+        if idx == words.len() {
+            *best = current.clone();
+            return;
+        }
+
+        current.push(words[idx].clone());
+        if accept(current) {
+            search(words, idx + 1, current, best, accept);
+        }
+        current.pop();
+        search(words, idx + 1, current, best, accept);
+    }
+
+    let mut best = Vec::new();
+    search(words, 0, &mut Vec::new(), &mut best, accept);
+    return best;
+}
+
+/// Returns a maximal (not necessarily maximum) subset of the words which has the property `accept`
+///
+/// The words are added greedily in the given order whenever the property still holds.
+///
+/// # Arguments
+/// * `words` a set of words
+/// * `accept` the property
+pub(crate) fn largest_subset_greedy(words: &[String], accept: &dyn Fn(&[String]) -> bool) -> Vec<String> {
+    let mut result = Vec::new();
+    for word in words {
+        result.push(word.clone());
+        if !accept(&result) {
+            result.pop();
+        }
+    }
+
+    return result;
+}
+
+/// Returns a partition of the words into a minimum number of blocks which have the property `accept`
+///
+/// `accept` must be hereditary (see [largest_subset_exact]). Each word is assigned to one of the blocks
+/// so far or to a new block, branches which can not improve the best partition found are cut.
+/// The search is exact, but exponential in the worst case. None if a single word is not accepted.
+///
+/// # Arguments
+/// * `words` a set of words
+/// * `accept` the property
+pub(crate) fn partition_exact(words: &[String], accept: &dyn Fn(&[String]) -> bool) -> Option<Vec<Vec<String>>> {
+    fn search(words: &[String], idx: usize, blocks: &mut Vec<Vec<String>>, best: &mut Vec<Vec<String>>, accept: &dyn Fn(&[String]) -> bool) {
+        if blocks.len() >= best.len() {
+            return;
+        }
+        if idx == words.len() {
+            *best = blocks.clone();
+            return;
+        }
+
+        for i in 0..blocks.len() {
+            blocks[i].push(words[idx].clone());
+            if accept(&blocks[i]) {
+                search(words, idx + 1, blocks, best, accept);
+            }
+            blocks[i].pop();
+        }
+        blocks.push(vec![words[idx].clone()]);
+        search(words, idx + 1, blocks, best, accept);
+        blocks.pop();
+    }
+
+    let mut best = partition_greedy(words, accept)?;
+    search(words, 0, &mut Vec::new(), &mut best, accept);
+    return Some(best);
+}
+
+/// Returns a partition of the words into blocks which have the property `accept`
+///
+/// Each word is added to the first block (in the given order) which still has the property or to a new block,
+/// which results in a small but not necessarily minimum number of blocks. None if a single word is not accepted.
+///
+/// # Arguments
+/// * `words` a set of words
+/// * `accept` the property
+pub(crate) fn partition_greedy(words: &[String], accept: &dyn Fn(&[String]) -> bool) -> Option<Vec<Vec<String>>> {
+    let mut blocks: Vec<Vec<String>> = Vec::new();
+    for word in words {
+        let mut placed = false;
+        for block in blocks.iter_mut() {
+            block.push(word.clone());
+            if accept(block) {
+                placed = true;
+                break;
+            }
+            block.pop();
+        }
+        if !placed {
+            if !accept(std::slice::from_ref(word)) {
+                return None;
+            }
+            blocks.push(vec![word.clone()]);
+        }
+    }
+
+    return Some(blocks);
+}
+
+/// Returns a largest circular subset of a code
+///
+/// If a code is not circular this function returns its best circular core,
+/// i.e. a subset of maximum size which is a circular code. The exact search is a
+/// branch and bound over all subsets which is only feasible for small codes.
+/// For larger codes the greedy search (\emph{exact = FALSE}) adds the words in the
+/// given order as long as the code stays circular, which results in a maximal but not
+/// necessarily maximum circular subset.
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param exact A boolean, if true the exact search is used
+///
+/// @return A String vector, the circular subset.
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGA", "AC", "GAC"))
+/// largest_circular_subset(code, TRUE)
+///
+/// @seealso \link{is_code_circular}
+///
+/// @export
+#[extendr]
+fn largest_circular_subset(tuples: Vec<String>, exact: bool) -> Vec<String> {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let words = code.get_code();
+        if exact {
+            return largest_subset_exact(&words, &is_circular_words);
+        }
+
+        return largest_subset_greedy(&words, &is_circular_words);
+    });
+}
+
+/// Partitions a code into circular codes
+///
+/// If a code is not circular, e.g. a code derived from a genome, this function splits it into a
+/// minimum number of blocks such that each block is a circular code. The number of blocks measures
+/// how far the code is from being circular. The exact search assigns each word to one of the
+/// blocks so far or to a new block and is only feasible for small codes.
+/// For larger codes the greedy search (\emph{exact = FALSE}) adds each word to the first block
+/// which stays circular, which results in a small but not necessarily minimum number of blocks.
+/// A word which is not a circular code by itself (e.g. \emph{AAA}) can not be placed in any block.
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param exact A boolean, if true the exact search is used
+///
+/// @return A list of String vectors, the circular blocks.
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGA", "AC", "GAC"))
+/// partition_into_circular(code, TRUE)
+///
+/// @seealso \link{largest_circular_subset}, \link{is_code_circular}
+///
+/// @export
+#[extendr]
+fn partition_into_circular(tuples: Vec<String>, exact: bool) -> Vec<Robj> {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let words = code.get_code();
+        let blocks = match exact {
+            true => partition_exact(&words, &is_circular_words),
+            false => partition_greedy(&words, &is_circular_words),
+        };
+        match blocks {
+            Some(blocks) => return blocks.iter().map(|b| b.iter().collect_robj()).collect(),
+            None => {
+                r_error!("Code can not be partitioned into circular codes: a word is not a circular code by itself");
+            }
+        }
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod subsets;
+    fn largest_circular_subset;
+    fn partition_into_circular;
+}
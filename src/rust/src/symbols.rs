@@ -0,0 +1,239 @@
+use extendr_api::prelude::*;
+
+use crate::guard::guard;
+
+/// The first letter of the Unicode private use area, the letters of multi-character symbols start here
+const FIRST_SYMBOL_LETTER: u32 = 0xE000;
+/// The last letter of the Unicode private use area
+const LAST_SYMBOL_LETTER: u32 = 0xF8FF;
+
+/// Declared symbols of an alphabet, e.g. codons or labeled bases like `m6A`
+///
+/// The library splits words into chars, so a word over multi-character symbols is encoded by
+/// replacing each multi-character symbol with a letter of the Unicode private use area.
+/// Single-character symbols are kept. Since each symbol is a single letter of the encoded word,
+/// tuple lengths, the splits of G(X) and circular shifts count symbols instead of chars.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Symbols {
+    /// The symbols in their declared order
+    symbols: Vec<String>,
+    /// The letter of each symbol in the encoded words
+    letters: Vec<char>,
+}
+
+impl Symbols {
+    /// Returns the declared symbols
+    ///
+    /// Returns an error if there are no symbols, a symbol is empty or declared twice, a symbol uses
+    /// a letter of the private use area or there are too many multi-character symbols.
+    ///
+    /// # Arguments
+    /// * `symbols` the symbols in their order
+    pub(crate) fn new(symbols: &[String]) -> Result<Symbols, String> {
+        if symbols.is_empty() {
+            return Err("No symbols are declared".to_string());
+        }
+        let mut letters = Vec::with_capacity(symbols.len());
+        let mut next = FIRST_SYMBOL_LETTER;
+        for (idx, s) in symbols.iter().enumerate() {
+            if s.is_empty() {
+                return Err(format!("Symbol {} is empty", idx + 1));
+            }
+            if symbols[..idx].contains(s) {
+                return Err(format!("Symbol {} ('{}') is declared twice", idx + 1, s));
+            }
+            if s.chars().any(is_symbol_letter) {
+                return Err(format!("Symbol {} ('{}') contains a letter of the private use area", idx + 1, s));
+            }
+            let mut chars = s.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => letters.push(c),
+                _ => {
+                    if next > LAST_SYMBOL_LETTER {
+                        return Err(format!("More than {} multi-character symbols are declared", LAST_SYMBOL_LETTER - FIRST_SYMBOL_LETTER + 1));
+                    }
+                    letters.push(char::from_u32(next).unwrap_or(char::REPLACEMENT_CHARACTER));
+                    next += 1;
+                }
+            }
+        }
+
+        return Ok(Symbols { symbols: symbols.to_vec(), letters });
+    }
+
+    /// Splits a word into the declared symbols
+    ///
+    /// Returns an error if the word can not be split or can be split in more than one way
+    /// (e.g. `ACG` over the symbols `A`, `AC`, `G` and `CG`).
+    ///
+    /// # Arguments
+    /// * `word` the word to split
+    pub(crate) fn tokenize(&self, word: &str) -> Result<Vec<usize>, String> {
+        let chars = word.chars().collect::<Vec<char>>();
+        let symbols = self.symbols.iter().map(|s| s.chars().collect::<Vec<char>>()).collect::<Vec<Vec<char>>>();
+        // The number of splits of each suffix (at most 2) and the first symbol of a split
+        let mut splits = vec![0u8; chars.len() + 1];
+        let mut first = vec![0; chars.len()];
+        splits[chars.len()] = 1;
+        for pos in (0..chars.len()).rev() {
+            for (idx, s) in symbols.iter().enumerate() {
+                if chars[pos..].starts_with(s) && splits[pos + s.len()] > 0 {
+                    splits[pos] = (splits[pos] + splits[pos + s.len()]).min(2);
+                    first[pos] = idx;
+                }
+            }
+        }
+
+        match splits[0] {
+            0 => {
+                // The first position behind the longest prefix which can be split
+                let mut reached = vec![false; chars.len() + 1];
+                reached[0] = true;
+                for pos in 0..chars.len() {
+                    if reached[pos] {
+                        for s in symbols.iter().filter(|s| chars[pos..].starts_with(s)) {
+                            reached[pos + s.len()] = true;
+                        }
+                    }
+                }
+                let pos = (0..chars.len()).rev().find(|p| reached[*p]).unwrap_or(0);
+                return Err(format!("'{}' can not be split into the declared symbols (at position {})", word, pos + 1));
+            }
+            1 => {
+                let mut tokens = Vec::new();
+                let mut pos = 0;
+                while pos < chars.len() {
+                    tokens.push(first[pos]);
+                    pos += symbols[first[pos]].len();
+                }
+                return Ok(tokens);
+            }
+            _ => return Err(format!("'{}' can be split into the declared symbols in more than one way", word)),
+        }
+    }
+
+    /// Returns the symbols of a word
+    ///
+    /// # Arguments
+    /// * `word` the word to split, see [Symbols::tokenize]
+    pub(crate) fn split(&self, word: &str) -> Result<Vec<String>, String> {
+        return Ok(self.tokenize(word)?.into_iter().map(|i| self.symbols[i].clone()).collect());
+    }
+
+    /// Returns the encoded word, i.e. each symbol is replaced by its letter
+    ///
+    /// # Arguments
+    /// * `word` the word to encode, see [Symbols::tokenize]
+    pub(crate) fn encode(&self, word: &str) -> Result<String, String> {
+        return Ok(self.tokenize(word)?.into_iter().map(|i| self.letters[i]).collect());
+    }
+
+    /// Returns all encoded words, the error names the first word which can not be encoded
+    ///
+    /// # Arguments
+    /// * `words` a set of words
+    pub(crate) fn encode_all(&self, words: &[String]) -> Result<Vec<String>, String> {
+        return words.iter().enumerate()
+            .map(|(idx, w)| self.encode(w).map_err(|e| format!("Word {}: {}", idx + 1, e)))
+            .collect();
+    }
+
+    /// Returns the decoded text, i.e. each letter of a multi-character symbol is replaced by the symbol
+    ///
+    /// All other letters are kept, so labels like `ACG:1` of the library are decoded as well.
+    ///
+    /// # Arguments
+    /// * `s` an encoded word or label
+    pub(crate) fn decode(&self, s: &str) -> String {
+        return s.chars().map(|c| match self.letters.iter().position(|l| *l == c && is_symbol_letter(c)) {
+            Some(i) => self.symbols[i].clone(),
+            None => c.to_string(),
+        }).collect();
+    }
+}
+
+/// Returns true if the letter encodes a multi-character symbol
+fn is_symbol_letter(c: char) -> bool {
+    return (FIRST_SYMBOL_LETTER..=LAST_SYMBOL_LETTER).contains(&(c as u32));
+}
+
+/// Returns the declared symbols or stops with an R error
+fn symbols_or_stop(symbols: &[String]) -> Symbols {
+    match Symbols::new(symbols) {
+        Ok(symbols) => return symbols,
+        Err(e) => {
+            r_error!("Symbols are not correct: {}", e);
+        }
+    }
+}
+
+/// Splits words into declared symbols
+///
+/// This function splits each word into the declared symbols of an alphabet, e.g. codons or
+/// labeled bases like "m6A". A word which can not be split, or can be split in more than one way,
+/// stops the execution with a precise error.
+///
+/// @param tuples A String vector, a set of words
+/// @param symbols A String vector, the declared symbols, e.g. c("A", "C", "G", "U", "m6A")
+///
+/// @return A list of String vectors with the symbols of each word.
+///
+/// @seealso \link{code_over_symbols}
+///
+/// @examples
+/// tokenize_words(c("m6ACG", "CGm6A"), c("A", "C", "G", "U", "m6A"))
+///
+/// @export
+#[extendr]
+fn tokenize_words(tuples: Vec<String>, symbols: Vec<String>) -> Vec<Robj> {
+    return guard(|| {
+        let symbols = symbols_or_stop(&symbols);
+
+        let mut result = Vec::with_capacity(tuples.len());
+        for t in &tuples {
+            match symbols.split(t) {
+                Ok(tokens) => result.push(tokens.iter().collect_robj()),
+                Err(e) => {
+                    r_error!("Word is not correct: {}", e);
+                }
+            }
+        }
+
+        return result;
+    });
+}
+
+/// Decodes words and labels of a code over declared symbols
+///
+/// This function replaces each letter which stands for a multi-character symbol in the result
+/// of \link{code_over_symbols}, or in the results of any analysis of such a code (e.g. the vertices of
+/// \link{get_representing_graph}), by the symbol. The symbols have to be declared in the same order.
+///
+/// @param tuples A String vector, encoded words or labels
+/// @param symbols A String vector, the declared symbols, e.g. c("A", "C", "G", "U", "m6A")
+///
+/// @return A String vector with the decoded words or labels.
+///
+/// @seealso \link{code_over_symbols}
+///
+/// @examples
+/// symbols <- c("A", "C", "G", "U", "m6A")
+/// decode_symbols(code_over_symbols(c("m6ACG", "CGm6A"), symbols), symbols)
+///
+/// @export
+#[extendr]
+fn decode_symbols(tuples: Vec<String>, symbols: Vec<String>) -> Vec<String> {
+    return guard(|| {
+        let symbols = symbols_or_stop(&symbols);
+        return tuples.iter().map(|t| symbols.decode(t)).collect();
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod symbols;
+    fn tokenize_words;
+    fn decode_symbols;
+}
@@ -0,0 +1,219 @@
+// Property-based testing support (only with the `testing` feature).
+//
+// The generators produce random codes and sequences as proptest strategies, so downstream crates can
+// reuse them. The invariants compare the implementations of the code properties against each other
+// and against the hierarchy of the properties, so an algorithmic disagreement is reported with a
+// shrunk counterexample.
+
+use proptest::collection::{btree_set, vec};
+use proptest::prelude::*;
+use proptest::sample::select;
+use proptest::test_runner::{Config, TestCaseError, TestRunner};
+
+use crate::automaton::{is_unambiguous, FlowerAutomaton};
+use crate::comma_free::{is_k_comma_free, suffix_prefix_overlap};
+use crate::gcat::CircCode;
+use crate::longest_path::LongestPathMemo;
+use crate::word_graph::shortest_cycle_within;
+
+/// Returns a strategy for words of the length `length` over the alphabet
+///
+/// # Arguments
+/// * `alphabet` the letters
+/// * `length` the word length
+pub fn word(alphabet: Vec<char>, length: usize) -> impl Strategy<Value = String> {
+    return vec(select(alphabet), length).prop_map(|c| c.into_iter().collect());
+}
+
+/// Returns a strategy for codes of words of the length `length`, i.e. for block codes (sorted, without duplicates)
+///
+/// # Arguments
+/// * `alphabet` the letters
+/// * `length` the word length
+/// * `max_words` the maximal number of words, at least one word is generated
+pub fn code(alphabet: Vec<char>, length: usize, max_words: usize) -> impl Strategy<Value = Vec<String>> {
+    return btree_set(word(alphabet, length), 1..=max_words.max(1)).prop_map(|s| s.into_iter().collect());
+}
+
+/// Returns a strategy for codes with words of the lengths `min_length` to `max_length` (sorted, without duplicates)
+///
+/// # Arguments
+/// * `alphabet` the letters
+/// * `min_length` the minimal word length (at least 1)
+/// * `max_length` the maximal word length
+/// * `max_words` the maximal number of words, at least one word is generated
+pub fn mixed_code(alphabet: Vec<char>, min_length: usize, max_length: usize, max_words: usize) -> impl Strategy<Value = Vec<String>> {
+    let lengths = min_length.max(1)..=max_length.max(min_length.max(1));
+    let any_word = lengths.prop_flat_map(move |l| word(alphabet.clone(), l));
+    return btree_set(any_word, 1..=max_words.max(1)).prop_map(|s| s.into_iter().collect());
+}
+
+/// Returns a strategy for sequences over the alphabet
+///
+/// # Arguments
+/// * `alphabet` the letters
+/// * `max_length` the maximal length of a sequence
+pub fn sequence(alphabet: Vec<char>, max_length: usize) -> impl Strategy<Value = String> {
+    return vec(select(alphabet), 0..=max_length).prop_map(|c| c.into_iter().collect());
+}
+
+/// Returns a strategy for sequences of concatenated code words together with the code
+///
+/// # Arguments
+/// * `codes` a strategy for codes
+/// * `max_words` the maximal number of concatenated words
+pub fn code_sequence(codes: impl Strategy<Value = Vec<String>>, max_words: usize) -> impl Strategy<Value = (Vec<String>, String)> {
+    return codes.prop_flat_map(move |words| {
+        let concatenation = vec(select(words.clone()), 0..=max_words).prop_map(|w| w.concat());
+        (Just(words), concatenation)
+    });
+}
+
+/// Returns a description of the first violated invariant of the code properties, `None` if all hold
+///
+/// The invariants are the agreement of the independent implementations of strong comma-freeness (word overlaps),
+/// comma-freeness (concatenations of two words), circularity (cycles of G(X)) and the code property (flower automaton),
+/// and the hierarchy strong comma-free ⇒ comma-free ⇒ circular ⇒ k-circular (for `k = 1..=max_k`) ⇒ (k-1)-circular.
+/// For block codes (all words have the same length) circular codes must also be codes.
+///
+/// # Arguments
+/// * `words` a set of words
+/// * `max_k` the maximal checked k of the k-circularity
+pub fn invariant_violation(words: &[String], max_k: usize) -> Option<String> {
+    let code = match CircCode::new_from_vec(words.to_vec()) {
+        Ok(code) => code,
+        Err(e) => return Some(format!("The words are rejected: {}", e)),
+    };
+    let strong_comma_free = code.is_strong_comma_free();
+    let comma_free = code.is_comma_free();
+    let circular = code.is_circular();
+    let is_code = code.is_code();
+
+    if strong_comma_free != suffix_prefix_overlap(words).is_none() {
+        return Some(format!("Strong comma-freeness disagrees with the word overlaps (library: {})", strong_comma_free));
+    }
+    if comma_free != is_k_comma_free(words, 2) {
+        return Some(format!("Comma-freeness disagrees with the concatenations of two words (library: {})", comma_free));
+    }
+    if circular != LongestPathMemo::new(words).is_acyclic() {
+        return Some(format!("Circularity disagrees with the cycles of G(X) (library: {})", circular));
+    }
+    if is_code != is_unambiguous(&FlowerAutomaton::new(words)) {
+        return Some(format!("The code property disagrees with the flower automaton (library: {})", is_code));
+    }
+    if strong_comma_free && !comma_free {
+        return Some("The code is strong comma-free but not comma-free".to_string());
+    }
+    if comma_free && !circular {
+        return Some("The code is comma-free but not circular".to_string());
+    }
+    let block = words.iter().all(|w| w.chars().count() == words[0].chars().count());
+    if block && circular && !is_code {
+        return Some("The block code is circular but not a code".to_string());
+    }
+    let mut previous = true;
+    for k in 1..=max_k {
+        let k_circular = shortest_cycle_within(words, 2 * k).is_none();
+        if circular && !k_circular {
+            return Some(format!("The code is circular but not {}-circular", k));
+        }
+        if k_circular && !previous {
+            return Some(format!("The code is {}-circular but not {}-circular", k, k - 1));
+        }
+        previous = k_circular;
+    }
+
+    return None;
+}
+
+/// Checks the invariants (see [invariant_violation]) for random codes
+///
+/// Returns the shrunk counterexample and the violated invariant if a check fails.
+///
+/// # Arguments
+/// * `codes` a strategy for codes, e.g. [code] or [mixed_code]
+/// * `cases` the number of generated codes
+/// * `max_k` the maximal checked k of the k-circularity
+pub fn check_invariants(codes: impl Strategy<Value = Vec<String>>, cases: u32, max_k: usize) -> Result<(), String> {
+    // Outside of a test there is no source file to persist failures next to
+    let mut runner = TestRunner::new(Config { failure_persistence: None, ..Config::with_cases(cases) });
+    return runner.run(&codes, |words| match invariant_violation(&words, max_k) {
+        Some(violation) => Err(TestCaseError::fail(violation)),
+        None => Ok(()),
+    }).map_err(|e| e.to_string());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DNA: [char; 4] = ['A', 'C', 'G', 'T'];
+
+    /// Returns true if the sequence is a concatenation of words of the code
+    fn is_concatenation(words: &[String], seq: &str) -> bool {
+        let mut reachable = vec![false; seq.len() + 1];
+        reachable[0] = true;
+        for i in 0..seq.len() {
+            if reachable[i] {
+                for w in words.iter().filter(|w| seq[i..].starts_with(w.as_str())) {
+                    reachable[i + w.len()] = true;
+                }
+            }
+        }
+
+        return reachable[seq.len()];
+    }
+
+    proptest! {
+        #[test]
+        fn words_have_the_length_over_the_alphabet(w in word(DNA.to_vec(), 3)) {
+            prop_assert_eq!(w.chars().count(), 3);
+            prop_assert!(w.chars().all(|c| DNA.contains(&c)));
+        }
+
+        #[test]
+        fn codes_are_sorted_block_codes(words in code(DNA.to_vec(), 3, 10)) {
+            prop_assert!(!words.is_empty() && words.len() <= 10);
+            prop_assert!(words.windows(2).all(|w| w[0] < w[1]));
+            prop_assert!(words.iter().all(|w| w.chars().count() == 3));
+        }
+
+        #[test]
+        fn mixed_codes_have_the_word_lengths(words in mixed_code(DNA.to_vec(), 2, 4, 10)) {
+            prop_assert!(!words.is_empty() && words.len() <= 10);
+            prop_assert!(words.windows(2).all(|w| w[0] < w[1]));
+            prop_assert!(words.iter().all(|w| (2..=4).contains(&w.chars().count())));
+        }
+
+        #[test]
+        fn sequences_are_limited(seq in sequence(DNA.to_vec(), 20)) {
+            prop_assert!(seq.len() <= 20);
+            prop_assert!(seq.chars().all(|c| DNA.contains(&c)));
+        }
+
+        #[test]
+        fn code_sequences_are_concatenations((words, seq) in code_sequence(mixed_code(DNA.to_vec(), 2, 4, 6), 8)) {
+            prop_assert!(seq.len() <= 8 * 4);
+            prop_assert!(is_concatenation(&words, &seq));
+        }
+
+        #[test]
+        fn strong_comma_free_codes_are_comma_free(words in code(DNA.to_vec(), 3, 8)) {
+            if suffix_prefix_overlap(&words).is_none() {
+                prop_assert!(is_k_comma_free(&words, 2));
+            }
+        }
+
+        #[test]
+        fn circular_codes_are_k_circular(words in mixed_code(DNA.to_vec(), 2, 3, 8)) {
+            if LongestPathMemo::new(&words).is_acyclic() {
+                prop_assert!((1..=4).all(|k| shortest_cycle_within(&words, 2 * k).is_none()));
+            }
+        }
+    }
+
+    #[test]
+    fn invariants_hold_for_random_trinucleotide_codes() {
+        assert_eq!(check_invariants(code(DNA.to_vec(), 3, 12), 64, 4), Ok(()));
+    }
+}
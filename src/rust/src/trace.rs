@@ -0,0 +1,45 @@
+// Tracing instrumentation. Without the `trace` feature all macros expand to nothing,
+// so the instrumentation is compiled out.
+
+/// Enters a span which is left at the end of the enclosing block
+#[cfg(feature = "trace")]
+macro_rules! trace_span {
+    ($($arg:tt)*) => {
+        let _trace_span = tracing::debug_span!($($arg)*).entered();
+    };
+}
+
+#[cfg(not(feature = "trace"))]
+macro_rules! trace_span {
+    ($($arg:tt)*) => {};
+}
+
+/// Emits an event, e.g. the number of explored states
+#[cfg(feature = "trace")]
+macro_rules! trace_event {
+    ($($arg:tt)*) => {
+        tracing::debug!($($arg)*);
+    };
+}
+
+#[cfg(not(feature = "trace"))]
+macro_rules! trace_event {
+    ($($arg:tt)*) => {};
+}
+
+/// Installs a subscriber printing all spans and events to stderr
+///
+/// Returns false if the `trace` feature is not compiled in or a subscriber is already installed.
+#[cfg(feature = "trace")]
+pub(crate) fn init_trace_log() -> bool {
+    return tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::DEBUG)
+        .with_writer(std::io::stderr)
+        .try_init()
+        .is_ok();
+}
+
+#[cfg(not(feature = "trace"))]
+pub(crate) fn init_trace_log() -> bool {
+    return false;
+}
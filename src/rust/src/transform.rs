@@ -0,0 +1,703 @@
+use extendr_api::prelude::*;
+
+use crate::alphabet::Alphabet;
+use crate::gcat::CircCode;
+use crate::guard::guard;
+use crate::lib_utils::new_code_from_vec;
+
+/// Returns true if the words are written in the RNA alphabet
+///
+/// A set of words is treated as RNA if it contains at least one `U` and no `T`.
+/// Mixed input (`T` and `U`) is treated as DNA.
+///
+/// # Arguments
+/// * `words` a set of words
+pub(crate) fn is_rna(words: &[String]) -> bool {
+    let has_u = words.iter().any(|w| w.contains(['U', 'u']));
+    let has_t = words.iter().any(|w| w.contains(['T', 't']));
+    return has_u && !has_t;
+}
+
+/// Replaces every `T` by `U` (case is preserved)
+///
+/// # Arguments
+/// * `words` a set of words
+pub(crate) fn words_to_rna(words: &[String]) -> Vec<String> {
+    return words.iter().map(|w| w.chars().map(|c| match c {
+        'T' => 'U',
+        't' => 'u',
+        _ => c,
+    }).collect()).collect();
+}
+
+/// Replaces every `U` by `T` (case is preserved)
+///
+/// # Arguments
+/// * `words` a set of words
+pub(crate) fn words_to_dna(words: &[String]) -> Vec<String> {
+    return words.iter().map(|w| w.chars().map(|c| match c {
+        'U' => 'T',
+        'u' => 't',
+        _ => c,
+    }).collect()).collect();
+}
+
+/// Returns the (letter wise) complement of each word
+///
+/// The words are normalized to DNA first, so `T` and `U` can be mixed.
+/// If the input is pure RNA the result is RNA again.
+///
+/// # Arguments
+/// * `words` a set of words
+pub(crate) fn words_complement(words: &[String]) -> Vec<String> {
+    let dna = Alphabet::dna();
+    let complement = words_to_dna(words).iter()
+        .map(|w| w.chars().map(|c| dna.complement(c).unwrap_or(c)).collect())
+        .collect::<Vec<String>>();
+
+    if is_rna(words) {
+        return words_to_rna(&complement);
+    }
+
+    return complement;
+}
+
+/// Returns each word reversed (mirror image)
+///
+/// # Arguments
+/// * `words` a set of words
+pub(crate) fn words_reversed(words: &[String]) -> Vec<String> {
+    return words.iter().map(|w| w.chars().rev().collect()).collect();
+}
+
+/// Returns all words which are equal to their mirror image
+///
+/// # Arguments
+/// * `words` a set of words
+pub(crate) fn palindromes(words: &[String]) -> Vec<String> {
+    return words.iter().filter(|w| w.chars().eq(w.chars().rev())).cloned().collect();
+}
+
+/// Returns the reverse complement of each word
+///
+/// # Arguments
+/// * `words` a set of words
+pub(crate) fn words_reverse_complement(words: &[String]) -> Vec<String> {
+    return words_reversed(&words_complement(words));
+}
+
+/// A transformation of a set of words, e.g. a symmetry of the nucleotide alphabet
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Transformation {
+    Identity,
+    /// Letter wise complement (A <-> T/U, C <-> G)
+    Complement,
+    /// Each word is reversed
+    Reversal,
+    /// Complement composed with reversal
+    ReverseComplement,
+    /// Each letter `from[i]` is replaced by `to[i]`, optionally followed by a reversal of each word
+    Permutation { from: Vec<char>, to: Vec<char>, reverse: bool },
+}
+
+impl Transformation {
+    /// Parses a transformation
+    ///
+    /// Accepted are the names `identity`, `complement`, `reverse` and `reverse_complement`
+    /// as well as letter permutations like `ACGT>TGCA`. A permutation prefixed with `rev:`
+    /// (e.g. `rev:ACGT>TGCA`) reverses each word after the letters are replaced.
+    pub fn parse(s: &str) -> Result<Transformation, String> {
+        match s.trim() {
+            "identity" => return Ok(Transformation::Identity),
+            "complement" => return Ok(Transformation::Complement),
+            "reverse" => return Ok(Transformation::Reversal),
+            "reverse_complement" => return Ok(Transformation::ReverseComplement),
+            _ => {}
+        }
+
+        let (reverse, mapping) = match s.trim().strip_prefix("rev:") {
+            Some(m) => (true, m),
+            None => (false, s.trim()),
+        };
+        let (from, to) = match mapping.split_once('>') {
+            Some((from, to)) => (from.chars().collect::<Vec<char>>(), to.chars().collect::<Vec<char>>()),
+            None => return Err(format!("Unknown transformation '{}'", s)),
+        };
+
+        let mut sorted_from = from.clone();
+        sorted_from.sort_unstable();
+        sorted_from.dedup();
+        let mut sorted_to = to.clone();
+        sorted_to.sort_unstable();
+        if from.len() != to.len() || sorted_from.len() != from.len() || sorted_from != sorted_to {
+            return Err(format!("'{}' is not a permutation of the letters '{}'", mapping, from.iter().collect::<String>()));
+        }
+
+        return Ok(Transformation::Permutation { from, to, reverse });
+    }
+
+    /// Applies the transformation to each word
+    pub fn apply(&self, words: &[String]) -> Vec<String> {
+        match self {
+            Transformation::Identity => words.to_vec(),
+            Transformation::Complement => words_complement(words),
+            Transformation::Reversal => words_reversed(words),
+            Transformation::ReverseComplement => words_reverse_complement(words),
+            Transformation::Permutation { from, to, reverse } => {
+                let replaced = words.iter().map(|w| w.chars().map(|c| match from.iter().position(|f| *f == c) {
+                    Some(i) => to[i],
+                    None => c,
+                }).collect()).collect::<Vec<String>>();
+                if *reverse {
+                    words_reversed(&replaced)
+                } else {
+                    replaced
+                }
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Transformation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Transformation::Identity => write!(f, "identity"),
+            Transformation::Complement => write!(f, "complement"),
+            Transformation::Reversal => write!(f, "reverse"),
+            Transformation::ReverseComplement => write!(f, "reverse_complement"),
+            Transformation::Permutation { from, to, reverse } => write!(f, "{}{}>{}",
+                                                                        if *reverse { "rev:" } else { "" },
+                                                                        from.iter().collect::<String>(),
+                                                                        to.iter().collect::<String>()),
+        }
+    }
+}
+
+/// Returns true if the set of words is invariant under the transformation, i.e. T(X) = X
+///
+/// # Arguments
+/// * `words` a set of words
+/// * `t` a transformation
+pub(crate) fn is_invariant_under(words: &[String], t: &Transformation) -> bool {
+    return same_words(words, &t.apply(words));
+}
+
+/// Returns true if the set of words is invariant under the reverse complement (self-complementary)
+pub(crate) fn is_invariant_under_reverse_complement(words: &[String]) -> bool {
+    return is_invariant_under(words, &Transformation::ReverseComplement);
+}
+
+/// Returns all permutations of the given letters
+fn letter_permutations(letters: &[char]) -> Vec<Vec<char>> {
+    if letters.len() <= 1 {
+        return vec![letters.to_vec()];
+    }
+
+    let mut result = Vec::new();
+    for (i, c) in letters.iter().enumerate() {
+        let mut rest = letters.to_vec();
+        rest.remove(i);
+        for mut p in letter_permutations(&rest) {
+            p.insert(0, *c);
+            result.push(p);
+        }
+    }
+
+    return result;
+}
+
+/// The maximal number of letters whose permutations are enumerated (6! = 720 permutations)
+pub(crate) const MAX_PERMUTED_LETTERS: usize = 6;
+
+/// Returns all letter permutations (with and without reversal) under which the set of words is invariant
+///
+/// The permutations are built over the letters used in `words`. Together they form the
+/// symmetry subgroup of the code. The number of permutations is factorial in the number of letters,
+/// so an error is returned if the words use more than [MAX_PERMUTED_LETTERS] letters.
+///
+/// # Arguments
+/// * `words` a set of words
+pub(crate) fn invariant_transformations(words: &[String]) -> Result<Vec<Transformation>, String> {
+    let mut letters = words.iter().flat_map(|w| w.chars()).collect::<Vec<char>>();
+    letters.sort_unstable();
+    letters.dedup();
+    if letters.len() > MAX_PERMUTED_LETTERS {
+        return Err(format!("The code uses {} letters, the permutations are only enumerated for at most {} letters",
+                           letters.len(), MAX_PERMUTED_LETTERS));
+    }
+
+    let mut result = Vec::new();
+    for reverse in [false, true] {
+        for to in letter_permutations(&letters) {
+            let t = Transformation::Permutation { from: letters.clone(), to, reverse };
+            if is_invariant_under(words, &t) {
+                result.push(t);
+            }
+        }
+    }
+
+    return Ok(result);
+}
+
+/// Returns the images of a set of words under all 24 permutations of the nucleotides
+///
+/// The permutations are applied to the letters `ACGT` (or `ACGU` for RNA, see [is_rna]), the first one
+/// is the identity. Each image is sorted.
+///
+/// # Arguments
+/// * `words` a set of words
+pub(crate) fn nucleotide_orbit(words: &[String]) -> Vec<(Transformation, Vec<String>)> {
+    let letters = Alphabet::nucleotides_of(words).letters().to_vec();
+    return letter_permutations(&letters).into_iter().map(|to| {
+        let t = Transformation::Permutation { from: letters.clone(), to, reverse: false };
+        let mut image = t.apply(words);
+        image.sort();
+        (t, image)
+    }).collect();
+}
+
+/// Returns all circular permutations (rotations) of a word, starting with the word itself
+///
+/// # Arguments
+/// * `word` a word
+pub(crate) fn rotations(word: &str) -> Vec<String> {
+    let chars = word.chars().collect::<Vec<char>>();
+    if chars.is_empty() {
+        return vec![String::new()];
+    }
+
+    return (0..chars.len()).map(|i| chars[i..].iter().chain(chars[..i].iter()).collect()).collect();
+}
+
+/// Returns the words circularly shifted by `sh` positions, e.g. `123` shifted by 2 is `312`
+///
+/// Each word is rotated to the left by `sh` modulo its length, so negative shifts rotate to
+/// the right and shifts by a multiple of the word length keep the word.
+///
+/// # Arguments
+/// * `words` a set of words
+/// * `sh` the number of shifts
+pub(crate) fn shift_words(words: &[String], sh: i32) -> Vec<String> {
+    return words.iter().map(|w| {
+        let rotations = rotations(w);
+        rotations[(sh as i64).rem_euclid(rotations.len() as i64) as usize].clone()
+    }).collect();
+}
+
+/// Returns the lexicographically minimal rotation of a word (canonical representative of its conjugacy class)
+///
+/// # Arguments
+/// * `word` a word
+pub(crate) fn canonical_rotation(word: &str) -> String {
+    return rotations(word).into_iter().min().unwrap_or_default();
+}
+
+/// Returns the closure of the words under circular permutation (sorted)
+///
+/// # Arguments
+/// * `words` a set of words
+pub(crate) fn conjugacy_closure(words: &[String]) -> Vec<String> {
+    let mut closure = words.iter().flat_map(|w| rotations(w)).collect::<Vec<String>>();
+    closure.sort();
+    closure.dedup();
+    return closure;
+}
+
+/// Returns all pairs of distinct words belonging to the same conjugacy class
+///
+/// A circular code contains at most one word of each conjugacy class, so each pair
+/// certifies that the words do not form a circular code.
+///
+/// # Arguments
+/// * `words` a set of words
+pub(crate) fn conjugate_pairs(words: &[String]) -> Vec<(String, String)> {
+    let canonical = words.iter().map(|w| canonical_rotation(w)).collect::<Vec<String>>();
+    let mut pairs = Vec::new();
+    for i in 0..words.len() {
+        for j in i + 1..words.len() {
+            if canonical[i] == canonical[j] && words[i] != words[j] {
+                pairs.push((words[i].clone(), words[j].clone()));
+            }
+        }
+    }
+
+    return pairs;
+}
+
+/// Returns why a set of words is not a maximal self-complementary C3 code
+///
+/// The properties are checked from the cheapest to the most expensive one: 20 distinct words,
+/// trinucleotides over {A, C, G, T}, self-complementarity and finally C3 (circularity of the
+/// code and both circular permutations). Returns `None` if all properties hold.
+///
+/// # Arguments
+/// * `words` a set of words (DNA or RNA)
+pub(crate) fn max_self_complementary_c3_violation(words: &[String]) -> Option<String> {
+    let mut words = words_to_dna(words);
+    words.sort();
+    words.dedup();
+    if words.len() != 20 {
+        return Some(format!("The code has {} words instead of 20", words.len()));
+    }
+    if let Some(w) = words.iter().find(|w| w.chars().count() != 3) {
+        return Some(format!("Word '{}' is not a trinucleotide", w));
+    }
+    if let Some(w) = words.iter().find(|w| !Alphabet::dna().covers(w)) {
+        return Some(format!("Word '{}' is not over the alphabet {{A, C, G, T}}", w));
+    }
+    if !is_invariant_under_reverse_complement(&words) {
+        return Some("The code is not self-complementary".to_string());
+    }
+
+    match CircCode::new_from_vec(words) {
+        Ok(code) if code.is_cn_circular() => None,
+        _ => Some("The code is not C3".to_string()),
+    }
+}
+
+/// Returns true if both sets contain the same words (order and duplicates are ignored)
+pub(crate) fn same_words(a: &[String], b: &[String]) -> bool {
+    let mut a = a.to_vec();
+    let mut b = b.to_vec();
+    a.sort();
+    a.dedup();
+    b.sort();
+    b.dedup();
+    return a == b;
+}
+
+/// Converts a code into the RNA alphabet
+///
+/// Every \emph{T} in the code is replaced by an \emph{U}.
+///
+/// @param tuples A gcatbase::gcat.code object
+///
+/// @return A String vector, the code over the RNA alphabet.
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "TGG", "AC"))
+/// code_to_rna(code)
+///
+/// @seealso \link{code_to_dna}
+///
+/// @export
+#[extendr]
+fn code_to_rna(tuples: Vec<String>) -> Vec<String> {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        return words_to_rna(&code.get_code());
+    });
+}
+
+/// Converts a code into the DNA alphabet
+///
+/// Every \emph{U} in the code is replaced by a \emph{T}.
+///
+/// @param tuples A gcatbase::gcat.code object
+///
+/// @return A String vector, the code over the DNA alphabet.
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "UGG", "AC"))
+/// code_to_dna(code)
+///
+/// @seealso \link{code_to_rna}
+///
+/// @export
+#[extendr]
+fn code_to_dna(tuples: Vec<String>) -> Vec<String> {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        return words_to_dna(&code.get_code());
+    });
+}
+
+/// Returns the complementary code
+///
+/// Each nucleotide of each word is replaced by its complement, i.e.
+/// A <-> T (or U) and C <-> G. DNA and RNA input are both accepted. If the code
+/// is written in the RNA alphabet the result is RNA as well.
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param reverse A boolean, if true each complemented word is also reversed (reverse complement).
+///
+/// @return A String vector, the complementary code.
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGU", "AC"))
+/// complementary_code(code, TRUE)
+///
+/// @seealso \link{is_code_self_complementary}
+///
+/// @export
+#[extendr]
+fn complementary_code(tuples: Vec<String>, reverse: bool) -> Vec<String> {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        if reverse {
+            return words_reverse_complement(&code.get_code());
+        }
+
+        return words_complement(&code.get_code());
+    });
+}
+
+/// Check if a code is self-complementary.
+///
+/// A code \emph{X} is self-complementary if it equals its reverse complement, i.e.
+/// for each word in \emph{X} its reversed complementary word is in \emph{X} as well.
+/// Self-complementarity is one of the defining properties of the maximal C3 codes.
+/// DNA and RNA input are both accepted.
+///
+/// @param tuples A gcatbase::gcat.code object
+///
+/// @return Boolean value. True if the code is self-complementary.
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGU"))
+/// is_code_self_complementary(code)
+///
+/// @seealso \link{complementary_code}
+///
+/// @export
+#[extendr]
+fn is_code_self_complementary(tuples: Vec<String>) -> bool {
+    return guard(|| {
+        let code = new_code_from_vec(words_to_dna(&tuples));
+        return is_invariant_under_reverse_complement(&code.get_code());
+    });
+}
+
+/// Check if a code is a maximal self-complementary C3 code.
+///
+/// The 216 maximal self-complementary C3 codes (including the code X of Arquès and Michel)
+/// are the central objects of circular code theory. A code belongs to this class if it consists of
+/// 20 trinucleotides, is self-complementary (see \link{is_code_self_complementary}) and C3
+/// (see \link{is_code_cn_circular}). The properties are checked from the cheapest to the most expensive one
+/// and the check stops at the first violated property.
+/// DNA and RNA input are both accepted.
+///
+/// @param tuples A gcatbase::gcat.code object
+///
+/// @return Boolean value. True if the code is a maximal self-complementary C3 code.
+///
+/// @examples
+/// code <- gcatbase::code(c("AAC", "AAT", "ACC", "ATC", "ATT", "CAG", "CTC", "CTG", "GAA", "GAC",
+///                          "GAG", "GAT", "GCC", "GGC", "GGT", "GTA", "GTC", "GTT", "TAC", "TTC"))
+/// is_code_max_self_complementary_c3(code)
+///
+/// @seealso \link{is_code_self_complementary}, \link{is_code_cn_circular}
+///
+/// @export
+#[extendr]
+fn is_code_max_self_complementary_c3(tuples: Vec<String>) -> bool {
+    return guard(|| {
+        return max_self_complementary_c3_violation(&tuples).is_none();
+    });
+}
+
+/// Returns the reversed code
+///
+/// Each word of the code is reversed (mirror image), i.e.
+/// let \emph{X}={ACG, CGG}, then the reversed code is {GCA, GGC}.
+/// Reversal composed with the complement results in the reverse complement,
+/// see \link{complementary_code}.
+///
+/// @param tuples A gcatbase::gcat.code object
+///
+/// @return A String vector, the reversed code.
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGG", "AC"))
+/// reversed_code(code)
+///
+/// @seealso \link{palindromic_words}
+///
+/// @export
+#[extendr]
+fn reversed_code(tuples: Vec<String>) -> Vec<String> {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        return words_reversed(&code.get_code());
+    });
+}
+
+/// Returns all palindromic words of a code
+///
+/// A word is a palindrome if it equals its reversed word, e.g. \emph{ACA}.
+///
+/// @param tuples A gcatbase::gcat.code object
+///
+/// @return A String vector with all palindromic words.
+///
+/// @examples
+/// code <- gcatbase::code(c("ACA", "CGG", "GG"))
+/// palindromic_words(code)
+///
+/// @seealso \link{reversed_code}
+///
+/// @export
+#[extendr]
+fn palindromic_words(tuples: Vec<String>) -> Vec<String> {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        return palindromes(&code.get_code());
+    });
+}
+
+/// Check if a code is invariant under a transformation.
+///
+/// This function checks if \emph{T(X) = X} for a code \emph{X} and a transformation \emph{T}.
+/// The transformation is either one of the names "identity", "complement", "reverse"
+/// and "reverse_complement" or a letter permutation like "ACGT>TGCA" (A is replaced by T, C by G, ...).
+/// A permutation prefixed with "rev:" (e.g. "rev:ACGT>TGCA") additionally reverses each word.
+///
+/// @param tuples A gcatbase::gcat.code object
+/// @param transformation A String, the transformation
+///
+/// @return Boolean value. True if the code is invariant under the transformation.
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGT"))
+/// is_code_invariant_under(code, "reverse_complement")
+///
+/// @seealso \link{invariant_transformations_of_code}
+///
+/// @export
+#[extendr]
+fn is_code_invariant_under(tuples: Vec<String>, transformation: String) -> bool {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        match Transformation::parse(&transformation) {
+            Ok(t) => return is_invariant_under(&code.get_code(), &t),
+            Err(e) => {
+                r_error!("Transformation is not correct: {}", e);
+            }
+        }
+    });
+}
+
+/// Returns the symmetry subgroup of a code
+///
+/// This function returns all transformations under which the code is invariant.
+/// Checked are all permutations of the letters used by the code, each with and
+/// without reversal of the words. The transformations are written as described in
+/// \link{is_code_invariant_under}. Since the number of permutations grows factorially,
+/// codes over more than 6 letters (e.g. amino acids) are rejected with an error.
+///
+/// @param tuples A gcatbase::gcat.code object
+///
+/// @return A String vector with all transformations which leave the code invariant.
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGT"))
+/// invariant_transformations_of_code(code)
+///
+/// @seealso \link{is_code_invariant_under}
+///
+/// @export
+#[extendr]
+fn invariant_transformations_of_code(tuples: Vec<String>) -> Vec<String> {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        match invariant_transformations(&code.get_code()) {
+            Ok(transformations) => return transformations.iter().map(|t| t.to_string()).collect(),
+            Err(e) => {
+                r_error!("Code is not correct: {}", e);
+            }
+        }
+    });
+}
+
+/// Returns the orbit of a code under the permutations of the nucleotides
+///
+/// @param tuples A gcatbase::gcat.code object
+///
+/// @return A list with the permutations (permutation), the images of the code (codes) and
+/// booleans (equal) which are true if the image is equal to the code.
+///
+/// @seealso \link{code_orbit}
+///
+#[extendr]
+fn code_orbit_obj(tuples: Vec<String>) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let words = code.get_code();
+        let orbit = nucleotide_orbit(&words);
+        return list!(permutation = orbit.iter().map(|o| o.0.to_string()).collect::<Vec<String>>(),
+        codes = orbit.iter().map(|o| o.1.iter().collect_robj()).collect::<Vec<Robj>>(),
+        equal = orbit.iter().map(|o| same_words(&o.1, &words)).collect::<Vec<bool>>());
+    });
+}
+
+/// Returns the conjugacy closure of a code
+///
+/// Two words are conjugated if one is a circular permutation of the other, e.g.
+/// \emph{ACG}, \emph{CGA} and \emph{GAC}. The conjugacy closure of a code \emph{X}
+/// contains all circular permutations of all words of \emph{X}.
+///
+/// @param tuples A gcatbase::gcat.code object
+///
+/// @return A String vector, the conjugacy closure (sorted).
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "AC"))
+/// conjugacy_closure_of_code(code)
+///
+/// @seealso \link{conjugated_words_of_code}
+///
+/// @export
+#[extendr]
+fn conjugacy_closure_of_code(tuples: Vec<String>) -> Vec<String> {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        return conjugacy_closure(&code.get_code());
+    });
+}
+
+/// Returns all pairs of conjugated words of a code
+///
+/// A circular code contains at most one word of each conjugacy class
+/// (see \link{conjugacy_closure_of_code}). Each returned pair is an immediate
+/// certificate that the code is not circular.
+///
+/// @param tuples A gcatbase::gcat.code object
+///
+/// @return A list with two String vectors (first, second). The i-th entries form the i-th pair.
+///
+/// @examples
+/// code <- gcatbase::code(c("ACG", "CGA", "AC"))
+/// conjugated_words_of_code(code)
+///
+/// @seealso \link{is_code_circular}
+///
+/// @export
+#[extendr]
+fn conjugated_words_of_code(tuples: Vec<String>) -> Robj {
+    return guard(|| {
+        let code = new_code_from_vec(tuples);
+        let pairs = conjugate_pairs(&code.get_code());
+        return list!(first = pairs.iter().map(|p| p.0.clone()).collect::<Vec<String>>(),
+        second = pairs.iter().map(|p| p.1.clone()).collect::<Vec<String>>());
+    });
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod transform;
+    fn code_to_rna;
+    fn code_to_dna;
+    fn complementary_code;
+    fn is_code_self_complementary;
+    fn is_code_max_self_complementary_c3;
+    fn reversed_code;
+    fn palindromic_words;
+    fn is_code_invariant_under;
+    fn invariant_transformations_of_code;
+    fn code_orbit_obj;
+    fn conjugacy_closure_of_code;
+    fn conjugated_words_of_code;
+}
@@ -0,0 +1,194 @@
+use std::collections::BTreeMap;
+
+use extendr_api::prelude::*;
+
+use crate::guard::guard;
+use crate::lib_utils::new_code_from_vec;
+
+/// A trie (prefix tree) over the words of a code
+///
+/// Each node stands for a prefix of a word, the root (node 0) for the empty prefix. The children of a node are
+/// ordered by their letter, so all traversals visit the words in lexicographic order. Reading a sequence letter
+/// by letter from a node replaces repeated prefix comparisons with all words by one step per letter.
+/// The decoder and the scanner share this dictionary.
+#[derive(Debug, Clone)]
+pub struct CodeTrie {
+    /// The children of each node by their letter
+    children: Vec<BTreeMap<char, usize>>,
+    /// The index of the word ending in each node
+    terminal: Vec<Option<usize>>,
+    words: Vec<String>,
+}
+
+impl CodeTrie {
+    /// The node of the empty prefix
+    pub(crate) const ROOT: usize = 0;
+
+    /// Returns the trie of a set of words
+    ///
+    /// The word indices refer to `words`. Empty words are ignored, a repeated word keeps its first index.
+    ///
+    /// # Arguments
+    /// * `words` a set of words
+    pub(crate) fn from_words(words: &[String]) -> CodeTrie {
+        let mut trie = CodeTrie { children: vec![BTreeMap::new()], terminal: vec![None], words: words.to_vec() };
+        for (idx, word) in words.iter().enumerate().filter(|(_, w)| !w.is_empty()) {
+            let mut node = CodeTrie::ROOT;
+            for c in word.chars() {
+                node = match trie.children[node].get(&c) {
+                    Some(next) => *next,
+                    None => {
+                        trie.children.push(BTreeMap::new());
+                        trie.terminal.push(None);
+                        let next = trie.children.len() - 1;
+                        trie.children[node].insert(c, next);
+                        next
+                    }
+                };
+            }
+            trie.terminal[node].get_or_insert(idx);
+        }
+
+        return trie;
+    }
+
+    /// Returns the words of the trie (in the order they were given)
+    pub(crate) fn words(&self) -> &[String] {
+        return &self.words;
+    }
+
+    /// Returns the number of nodes including the root
+    pub(crate) fn node_count(&self) -> usize {
+        return self.children.len();
+    }
+
+    /// Returns the node reached from `node` by reading the letter `c`, `None` if no word continues this way
+    pub(crate) fn step(&self, node: usize, c: char) -> Option<usize> {
+        return self.children[node].get(&c).copied();
+    }
+
+    /// Returns the children `(letter, node)` of a node ordered by their letter
+    pub(crate) fn children(&self, node: usize) -> impl Iterator<Item = (char, usize)> + '_ {
+        return self.children[node].iter().map(|(c, n)| (*c, *n));
+    }
+
+    /// Returns the index of the word ending in `node`
+    pub(crate) fn word_at(&self, node: usize) -> Option<usize> {
+        return self.terminal[node];
+    }
+
+    /// Returns true if the prefix of `node` is a proper prefix of a word
+    pub(crate) fn has_children(&self, node: usize) -> bool {
+        return !self.children[node].is_empty();
+    }
+
+    /// Returns the node of a prefix, `None` if it is not a prefix of a word
+    pub(crate) fn find(&self, prefix: &str) -> Option<usize> {
+        return prefix.chars().try_fold(CodeTrie::ROOT, |node, c| self.step(node, c));
+    }
+
+    /// Returns the indices of all words which are prefixes of `s`, ordered by their length
+    ///
+    /// # Arguments
+    /// * `s` a sequence, e.g. the rest of a sequence to factorize
+    pub(crate) fn prefixes_of<'a>(&'a self, s: &'a str) -> impl Iterator<Item = usize> + 'a {
+        return s.chars()
+            .scan(CodeTrie::ROOT, move |node, c| {
+                *node = self.step(*node, c)?;
+                Some(*node)
+            })
+            .filter_map(move |node| self.word_at(node));
+    }
+
+    /// Returns the indices of all words below `node` in lexicographic order
+    fn words_below(&self, node: usize) -> Vec<usize> {
+        let mut result = Vec::new();
+        let mut stack = vec![node];
+        while let Some(n) = stack.pop() {
+            result.extend(self.terminal[n]);
+            stack.extend(self.children[n].values().rev());
+        }
+
+        return result;
+    }
+}
+
+/// A trie (prefix tree) over the words of a code
+///
+/// The trie answers prefix queries on the words of a code, e.g. which words start with a prefix or
+/// which words are prefixes of a sequence, in time linear in the length of the query.
+/// It is the dictionary used by the decoders and the sequence scans of this package.
+///
+/// @examples
+/// trie <- CodeTrie$new(gcatbase::code(c("ACG", "CGG", "AC")))
+/// trie$contains("AC")
+/// trie$words_with_prefix("A")
+/// trie$prefix_words("ACGT")
+///
+/// @export
+#[extendr]
+impl CodeTrie {
+    /// Returns the trie of a code
+    ///
+    /// @param tuples A gcatbase::gcat.code object
+    fn new(tuples: Vec<String>) -> CodeTrie {
+        return guard(|| {
+            let code = new_code_from_vec(tuples);
+            return CodeTrie::from_words(&code.get_code());
+        });
+    }
+
+    /// Returns true if the word is a word of the code
+    ///
+    /// @param word A String
+    fn contains(&self, word: String) -> bool {
+        return guard(|| {
+            return self.find(&word).is_some_and(|node| self.word_at(node).is_some());
+        });
+    }
+
+    /// Returns true if the prefix is a prefix of a word of the code (a word is a prefix of itself)
+    ///
+    /// @param prefix A String
+    fn is_prefix(&self, prefix: String) -> bool {
+        return guard(|| {
+            return self.find(&prefix).is_some();
+        });
+    }
+
+    /// Returns all words of the code starting with the prefix in lexicographic order
+    ///
+    /// @param prefix A String
+    fn words_with_prefix(&self, prefix: String) -> Vec<String> {
+        return guard(|| {
+            return match self.find(&prefix) {
+                Some(node) => self.words_below(node).into_iter().map(|i| self.words[i].clone()).collect(),
+                None => vec![],
+            };
+        });
+    }
+
+    /// Returns all words of the code which are prefixes of the sequence, ordered by their length
+    ///
+    /// @param seq A String
+    fn prefix_words(&self, seq: String) -> Vec<String> {
+        return guard(|| {
+            return self.prefixes_of(&seq).map(|i| self.words[i].clone()).collect();
+        });
+    }
+
+    /// Returns the number of nodes of the trie including the root
+    fn nodes(&self) -> i32 {
+        return guard(|| {
+            return self.node_count() as i32;
+        });
+    }
+}
+
+// Macro to generate exports.
+// This ensures exported functions are registered with R.
+// See corresponding C rust_gcatcirc_lib.code in `entrypoint.c`.
+extendr_module! {
+    mod trie;
+    impl CodeTrie;
+}
@@ -0,0 +1,729 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, BTreeSet};
+use std::hash::{Hash, Hasher};
+
+use crate::limits::{Budget, PathOptions, SearchLimits};
+
+/// An edge of the representing graph together with the word it originates from
+///
+/// A word `N1...Nn` induces the edges `[N1...Ni, Ni+1...Nn]` for `0 < i < n`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct WordEdge {
+    pub(crate) from: String,
+    pub(crate) to: String,
+    pub(crate) word: String,
+    /// The split position `i`
+    pub(crate) split: usize,
+}
+
+/// Returns all edges of the representing graph G(X)
+///
+/// # Arguments
+/// * `words` a set of words
+pub(crate) fn word_edges(words: &[String]) -> Vec<WordEdge> {
+    let mut edges = Vec::new();
+    for word in words {
+        let chars = word.chars().collect::<Vec<char>>();
+        for i in 1..chars.len() {
+            edges.push(WordEdge {
+                from: chars[..i].iter().collect(),
+                to: chars[i..].iter().collect(),
+                word: word.clone(),
+                split: i,
+            });
+        }
+    }
+
+    return edges;
+}
+
+/// Returns the edges of G(X) produced by splitting the words at position `i` (distinct, sorted)
+///
+/// These edges form the component C_i of the representing graph in the sense of
+/// Fimmel, Michel and Strüngmann, i.e. `[N1...Ni, Ni+1...Nn]` for all words `N1...Nn` with `n > i`.
+///
+/// # Arguments
+/// * `words` a set of words
+/// * `i` the split position
+pub(crate) fn edges_by_split(words: &[String], i: usize) -> Vec<WordEdge> {
+    let mut edges = word_edges(words).into_iter().filter(|e| e.split == i).collect::<Vec<WordEdge>>();
+    edges.sort();
+    edges.dedup();
+    return edges;
+}
+
+/// The edges gained and lost between two representing graphs
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct EdgeDiff {
+    /// The edges of the second graph which are not edges of the first graph (sorted)
+    pub(crate) gained: Vec<(String, String)>,
+    /// The edges of the first graph which are not edges of the second graph (sorted)
+    pub(crate) lost: Vec<(String, String)>,
+}
+
+/// Returns the edges gained and lost from G(X) to G(Y)
+///
+/// # Arguments
+/// * `before` the set of words X
+/// * `after` the set of words Y
+pub(crate) fn edge_difference(before: &[String], after: &[String]) -> EdgeDiff {
+    let edge_set = |words: &[String]| word_edges(words).into_iter().map(|e| (e.from, e.to)).collect::<BTreeSet<(String, String)>>();
+    let (before, after) = (edge_set(before), edge_set(after));
+
+    return EdgeDiff {
+        gained: after.difference(&before).cloned().collect(),
+        lost: before.difference(&after).cloned().collect(),
+    };
+}
+
+/// Returns the adjacency list of the representing graph G(X)
+///
+/// Every vertex is a key of the map, also vertices without outgoing edges.
+/// The successors of each vertex are sorted and distinct.
+///
+/// # Arguments
+/// * `words` a set of words
+pub(crate) fn adjacency(words: &[String]) -> BTreeMap<String, Vec<String>> {
+    trace_span!("graph_construction", words = words.len());
+    let mut adj: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for e in word_edges(words) {
+        adj.entry(e.to.clone()).or_default();
+        adj.entry(e.from).or_default().insert(e.to);
+    }
+
+    return adj.into_iter().map(|(v, s)| (v, s.into_iter().collect())).collect();
+}
+
+/// Returns the simple cycles of the representing graph G(X) found within the budget
+///
+/// Each cycle is a list of vertices `[v1, ..., vk]` with the edges `[v1, v2], ..., [vk, v1]`.
+/// Every cycle is reported exactly once and starts with its lexicographically smallest vertex.
+/// The number of cycles can grow exponentially, so there is no unbounded variant.
+/// If the budget is exhausted the result is partial, see [Budget::is_truncated].
+///
+/// # Arguments
+/// * `words` a set of words
+/// * `budget` the limits of the search
+pub(crate) fn simple_cycles_limited(words: &[String], budget: &mut Budget) -> Vec<Vec<String>> {
+    return simple_cycles_bounded(words, &PathOptions::default(), budget).0;
+}
+
+/// Returns the simple cycles of G(X) within the bounds `options` found within the budget
+///
+/// Returns the cycles and true if the search stopped at a cycle longer than
+/// [PathOptions::stop_above_length] (this cycle is the last one of the result).
+///
+/// # Arguments
+/// * `words` a set of words
+/// * `options` the bounds of the search
+/// * `budget` the limits of the search
+pub(crate) fn simple_cycles_bounded(words: &[String], options: &PathOptions, budget: &mut Budget) -> (Vec<Vec<String>>, bool) {
+    let mut cycles = Vec::new();
+    let mut exceeded = false;
+    let vertices = for_each_simple_cycle(words, options, budget, &mut |c| {
+        cycles.push(c.to_vec());
+        exceeded = options.stop_above_length.is_some_and(|l| c.len() > l);
+        return !exceeded;
+    });
+
+    return (cycles.into_iter().map(|c| c.into_iter().map(|i| vertices[i].clone()).collect()).collect(), exceeded);
+}
+
+/// Calls `visit` for each simple cycle of G(X) within the bounds found within the budget, without storing the cycles
+///
+/// The cycles are given as vertex indices, each cycle starts with its smallest index.
+/// The search stops if `visit` returns false. Returns the vertices of G(X) in the order of the indices.
+///
+/// # Arguments
+/// * `words` a set of words
+/// * `options` the bounds of the search
+/// * `budget` the limits of the search
+/// * `visit` called with each cycle
+fn for_each_simple_cycle(words: &[String], options: &PathOptions, budget: &mut Budget, visit: &mut dyn FnMut(&[usize]) -> bool) -> Vec<String> {
+    let adj = adjacency(words);
+    let vertices = adj.keys().cloned().collect::<Vec<String>>();
+    let index = vertices.iter().enumerate().map(|(i, v)| (v.clone(), i)).collect::<BTreeMap<String, usize>>();
+    let succ = vertices.iter().map(|v| adj[v].iter().map(|s| index[s]).collect()).collect::<Vec<Vec<usize>>>();
+    // The memory of a stored vertex label
+    let sizes = vertices.iter().map(|v| v.len() + std::mem::size_of::<String>()).collect::<Vec<usize>>();
+
+    // The fixed state of the search of the cycles through a start vertex
+    struct Search<'a> {
+        start: usize,
+        succ: &'a [Vec<usize>],
+        sizes: &'a [usize],
+        options: &'a PathOptions,
+    }
+
+    // Returns false if the search has to stop
+    fn dfs(search: &Search, v: usize, path: &mut Vec<usize>, on_path: &mut [bool], visit: &mut dyn FnMut(&[usize]) -> bool,
+           budget: &mut Budget) -> bool {
+        let (start, sizes, options) = (search.start, search.sizes, search.options);
+        let mut branches = 0;
+        for &w in &search.succ[v] {
+            if !budget.visit() {
+                return false;
+            }
+            if w == start {
+                let bytes = std::mem::size_of::<Vec<String>>() + path.iter().map(|v| sizes[*v]).sum::<usize>();
+                if !budget.found_sized(bytes) || !visit(path) {
+                    return false;
+                }
+            } else if w > start && !on_path[w] && options.max_depth.is_none_or(|d| path.len() < d) {
+                branches += 1;
+                if options.max_branches.is_some_and(|b| branches > b) {
+                    budget.mark_partial();
+                    break;
+                }
+                on_path[w] = true;
+                path.push(w);
+                let go_on = dfs(search, w, path, on_path, visit, budget);
+                path.pop();
+                on_path[w] = false;
+                if !go_on {
+                    return false;
+                }
+            }
+        }
+
+        return true;
+    }
+
+    trace_span!("cycle_search", vertices = vertices.len());
+    let mut found = 0;
+    let mut counting = |c: &[usize]| {
+        found += 1;
+        return visit(c);
+    };
+    let mut on_path = vec![false; vertices.len()];
+    for start in 0..vertices.len() {
+        on_path[start] = true;
+        let search = Search { start, succ: &succ, sizes: &sizes, options };
+        let go_on = dfs(&search, start, &mut vec![start], &mut on_path, &mut counting, budget);
+        on_path[start] = false;
+        if !go_on {
+            break;
+        }
+    }
+    trace_event!(cycles = found, nodes = budget.nodes(), truncated = budget.is_truncated());
+
+    return vertices;
+}
+
+/// Returns a shortest cycle of G(X) with at most `max_edges` edges
+///
+/// A breadth-first search from each vertex finds the shortest cycle through it, so unlike the
+/// enumeration of all cycles the run time is polynomial in the size of the graph.
+/// Returns `None` if there is no such cycle.
+///
+/// # Arguments
+/// * `words` a set of words
+/// * `max_edges` the maximal number of edges of the cycle
+pub(crate) fn shortest_cycle_within(words: &[String], max_edges: usize) -> Option<Vec<String>> {
+    let adj = adjacency(words);
+    let mut shortest: Option<Vec<String>> = None;
+    for start in adj.keys() {
+        let bound = shortest.as_ref().map_or(max_edges, |c| c.len() - 1);
+        let mut parent: BTreeMap<&String, &String> = BTreeMap::new();
+        let mut level = vec![start];
+        let mut depth = 0;
+        'search: while !level.is_empty() && depth < bound {
+            depth += 1;
+            let mut next = Vec::new();
+            for v in level {
+                for s in &adj[v] {
+                    if s == start {
+                        let mut cycle = vec![v.clone()];
+                        let mut u = v;
+                        while let Some(p) = parent.get(u) {
+                            cycle.push((*p).clone());
+                            u = p;
+                        }
+                        cycle.reverse();
+                        shortest = Some(cycle);
+                        break 'search;
+                    }
+                    if !parent.contains_key(s) {
+                        parent.insert(s, v);
+                        next.push(s);
+                    }
+                }
+            }
+            level = next;
+        }
+    }
+
+    return shortest;
+}
+
+/// The number of paths of a search and the distribution of their lengths
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct PathSummary {
+    /// The number of paths (saturating)
+    pub(crate) count: u128,
+    /// The number of paths by their number of edges
+    pub(crate) lengths: BTreeMap<usize, u128>,
+}
+
+/// Returns the number of simple cycles of G(X) and their lengths found within the budget
+///
+/// The length of a cycle is its number of edges (= vertices). The cycles are counted, not stored.
+///
+/// # Arguments
+/// * `words` a set of words
+/// * `budget` the limits of the search
+pub(crate) fn cycles_summary_limited(words: &[String], budget: &mut Budget) -> PathSummary {
+    let mut summary = PathSummary::default();
+    for_each_simple_cycle(words, &PathOptions::default(), budget, &mut |c| {
+        summary.count += 1;
+        *summary.lengths.entry(c.len()).or_insert(0) += 1;
+        return true;
+    });
+
+    return summary;
+}
+
+/// Returns the simple cycles of G(X) which start with the path `prefix`, found within the budget
+///
+/// Every cycle is reported exactly once and starts with `prefix`. A prefix with one vertex `v`
+/// yields the cycles through `v`, a prefix `[u, v]` the cycles through the edge `[u, v]`.
+/// A prefix which is not a path of G(X) yields no cycles.
+///
+/// # Arguments
+/// * `words` a set of words
+/// * `prefix` a path of G(X)
+/// * `budget` the limits of the search
+pub(crate) fn cycles_with_prefix_limited(words: &[String], prefix: &[String], budget: &mut Budget) -> Vec<Vec<String>> {
+    let adj = adjacency(words);
+    if prefix.is_empty() || prefix.iter().any(|v| !adj.contains_key(v)) || prefix.windows(2).any(|e| !adj[&e[0]].contains(&e[1])) {
+        return vec![];
+    }
+    // The loop [v, v] is the only cycle through itself
+    if prefix.len() == 2 && prefix[0] == prefix[1] {
+        return if budget.found() { vec![vec![prefix[0].clone()]] } else { vec![] };
+    }
+    let vertices = adj.keys().cloned().collect::<Vec<String>>();
+    let index = vertices.iter().enumerate().map(|(i, v)| (v.clone(), i)).collect::<BTreeMap<String, usize>>();
+    let succ = vertices.iter().map(|v| adj[v].iter().map(|s| index[s]).collect()).collect::<Vec<Vec<usize>>>();
+
+    fn dfs(start: usize, v: usize, succ: &[Vec<usize>], path: &mut Vec<usize>, on_path: &mut [bool], cycles: &mut Vec<Vec<usize>>, budget: &mut Budget) {
+        for &w in &succ[v] {
+            if !budget.visit() {
+                return;
+            }
+            if w == start {
+                if !budget.found() {
+                    return;
+                }
+                cycles.push(path.clone());
+            } else if !on_path[w] {
+                on_path[w] = true;
+                path.push(w);
+                dfs(start, w, succ, path, on_path, cycles, budget);
+                path.pop();
+                on_path[w] = false;
+            }
+        }
+    }
+
+    let mut path = prefix.iter().map(|v| index[v]).collect::<Vec<usize>>();
+    let mut on_path = vec![false; vertices.len()];
+    for &v in &path {
+        if on_path[v] {
+            return vec![];
+        }
+        on_path[v] = true;
+    }
+
+    trace_span!("cycle_search", vertices = vertices.len(), prefix = path.len());
+    let mut cycles = Vec::new();
+    let last = path[path.len() - 1];
+    dfs(path[0], last, &succ, &mut path, &mut on_path, &mut cycles, budget);
+    trace_event!(cycles = cycles.len(), nodes = budget.nodes(), truncated = budget.is_truncated());
+
+    return cycles.into_iter().map(|c| c.into_iter().map(|i| vertices[i].clone()).collect()).collect();
+}
+
+/// The longest paths of an acyclic G(X) as dynamic program
+struct LongestPathDag {
+    vertices: Vec<String>,
+    /// The predecessors of each vertex
+    pred: Vec<Vec<usize>>,
+    /// The number of edges of a longest path ending in each vertex
+    dist: Vec<usize>,
+    /// The number of edges of the longest paths
+    max: usize,
+}
+
+impl LongestPathDag {
+    /// Returns the dynamic program of G(X), `None` if G(X) contains a cycle or has no edges
+    fn new(words: &[String]) -> Option<LongestPathDag> {
+        return LongestPathDag::from_adjacency(&adjacency(words)).filter(|dag| dag.max > 0);
+    }
+
+    /// Returns the dynamic program of a graph, `None` if the graph contains a cycle
+    ///
+    /// Unlike [LongestPathDag::new] a graph without edges is accepted, its longest paths have no edges.
+    ///
+    /// # Arguments
+    /// * `adj` the successors of each vertex, every successor is a vertex
+    fn from_adjacency(adj: &BTreeMap<String, Vec<String>>) -> Option<LongestPathDag> {
+        let vertices = adj.keys().cloned().collect::<Vec<String>>();
+        let index = vertices.iter().enumerate().map(|(i, v)| (v.clone(), i)).collect::<BTreeMap<String, usize>>();
+        let mut pred: Vec<Vec<usize>> = vec![vec![]; vertices.len()];
+        let mut in_degree = vec![0; vertices.len()];
+        for (v, succ) in adj {
+            for s in succ {
+                pred[index[s]].push(index[v]);
+                in_degree[index[s]] += 1;
+            }
+        }
+
+        // Longest path ending in each vertex (Kahn's topological order)
+        let mut dist = vec![0usize; vertices.len()];
+        let mut order = Vec::with_capacity(vertices.len());
+        let mut queue = (0..vertices.len()).filter(|v| in_degree[*v] == 0).collect::<Vec<usize>>();
+        while let Some(v) = queue.pop() {
+            order.push(v);
+            for s in &adj[&vertices[v]] {
+                let s = index[s];
+                dist[s] = dist[s].max(dist[v] + 1);
+                in_degree[s] -= 1;
+                if in_degree[s] == 0 {
+                    queue.push(s);
+                }
+            }
+        }
+
+        let max = dist.iter().copied().max().unwrap_or(0);
+        if order.len() < vertices.len() {
+            return None;
+        }
+
+        return Some(LongestPathDag { vertices, pred, dist, max });
+    }
+
+    /// Returns the vertices which end a longest path
+    fn ends(&self) -> Vec<usize> {
+        return (0..self.vertices.len()).filter(|v| self.dist[*v] == self.max).collect();
+    }
+
+    /// Returns all longest paths found within the budget, each path is reported once
+    ///
+    /// A graph without edges has no longest paths.
+    ///
+    /// # Arguments
+    /// * `budget` the limits of the search
+    fn paths(&self, budget: &mut Budget) -> Vec<Vec<String>> {
+        fn collect(v: usize, pred: &[Vec<usize>], dist: &[usize], path: &mut Vec<usize>, paths: &mut Vec<Vec<usize>>, budget: &mut Budget) {
+            if !budget.visit() {
+                return;
+            }
+            if dist[v] == 0 {
+                if budget.found() {
+                    paths.push(path.iter().rev().copied().collect());
+                }
+                return;
+            }
+            for &u in &pred[v] {
+                if dist[u] + 1 == dist[v] {
+                    path.push(u);
+                    collect(u, pred, dist, path, paths, budget);
+                    path.pop();
+                }
+            }
+        }
+
+        if self.max == 0 {
+            return vec![];
+        }
+        trace_span!("longest_path_enumeration", vertices = self.vertices.len(), length = self.max);
+        let mut paths = Vec::new();
+        for end in self.ends() {
+            collect(end, &self.pred, &self.dist, &mut vec![end], &mut paths, budget);
+        }
+        trace_event!(paths = paths.len(), nodes = budget.nodes(), truncated = budget.is_truncated());
+
+        return paths.into_iter().map(|p| p.into_iter().map(|i| self.vertices[i].clone()).collect()).collect();
+    }
+}
+
+/// Returns a path of G(X) with a maximal sum of vertex weights
+///
+/// Unlike [longest_paths_limited] the paths are not compared by their number of edges but by the
+/// weights of their vertices, e.g. the number of letters of the represented sequence (see [represented_path_word]).
+/// Ties are resolved by the lexicographically smallest path. Returns `None` if G(X) contains a cycle or has no edges.
+///
+/// # Arguments
+/// * `words` a set of words
+/// * `weight` the weight of a vertex
+pub(crate) fn heaviest_path(words: &[String], weight: impl Fn(&str) -> usize) -> Option<Vec<String>> {
+    let adj = adjacency(words);
+    let mut best: BTreeMap<&String, (usize, Option<&String>)> = BTreeMap::new();
+    let mut in_degree: BTreeMap<&String, usize> = adj.keys().map(|v| (v, 0)).collect();
+    for s in adj.values().flatten() {
+        *in_degree.get_mut(s)? += 1;
+    }
+
+    // Heaviest path ending in each vertex (Kahn's topological order), predecessors are visited in lexicographic order
+    let mut queue = in_degree.iter().filter(|(_, d)| **d == 0).map(|(v, _)| *v).collect::<Vec<&String>>();
+    let mut visited = 0;
+    while let Some(v) = queue.pop() {
+        visited += 1;
+        let (w, _) = *best.entry(v).or_insert((weight(v), None));
+        for s in &adj[v] {
+            let candidate = w + weight(s);
+            let entry = best.entry(s).or_insert((weight(s), None));
+            if candidate > entry.0 || (candidate == entry.0 && entry.1.is_some_and(|p| v < p)) {
+                *entry = (candidate, Some(v));
+            }
+            let d = in_degree.get_mut(s)?;
+            *d -= 1;
+            if *d == 0 {
+                queue.push(s);
+            }
+        }
+    }
+    if visited < adj.len() || adj.values().all(|s| s.is_empty()) {
+        return None;
+    }
+
+    let (mut v, _) = best.iter().fold(None, |m: Option<(&String, usize)>, (v, (w, _))| match m {
+        Some((_, mw)) if mw >= *w => m,
+        _ => Some((*v, *w)),
+    })?;
+    let mut path = vec![v.clone()];
+    while let Some(Some(p)) = best.get(v).map(|b| b.1) {
+        path.push(p.clone());
+        v = p;
+    }
+    path.reverse();
+
+    return Some(path);
+}
+
+/// Returns all longest paths of G(X) found within the budget
+///
+/// Returns `None` if G(X) contains a cycle (the longest paths are not defined) or has no edges.
+///
+/// # Arguments
+/// * `words` a set of words
+/// * `budget` the limits of the search
+pub(crate) fn longest_paths_limited(words: &[String], budget: &mut Budget) -> Option<Vec<Vec<String>>> {
+    return Some(LongestPathDag::new(words)?.paths(budget));
+}
+
+/// Returns all longest paths of a graph given by its edges, `None` if the graph contains a cycle
+///
+/// Unlike [longest_paths_limited] the graph is not built from words, so it can be any sub graph, e.g. an
+/// i-component. The result is defined for all graphs: a graph without edges (e.g. without vertices or with a
+/// single vertex) has no longest paths, the list is empty. Each path is reported once.
+///
+/// # Arguments
+/// * `vertices` the vertices
+/// * `edges` the edges `(from, to)` between the vertices
+pub(crate) fn longest_paths_of_edges(vertices: &[String], edges: &[(String, String)]) -> Option<Vec<Vec<String>>> {
+    let mut adj: BTreeMap<String, BTreeSet<String>> = vertices.iter().map(|v| (v.clone(), BTreeSet::new())).collect();
+    for (from, to) in edges {
+        adj.entry(to.clone()).or_default();
+        adj.entry(from.clone()).or_default().insert(to.clone());
+    }
+    let adj = adj.into_iter().map(|(v, s)| (v, s.into_iter().collect())).collect();
+
+    return Some(LongestPathDag::from_adjacency(&adj)?.paths(&mut Budget::new(SearchLimits::default())));
+}
+
+/// Returns the number of longest paths of G(X) and their length without enumerating the paths
+///
+/// The paths are counted by dynamic programming over the topological order, so the run time is linear
+/// in the size of the graph even if the number of paths is exponential.
+/// Returns `None` if G(X) contains a cycle or has no edges.
+///
+/// # Arguments
+/// * `words` a set of words
+pub(crate) fn longest_paths_summary(words: &[String]) -> Option<PathSummary> {
+    let dag = LongestPathDag::new(words)?;
+
+    // The number of longest paths ending in each vertex, computed by increasing distance
+    let mut order = (0..dag.vertices.len()).collect::<Vec<usize>>();
+    order.sort_by_key(|v| dag.dist[*v]);
+    let mut count = vec![0u128; dag.vertices.len()];
+    for v in order {
+        count[v] = match dag.dist[v] {
+            0 => 1,
+            d => dag.pred[v].iter().filter(|u| dag.dist[**u] + 1 == d).fold(0u128, |c, u| c.saturating_add(count[*u])),
+        };
+    }
+
+    let total = dag.ends().iter().fold(0u128, |c, v| c.saturating_add(count[*v]));
+    return Some(PathSummary { count: total, lengths: BTreeMap::from([(dag.max, total)]) });
+}
+
+/// Returns the words represented by the edges of a cycle
+///
+/// The edge `[u, v]` is represented by the word `uv`.
+///
+/// # Arguments
+/// * `cycle` a cycle `[v1, ..., vk]`
+pub(crate) fn cycle_words(cycle: &[String]) -> Vec<String> {
+    return (0..cycle.len()).map(|i| format!("{}{}", cycle[i], cycle[(i + 1) % cycle.len()])).collect();
+}
+
+/// Returns the number of simple cycles of G(X) found within the budget each word participates in
+///
+/// A word participates in a cycle if one of its edges is part of the cycle (see [cycle_words]).
+/// The words are sorted by the number of cycles (decreasing) and then lexicographically.
+///
+/// # Arguments
+/// * `words` a set of words
+/// * `budget` the limits of the cycle search
+pub(crate) fn cycles_per_word(words: &[String], budget: &mut Budget) -> Vec<(String, usize)> {
+    let cycles = simple_cycles_limited(words, budget).iter().map(|c| cycle_words(c)).collect::<Vec<Vec<String>>>();
+    let mut counts = words.iter()
+        .map(|w| (w.clone(), cycles.iter().filter(|c| c.contains(w)).count()))
+        .collect::<Vec<(String, usize)>>();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    return counts;
+}
+
+/// The sequence represented by a path in G(X) and the two decompositions it certifies
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PathWord {
+    /// The concatenation of all vertices
+    pub(crate) word: String,
+    /// The words represented by the edges of the path
+    pub(crate) edge_words: Vec<String>,
+    /// The words of the edges 1, 3, 5, ...
+    pub(crate) first_decomposition: Vec<String>,
+    /// The words of the edges 2, 4, 6, ...
+    pub(crate) second_decomposition: Vec<String>,
+}
+
+/// Returns the sequence represented by a path (or cycle) of vertices
+///
+/// The path `[v1, ..., vk]` represents the sequence `v1...vk`. Its edges `[vi, vi+1]` represent the words `vivi+1`,
+/// so the sequence is read once with the words of the odd edges and once with the words of the even edges.
+/// For a cycle the closing edge `[vk, v1]` is included and the sequence is read circularly.
+///
+/// # Arguments
+/// * `path` a list of vertices
+/// * `cycle` true if the path is a cycle
+pub(crate) fn represented_path_word(path: &[String], cycle: bool) -> PathWord {
+    let edge_words = if cycle {
+        cycle_words(path)
+    } else {
+        path.windows(2).map(|e| format!("{}{}", e[0], e[1])).collect::<Vec<String>>()
+    };
+
+    return PathWord {
+        word: path.concat(),
+        first_decomposition: edge_words.iter().step_by(2).cloned().collect(),
+        second_decomposition: edge_words.iter().skip(1).step_by(2).cloned().collect(),
+        edge_words,
+    };
+}
+
+/// Returns the distinct paths in sorted order
+///
+/// Graphs with parallel edges report the same vertex sequence once per combination of edges.
+///
+/// # Arguments
+/// * `paths` a list of paths
+pub(crate) fn dedup_paths(paths: &[Vec<String>]) -> Vec<Vec<String>> {
+    let mut paths = paths.to_vec();
+    paths.sort();
+    paths.dedup();
+    return paths;
+}
+
+/// A cheap isomorphism invariant of a graph
+///
+/// Two graphs with different fingerprints are different (also up to isomorphism),
+/// equal fingerprints do not imply equal graphs. In contrast to comparing the vertex and
+/// edge lists, the fingerprint does not depend on the order in which the graph was constructed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct GraphFingerprint {
+    pub(crate) vertices: usize,
+    pub(crate) edges: usize,
+    /// The sorted sequence of (in-degree, out-degree) pairs
+    pub(crate) degrees: Vec<(usize, usize)>,
+    /// The cyclomatic number |E| - |V| + c of the underlying undirected graph with c components,
+    /// the graph is acyclic if it is 0
+    pub(crate) cycle_bound: usize,
+}
+
+impl GraphFingerprint {
+    /// Computes the fingerprint of a graph given by its vertices and (distinct) edges
+    ///
+    /// # Arguments
+    /// * `vertices` the vertices of the graph
+    /// * `edges` the edges `(from, to)` of the graph
+    pub(crate) fn new(vertices: &[String], edges: &[(String, String)]) -> GraphFingerprint {
+        let vertex_set = vertices.iter().chain(edges.iter().flat_map(|(a, b)| [a, b])).collect::<BTreeSet<&String>>();
+        let edge_set = edges.iter().collect::<BTreeSet<&(String, String)>>();
+        let index = vertex_set.iter().enumerate().map(|(i, v)| (*v, i)).collect::<BTreeMap<&String, usize>>();
+
+        let mut degrees = vec![(0, 0); index.len()];
+        let mut parent = (0..index.len()).collect::<Vec<usize>>();
+        fn find(parent: &mut [usize], mut i: usize) -> usize {
+            while parent[i] != i {
+                parent[i] = parent[parent[i]];
+                i = parent[i];
+            }
+
+            return i;
+        }
+
+        let mut components = index.len();
+        for (a, b) in &edge_set {
+            let (i, j) = (index[a], index[b]);
+            degrees[i].1 += 1;
+            degrees[j].0 += 1;
+            let (ri, rj) = (find(&mut parent, i), find(&mut parent, j));
+            if ri != rj {
+                parent[ri] = rj;
+                components -= 1;
+            }
+        }
+        degrees.sort();
+
+        return GraphFingerprint {
+            vertices: index.len(),
+            edges: edge_set.len(),
+            degrees,
+            cycle_bound: edge_set.len() + components - index.len(),
+        };
+    }
+
+    /// Returns a hash of the fingerprint
+    pub(crate) fn hash_value(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        return hasher.finish();
+    }
+}
+
+/// Graphs which have a fingerprint
+pub(crate) trait Fingerprint {
+    fn fingerprint(&self) -> GraphFingerprint;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edges(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        return pairs.iter().map(|(a, b)| (a.to_string(), b.to_string())).collect();
+    }
+
+    #[test]
+    fn longest_paths_of_cyclic_and_edgeless_graphs() {
+        let vertices = ["A", "C", "G"].map(String::from);
+        assert_eq!(longest_paths_of_edges(&vertices, &edges(&[("A", "C"), ("C", "G"), ("G", "A")])), None);
+        assert_eq!(longest_paths_of_edges(&vertices, &edges(&[("A", "A")])), None);
+        assert_eq!(longest_paths_of_edges(&vertices, &[]), Some(vec![]));
+        assert_eq!(longest_paths_of_edges(&[], &[]), Some(vec![]));
+        assert_eq!(longest_paths_of_edges(&vertices, &edges(&[("A", "C"), ("C", "G"), ("A", "G")])),
+                   Some(vec![vec!["A".to_string(), "C".to_string(), "G".to_string()]]));
+    }
+}